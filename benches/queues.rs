@@ -0,0 +1,319 @@
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use fbheap::{
+    arena::ArenaQueue, binomial::BinomialQueue, dary::DaryQueue, graph::dijkstra, heap::BareQueue,
+    pairing::PairingQueue, queue::PriorityQueue,
+};
+use std::{cmp::Reverse, collections::BinaryHeap, hint::black_box};
+
+/// numbers of elements every benchmark runs with
+const SIZES: [usize; 2] = [1_000, 10_000];
+
+/// deterministic pseudo random priorities, so that every run measures the same work
+fn priorities(count: usize, seed: u64) -> Vec<u64> {
+    let mut state = seed;
+    (0..count)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            state >> 33
+        })
+        .collect()
+}
+
+/// random graph in which every vertex has a few outgoing edges, for dijkstra like workloads
+fn graph(vertices: usize) -> Vec<Vec<(usize, u64)>> {
+    let targets = priorities(4 * vertices, 1);
+    let weights = priorities(4 * vertices, 2);
+    (0..vertices)
+        .map(|vertex| {
+            (4 * vertex..4 * vertex + 4)
+                .map(|edge| (targets[edge] as usize % vertices, weights[edge] % 100))
+                .collect()
+        })
+        .collect()
+}
+
+fn push_all(queue: &mut impl PriorityQueue<usize, u64>, priorities: &[u64]) {
+    for (value, &priority) in priorities.iter().enumerate() {
+        queue.push(value, priority).unwrap();
+    }
+}
+
+fn pop_all(queue: &mut impl PriorityQueue<usize, u64>) {
+    while let Ok(element) = queue.pop() {
+        black_box(element);
+    }
+}
+
+/// dijkstra on a binary heap, which cannot decrease keys and pushes duplicates instead
+fn binary_heap_dijkstra(adjacency: &[Vec<(usize, u64)>], source: usize) -> Vec<Option<u64>> {
+    let mut distances = vec![None; adjacency.len()];
+    let mut heap = BinaryHeap::from([Reverse((0, source))]);
+    while let Some(Reverse((distance, vertex))) = heap.pop() {
+        if distances[vertex].is_some() {
+            continue;
+        }
+        distances[vertex] = Some(distance);
+        for &(target, weight) in &adjacency[vertex] {
+            if distances[target].is_none() {
+                heap.push(Reverse((distance + weight, target)));
+            }
+        }
+    }
+    distances
+}
+
+/* # benchmarks */
+
+fn push(c: &mut Criterion) {
+    let mut group = c.benchmark_group("push");
+    for size in SIZES {
+        let priorities = priorities(size, 0);
+        group.bench_with_input(BenchmarkId::new("BareQueue", size), &priorities, |b, p| {
+            b.iter(|| push_all(&mut BareQueue::new(), p));
+        });
+        group.bench_with_input(BenchmarkId::new("ArenaQueue", size), &priorities, |b, p| {
+            b.iter(|| push_all(&mut ArenaQueue::new(), p));
+        });
+        group.bench_with_input(
+            BenchmarkId::new("PairingQueue", size),
+            &priorities,
+            |b, p| {
+                b.iter(|| push_all(&mut PairingQueue::new(), p));
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("BinomialQueue", size),
+            &priorities,
+            |b, p| {
+                b.iter(|| push_all(&mut BinomialQueue::new(), p));
+            },
+        );
+        group.bench_with_input(BenchmarkId::new("DaryQueue", size), &priorities, |b, p| {
+            b.iter(|| push_all(&mut DaryQueue::<_, _, 4>::new(), p));
+        });
+        group.bench_with_input(BenchmarkId::new("BinaryHeap", size), &priorities, |b, p| {
+            b.iter(|| {
+                let mut heap = BinaryHeap::new();
+                for (value, &priority) in p.iter().enumerate() {
+                    heap.push(Reverse((priority, value)));
+                }
+                heap
+            });
+        });
+    }
+    group.finish();
+}
+
+fn pop(c: &mut Criterion) {
+    let mut group = c.benchmark_group("pop");
+    for size in SIZES {
+        let priorities = priorities(size, 0);
+        group.bench_with_input(BenchmarkId::new("BareQueue", size), &priorities, |b, p| {
+            b.iter_batched_ref(
+                || {
+                    let mut queue = BareQueue::new();
+                    push_all(&mut queue, p);
+                    queue
+                },
+                pop_all,
+                BatchSize::LargeInput,
+            );
+        });
+        group.bench_with_input(
+            BenchmarkId::new("BareQueue into", size),
+            &priorities,
+            |b, p| {
+                b.iter_batched_ref(
+                    || {
+                        let mut queue = BareQueue::new();
+                        push_all(&mut queue, p);
+                        queue
+                    },
+                    |queue| {
+                        let mut slot = None;
+                        while queue.pop_min_into(&mut slot).unwrap() {
+                            black_box(&slot);
+                        }
+                    },
+                    BatchSize::LargeInput,
+                );
+            },
+        );
+        group.bench_with_input(BenchmarkId::new("ArenaQueue", size), &priorities, |b, p| {
+            b.iter_batched_ref(
+                || {
+                    let mut queue = ArenaQueue::new();
+                    push_all(&mut queue, p);
+                    queue
+                },
+                pop_all,
+                BatchSize::LargeInput,
+            );
+        });
+        group.bench_with_input(
+            BenchmarkId::new("PairingQueue", size),
+            &priorities,
+            |b, p| {
+                b.iter_batched_ref(
+                    || {
+                        let mut queue = PairingQueue::new();
+                        push_all(&mut queue, p);
+                        queue
+                    },
+                    pop_all,
+                    BatchSize::LargeInput,
+                );
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("BinomialQueue", size),
+            &priorities,
+            |b, p| {
+                b.iter_batched_ref(
+                    || {
+                        let mut queue = BinomialQueue::new();
+                        push_all(&mut queue, p);
+                        queue
+                    },
+                    pop_all,
+                    BatchSize::LargeInput,
+                );
+            },
+        );
+        group.bench_with_input(BenchmarkId::new("DaryQueue", size), &priorities, |b, p| {
+            b.iter_batched_ref(
+                || {
+                    let mut queue = DaryQueue::<_, _, 4>::new();
+                    push_all(&mut queue, p);
+                    queue
+                },
+                pop_all,
+                BatchSize::LargeInput,
+            );
+        });
+        group.bench_with_input(BenchmarkId::new("BinaryHeap", size), &priorities, |b, p| {
+            b.iter_batched_ref(
+                || {
+                    p.iter()
+                        .enumerate()
+                        .map(|(value, &priority)| Reverse((priority, value)))
+                        .collect::<BinaryHeap<_>>()
+                },
+                |heap| {
+                    while let Some(element) = heap.pop() {
+                        black_box(element);
+                    }
+                },
+                BatchSize::LargeInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+/// decreasing through handles, since decreasing by value searches the whole queue,
+/// against a binary heap which pushes the element again and skips the outdated copy later
+fn decrease(c: &mut Criterion) {
+    let mut group = c.benchmark_group("decrease");
+    for size in SIZES {
+        let cases = (priorities(size, 0), priorities(size, 3));
+        group.bench_with_input(BenchmarkId::new("BareQueue", size), &cases, |b, (p, d)| {
+            b.iter_batched_ref(
+                || {
+                    let mut queue = BareQueue::new();
+                    let handles: Vec<_> = p
+                        .iter()
+                        .enumerate()
+                        .map(|(value, &priority)| {
+                            queue.push_with_handle(value, priority + (1 << 31))
+                        })
+                        .collect();
+                    queue.pop().unwrap();
+                    (queue, handles)
+                },
+                |(queue, handles)| {
+                    for (handle, &priority) in handles.iter().zip(d.iter()) {
+                        black_box(queue.decrease_priority_at(handle, priority).ok());
+                    }
+                },
+                BatchSize::LargeInput,
+            );
+        });
+        group.bench_with_input(BenchmarkId::new("ArenaQueue", size), &cases, |b, (p, d)| {
+            b.iter_batched_ref(
+                || {
+                    let mut queue = ArenaQueue::new();
+                    let handles: Vec<_> = p
+                        .iter()
+                        .enumerate()
+                        .map(|(value, &priority)| {
+                            queue.push_with_handle(value, priority + (1 << 31)).unwrap()
+                        })
+                        .collect();
+                    queue.pop().unwrap();
+                    (queue, handles)
+                },
+                |(queue, handles)| {
+                    for (&handle, &priority) in handles.iter().zip(d.iter()) {
+                        black_box(queue.decrease_priority_at(handle, priority).ok());
+                    }
+                },
+                BatchSize::LargeInput,
+            );
+        });
+        group.bench_with_input(BenchmarkId::new("DaryQueue", size), &cases, |b, (p, d)| {
+            b.iter_batched_ref(
+                || {
+                    let mut queue = DaryQueue::<_, _, 4>::new();
+                    for (value, &priority) in p.iter().enumerate() {
+                        queue.push(value, priority + (1 << 31)).unwrap();
+                    }
+                    queue.pop().unwrap();
+                    queue
+                },
+                |queue| {
+                    for (value, &priority) in d.iter().enumerate() {
+                        black_box(queue.decrease_priority(&value, priority).ok());
+                    }
+                },
+                BatchSize::LargeInput,
+            );
+        });
+        group.bench_with_input(BenchmarkId::new("BinaryHeap", size), &cases, |b, (p, d)| {
+            b.iter_batched_ref(
+                || {
+                    p.iter()
+                        .enumerate()
+                        .map(|(value, &priority)| Reverse((priority + (1 << 31), value)))
+                        .collect::<BinaryHeap<_>>()
+                },
+                |heap| {
+                    for (value, &priority) in d.iter().enumerate() {
+                        heap.push(Reverse((priority, value)));
+                    }
+                },
+                BatchSize::LargeInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+fn shortest_paths(c: &mut Criterion) {
+    let mut group = c.benchmark_group("dijkstra");
+    for size in SIZES {
+        let adjacency = graph(size);
+        group.bench_with_input(BenchmarkId::new("BareQueue", size), &adjacency, |b, a| {
+            b.iter(|| dijkstra(a, 0).unwrap());
+        });
+        group.bench_with_input(BenchmarkId::new("BinaryHeap", size), &adjacency, |b, a| {
+            b.iter(|| binary_heap_dijkstra(a, 0));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, push, pop, decrease, shortest_paths);
+criterion_main!(benches);
@@ -0,0 +1,18 @@
+#![no_main]
+
+use fbheap::{heap::BareQueue, model::Op, policy::Consolidation};
+use libfuzzer_sys::fuzz_target;
+
+// the first byte picks when to consolidate, the rest is decoded into operations
+fuzz_target!(|bytes: &[u8]| {
+    let Some((&strategy, bytes)) = bytes.split_first() else {
+        return;
+    };
+    let consolidation = match strategy % 4 {
+        0 => Consolidation::Eager,
+        1 => Consolidation::Adaptive,
+        factor => Consolidation::Lazy(usize::from(factor - 1)),
+    };
+    let mut queue = BareQueue::with_consolidation(consolidation);
+    queue.apply_ops(&Op::decode(bytes));
+});
@@ -0,0 +1,783 @@
+use crate::{
+    error::Error,
+    queue::PriorityQueue,
+    slab::{Slab, SlabNode},
+};
+use alloc::vec::Vec;
+use core::{
+    mem::{swap, take},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// identity given to the next queue handing out handles, zero being left for queues which have none
+static NEXT_IDENTITY: AtomicUsize = AtomicUsize::new(1);
+
+/* # arena queue */
+
+/// node stored in a slot of the arena, linked to other nodes by index
+struct Slot<T, Priority> {
+    /// held value
+    t: T,
+    /// priority of the held value
+    priority: Priority,
+    /// index of the parent node in the tree structure
+    parent: Option<u32>,
+    /// indices of children in the tree structure
+    children: Vec<u32>,
+    /// flag for whether this node has lost any children already
+    marked: bool,
+}
+
+impl<T, Priority> Slot<T, Priority> {
+    /// create ampty node
+    const fn new(t: T, priority: Priority) -> Self {
+        Self {
+            t,
+            priority,
+            parent: None,
+            children: Vec::new(),
+            marked: false,
+        }
+    }
+}
+
+impl<T, Priority> SlabNode for Slot<T, Priority>
+where
+    Priority: Ord,
+{
+    type Priority = Priority;
+
+    fn priority(&self) -> &Priority {
+        &self.priority
+    }
+}
+
+/**
+reference to a single element of an `ArenaQueue`, tagged with the queue which gave it out
+and the generation of its slot
+
+a slot is reused once its element leaves the queue, but its generation changes on every release,
+so a handle to an element that was popped or deleted is told apart from one to the new occupant,
+while a slot whose generation would wrap around is retired instead of being reused
+*/
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ArenaHandle {
+    /// identity of the queue which gave out the handle
+    queue: usize,
+    /// index of the slot holding the element
+    index: u32,
+    /// generation of the slot when the element was pushed
+    generation: u32,
+}
+
+/**
+fibonacci queue storing its nodes in a single vector, linked by indices instead of pointers
+
+it offers the same operations as the `BareQueue`, without reference counting,
+and can be sent across threads whenever its contents can
+
+```
+use fbheap::arena::ArenaQueue;
+use fbheap::error::Error::Empty;
+
+let mut queue = ArenaQueue::new();
+queue.push("i was first", 3);
+queue.push("i am important", 1);
+queue.push("i was not important at first", 4);
+assert_eq!(queue.pop(), Ok(("i am important", 1)));
+queue.decrease_priority(&"i was not important at first", 2);
+
+let mut queue = std::thread::spawn(move || {
+    assert_eq!(queue.pop(), Ok(("i was not important at first", 2)));
+    queue
+})
+.join()
+.unwrap();
+
+assert_eq!(queue.pop(), Ok(("i was first", 3)));
+assert!(queue.is_empty());
+assert_eq!(queue.pop(), Err(Empty));
+```
+
+since it holds no reference counted pointers, it can also be shared from a static
+
+```
+use fbheap::arena::ArenaQueue;
+use std::sync::Mutex;
+
+static JOBS: Mutex<ArenaQueue<&str, u32>> = Mutex::new(ArenaQueue::new());
+
+std::thread::spawn(|| JOBS.lock().unwrap().push("reindex", 2))
+    .join()
+    .unwrap()?;
+JOBS.lock().unwrap().push("backup", 1)?;
+assert_eq!(JOBS.lock().unwrap().pop(), Ok(("backup", 1)));
+# Ok::<(), fbheap::error::Error>(())
+```
+*/
+pub struct ArenaQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /// storage for nodes, vacant slots are reused by later pushes
+    slots: Slab<Slot<T, Priority>>,
+    /// generation of every slot, advanced whenever the slot is released
+    generations: Vec<u32>,
+    /// indices of roots
+    roots: Vec<u32>,
+    /// index of the node with the lowest priority, it such exists
+    first: Option<u32>,
+    /// number of nodes in the queue
+    node_count: usize,
+    /// identity shared by all handles given out by the queue, zero until the first one is
+    identity: usize,
+}
+
+impl<T, Priority> Default for ArenaQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, Priority> ArenaQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /* # helper functions */
+
+    /* ## slot functions */
+
+    fn allocate(&mut self, slot: Slot<T, Priority>) -> Result<u32, Error> {
+        let index = self.slots.allocate(slot)?;
+        if index as usize == self.generations.len() {
+            self.generations.push(0);
+        }
+        Ok(index)
+    }
+
+    fn release(&mut self, index: u32) -> Result<Slot<T, Priority>, Error> {
+        let slot = self.slots.take(index)?;
+        // a slot which ran out of generations is never reused, so that no stale handle reaches it
+        let generation = &mut self.generations[index as usize];
+        if let Some(next) = generation.checked_add(1) {
+            *generation = next;
+            self.slots.vacate(index);
+        }
+        Ok(slot)
+    }
+
+    /// identity of the queue, drawn when it gives out its first handle
+    fn identity(&mut self) -> Result<usize, Error> {
+        if self.identity == 0 {
+            self.identity = NEXT_IDENTITY
+                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |next| {
+                    next.checked_add(1)
+                })
+                .map_err(|_| Error::ReachedCapacity)?;
+        }
+        Ok(self.identity)
+    }
+
+    /// index of the slot a handle points to, provided its element is still there
+    fn handle_index(&self, handle: ArenaHandle) -> Result<u32, Error> {
+        if self.identity == 0 || handle.queue != self.identity {
+            return Err(Error::NotFound("handle"));
+        }
+        match self.generations.get(handle.index as usize) {
+            Some(&generation)
+                if generation == handle.generation && self.slots.get(handle.index).is_ok() =>
+            {
+                Ok(handle.index)
+            }
+            Some(_) => Err(Error::StaleHandle),
+            None => Err(Error::NotFound("handle")),
+        }
+    }
+
+    /* ## first element functions */
+
+    fn find_first(&self) -> Result<Option<u32>, Error> {
+        let mut first = None;
+        for &root in &self.roots {
+            match first {
+                Some(index) if !self.slots.is_lower(root, index)? => {}
+                _ => first = Some(root),
+            }
+        }
+        Ok(first)
+    }
+
+    /// make the node first, if it has lower priority than the current first one
+    fn offer_first(&mut self, index: u32) -> Result<(), Error> {
+        match self.first {
+            Some(first) if !self.slots.is_lower(index, first)? => {}
+            _ => self.first = Some(index),
+        }
+        Ok(())
+    }
+
+    /* ## root functions */
+
+    fn remove_root(&mut self, index: u32) -> Result<(), Error> {
+        let position = self
+            .roots
+            .iter()
+            .position(|&root| root == index)
+            .ok_or(Error::NotFound("node"))?;
+        self.roots.swap_remove(position);
+        Ok(())
+    }
+
+    /* ## structural functions */
+
+    fn link(&mut self, parent: u32, child: u32) -> Result<(), Error> {
+        self.slots.get_mut(child)?.parent = Some(parent);
+        let parent = self.slots.get_mut(parent)?;
+        parent.children.push(child);
+        parent.marked = false;
+        Ok(())
+    }
+
+    /// link roots of equal rank until all ranks differ, returning the root with the lowest priority
+    fn consolidate(&mut self) -> Result<Option<u32>, Error> {
+        let mut ranks: Vec<Option<u32>> = Vec::new();
+
+        for mut root in take(&mut self.roots) {
+            loop {
+                let rank = self.slots.get(root)?.children.len();
+                if rank >= ranks.len() {
+                    ranks.resize(rank + 1, None);
+                }
+                let Some(mut node) = ranks[rank].take() else {
+                    ranks[rank] = Some(root);
+                    break;
+                };
+                // keep the smaller of the two as the root of the linked tree
+                if self.slots.is_lower(node, root)? {
+                    swap(&mut root, &mut node);
+                }
+                self.link(root, node)?;
+            }
+        }
+
+        let mut first = None;
+        for root in ranks.into_iter().flatten() {
+            match first {
+                Some(index) if !self.slots.is_lower(root, index)? => {}
+                _ => first = Some(root),
+            }
+            self.roots.push(root);
+        }
+        Ok(first)
+    }
+
+    /// separate node from its parent and add it to the list of roots,
+    /// continuing with every ancestor which has already lost a child
+    fn cut_node(&mut self, index: u32) -> Result<(), Error> {
+        let mut node = index;
+        while let Some(parent) = self.slots.get(node)?.parent {
+            let slot = self.slots.get_mut(node)?;
+            slot.parent = None;
+            slot.marked = false;
+
+            let siblings = &mut self.slots.get_mut(parent)?.children;
+            let position = siblings
+                .iter()
+                .position(|&child| child == node)
+                .ok_or(Error::NotFound("node"))?;
+            siblings.swap_remove(position);
+            self.roots.push(node);
+
+            let parent_slot = self.slots.get_mut(parent)?;
+            if parent_slot.marked {
+                node = parent;
+            } else {
+                parent_slot.marked = true;
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// move all children of a node to the list of roots
+    fn promote_children(&mut self, index: u32) -> Result<(), Error> {
+        for child in take(&mut self.slots.get_mut(index)?.children) {
+            let slot = self.slots.get_mut(child)?;
+            slot.parent = None;
+            slot.marked = false;
+            self.roots.push(child);
+        }
+        Ok(())
+    }
+
+    /// detach a node from the queue, leaving it without parent or children
+    fn remove_node(&mut self, index: u32) -> Result<(), Error> {
+        self.cut_node(index)?;
+        self.promote_children(index)?;
+        self.remove_root(index)?;
+        self.node_count = self.node_count.checked_sub(1).ok_or(Error::Empty)?;
+        if self.first == Some(index) {
+            self.first = self.find_first()?;
+        }
+        Ok(())
+    }
+
+    fn decrease_node(&mut self, index: u32, priority: Priority) -> Result<(), Error> {
+        self.slots.get_mut(index)?.priority = priority;
+        match self.slots.get(index)?.parent {
+            Some(parent) if self.slots.is_lower(index, parent)? => self.cut_node(index)?,
+            _ => {}
+        }
+        if self.slots.get(index)?.parent.is_none() {
+            self.offer_first(index)?;
+        }
+        Ok(())
+    }
+
+    fn increase_node(&mut self, index: u32, priority: Priority) -> Result<(), Error> {
+        // the node may now be bigger than its children, so it has to be isolated
+        self.cut_node(index)?;
+        self.promote_children(index)?;
+        self.slots.get_mut(index)?.priority = priority;
+        if self.first == Some(index) {
+            self.first = self.find_first()?;
+        }
+        Ok(())
+    }
+
+    fn get_node(&self, t: &T) -> Option<u32> {
+        self.slots.position(|slot| slot.t == *t)
+    }
+
+    /* # heap functionality */
+
+    /// construct empty queue
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            slots: Slab::new(),
+            generations: Vec::new(),
+            roots: Vec::new(),
+            first: None,
+            node_count: 0,
+            identity: 0,
+        }
+    }
+
+    /// returns true if the queue is empty
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.node_count == 0
+    }
+
+    /// returns the number of elements in the queue
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.node_count
+    }
+
+    /**
+    push a value onto the queue with given priority
+
+    # Errors
+    will error if the queue is already at capacity
+    */
+    pub fn push(&mut self, t: T, priority: Priority) -> Result<(), Error> {
+        let index = self.allocate(Slot::new(t, priority))?;
+        self.node_count = self
+            .node_count
+            .checked_add(1)
+            .ok_or(Error::ReachedCapacity)?;
+        self.roots.push(index);
+        self.offer_first(index)
+    }
+
+    /**
+    return the element with the lowest priority
+
+    # Errors
+    Empty => cannot return element from empty queue\n
+    NotFound => internal indexing error
+    */
+    pub fn pop(&mut self) -> Result<(T, Priority), Error> {
+        let first = self.first.ok_or(Error::Empty)?;
+        self.remove_root(first)?;
+        self.promote_children(first)?;
+        self.node_count = self.node_count.checked_sub(1).ok_or(Error::Empty)?;
+
+        self.first = self.consolidate()?;
+
+        let slot = self.release(first)?;
+        Ok((slot.t, slot.priority))
+    }
+
+    /**
+    decreases the priority of the item with given value
+
+    # Errors
+    NotFound => index with given value was not found in the queue\n
+    CannotIncreasePriority => the give prioprity is higher than the current one for the index of that value
+    */
+    pub fn decrease_priority(&mut self, value: &T, new_priority: Priority) -> Result<(), Error> {
+        let index = self.get_node(value).ok_or(Error::NotFound("value"))?;
+        if self.slots.get(index)?.priority > new_priority {
+            self.decrease_node(index, new_priority)
+        } else {
+            Err(Error::CannotIncreasePriority {
+                current_was_lower: self.slots.get(index)?.priority < new_priority,
+            })
+        }
+    }
+
+    /**
+    increases the priority of the item with given value
+
+    # Errors
+    NotFound => index with given value was not found in the queue\n
+    CannotDecreasePriority => the given priority is lower than the current one for the index of that value
+    */
+    pub fn increase_priority(&mut self, value: &T, new_priority: Priority) -> Result<(), Error> {
+        let index = self.get_node(value).ok_or(Error::NotFound("value"))?;
+        if self.slots.get(index)?.priority < new_priority {
+            self.increase_node(index, new_priority)
+        } else {
+            Err(Error::CannotDecreasePriority {
+                current_was_higher: self.slots.get(index)?.priority > new_priority,
+            })
+        }
+    }
+
+    /**
+    changes the priority of the item with given value, in whichever direction
+
+    # Errors
+    NotFound => index with given value was not found in the queue
+    */
+    pub fn update_priority(&mut self, value: &T, new_priority: Priority) -> Result<(), Error> {
+        let index = self.get_node(value).ok_or(Error::NotFound("value"))?;
+        if self.slots.get(index)?.priority > new_priority {
+            self.decrease_node(index, new_priority)
+        } else if self.slots.get(index)?.priority < new_priority {
+            self.increase_node(index, new_priority)
+        } else {
+            Ok(())
+        }
+    }
+
+    /**
+    remove the item with given value from the queue, regardless of its priority
+
+    # Errors
+    NotFound => index with given value was not found in the queue
+    */
+    pub fn delete(&mut self, value: &T) -> Result<(T, Priority), Error> {
+        let index = self.get_node(value).ok_or(Error::NotFound("value"))?;
+        self.remove_node(index)?;
+        let slot = self.release(index)?;
+        Ok((slot.t, slot.priority))
+    }
+
+    /**
+    move all elements of another queue into this one,
+    which takes time proportional to the size of the other queue, since its indices are shifted,
+    so handles given out by the other queue are not accepted afterwards
+
+    ```
+    use fbheap::arena::ArenaQueue;
+    use fbheap::error::Error::NotFound;
+
+    let mut queue = ArenaQueue::new();
+    let kept = queue.push_with_handle("kept", 2).unwrap();
+    let mut other = ArenaQueue::new();
+    let moved = other.push_with_handle("moved", 3).unwrap();
+    queue.meld(other).unwrap();
+    assert_eq!(queue.decrease_priority_at(moved, 1), Err(NotFound("handle")));
+    queue.decrease_priority_at(kept, 1).unwrap();
+    assert_eq!(queue.pop(), Ok(("kept", 1)));
+    ```
+
+    # Errors
+    will error if the combined queue would exceed capacity
+    */
+    pub fn meld(&mut self, other: Self) -> Result<(), Error> {
+        let node_count = self
+            .node_count
+            .checked_add(other.node_count)
+            .ok_or(Error::ReachedCapacity)?;
+        let offset = self.slots.append(other.slots, |slot, offset| {
+            slot.parent = slot.parent.map(|index| index + offset);
+            slot.children.iter_mut().for_each(|child| *child += offset);
+        })?;
+        self.node_count = node_count;
+
+        let shift = |index: u32| index + offset;
+        self.generations.extend(other.generations);
+        self.roots.extend(other.roots.iter().copied().map(shift));
+        if let Some(first) = other.first {
+            self.offer_first(shift(first))?;
+        }
+        Ok(())
+    }
+
+    /* # handle functionality */
+
+    /**
+    push a value onto the queue with given priority, returning a handle to it
+
+    ```
+    use fbheap::arena::ArenaQueue;
+    use fbheap::error::Error::StaleHandle;
+
+    let mut queue = ArenaQueue::new();
+    let popped = queue.push_with_handle("popped", 0).unwrap();
+    let kept = queue.push_with_handle("kept", 2).unwrap();
+    assert_eq!(queue.pop(), Ok(("popped", 0)));
+
+    // the slot of the popped element is reused, but the old handle does not reach the new one
+    let reused = queue.push_with_handle("reused", 3).unwrap();
+    assert_eq!(queue.decrease_priority_at(popped, 1), Err(StaleHandle));
+    queue.decrease_priority_at(reused, 1).unwrap();
+    assert_eq!(queue.delete_at(kept), Ok(("kept", 2)));
+    assert_eq!(queue.delete_at(kept), Err(StaleHandle));
+    assert_eq!(queue.pop(), Ok(("reused", 1)));
+    ```
+
+    # Errors
+    will error if the queue is already at capacity, or no identities are left for new queues
+    */
+    pub fn push_with_handle(&mut self, t: T, priority: Priority) -> Result<ArenaHandle, Error> {
+        let queue = self.identity()?;
+        self.push(t, priority)?;
+        let index = self.roots.last().copied().ok_or(Error::NotFound("node"))?;
+        Ok(ArenaHandle {
+            queue,
+            index,
+            generation: self.generations[index as usize],
+        })
+    }
+
+    /**
+    decreases the priority of the item the handle points to
+
+    # Errors
+    StaleHandle => the element the handle points to is no longer in the queue\n
+    NotFound => the handle was not given out by this queue, or by a queue melded into it\n
+    CannotIncreasePriority => the give prioprity is higher than the current one for the index of that value
+    */
+    pub fn decrease_priority_at(
+        &mut self,
+        handle: ArenaHandle,
+        new_priority: Priority,
+    ) -> Result<(), Error> {
+        let index = self.handle_index(handle)?;
+        if self.slots.get(index)?.priority > new_priority {
+            self.decrease_node(index, new_priority)
+        } else {
+            Err(Error::CannotIncreasePriority {
+                current_was_lower: self.slots.get(index)?.priority < new_priority,
+            })
+        }
+    }
+
+    /**
+    remove the item the handle points to from the queue, regardless of its priority
+
+    # Errors
+    StaleHandle => the element the handle points to is no longer in the queue\n
+    NotFound => the handle was not given out by this queue, or by a queue melded into it
+    */
+    pub fn delete_at(&mut self, handle: ArenaHandle) -> Result<(T, Priority), Error> {
+        let index = self.handle_index(handle)?;
+        self.remove_node(index)?;
+        let slot = self.release(index)?;
+        Ok((slot.t, slot.priority))
+    }
+}
+
+/* # priority queue */
+
+impl<T, Priority> PriorityQueue<T, Priority> for ArenaQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    fn push(&mut self, t: T, priority: Priority) -> Result<(), Error> {
+        Self::push(self, t, priority)
+    }
+
+    fn pop(&mut self) -> Result<(T, Priority), Error> {
+        Self::pop(self)
+    }
+
+    fn peek(&self) -> Option<(T, Priority)>
+    where
+        T: Clone,
+        Priority: Clone,
+    {
+        let slot = self.slots.get(self.first?).ok()?;
+        Some((slot.t.clone(), slot.priority.clone()))
+    }
+
+    fn decrease_priority(&mut self, value: &T, new_priority: Priority) -> Result<(), Error> {
+        Self::decrease_priority(self, value, new_priority)
+    }
+
+    fn delete(&mut self, value: &T) -> Result<(T, Priority), Error> {
+        Self::delete(self, value)
+    }
+
+    fn is_empty(&self) -> bool {
+        Self::is_empty(self)
+    }
+
+    fn len(&self) -> usize {
+        Self::len(self)
+    }
+
+    fn meld(&mut self, other: Self) -> Result<(), Error> {
+        Self::meld(self, other)
+    }
+}
+
+/* # serialization */
+
+/// handles are serialized as the triple of their queue, index and generation,
+/// and only ever point into the queue which gave them out, never into one deserialized from it
+#[cfg(feature = "serde")]
+impl serde::Serialize for ArenaHandle {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde::Serialize::serialize(&(self.queue, self.index, self.generation), serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ArenaHandle {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (queue, index, generation): (usize, u32, u32) =
+            serde::Deserialize::deserialize(deserializer)?;
+        Ok(Self {
+            queue,
+            index,
+            generation,
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T, Priority> serde::Serialize for Slot<T, Priority>
+where
+    T: serde::Serialize,
+    Priority: serde::Serialize,
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let slot = (
+            &self.t,
+            &self.priority,
+            self.parent,
+            &self.children,
+            self.marked,
+        );
+        serde::Serialize::serialize(&slot, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T, Priority> serde::Deserialize<'de> for Slot<T, Priority>
+where
+    T: serde::Deserialize<'de>,
+    Priority: serde::Deserialize<'de>,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (t, priority, parent, children, marked) =
+            serde::Deserialize::deserialize(deserializer)?;
+        Ok(Self {
+            t,
+            priority,
+            parent,
+            children,
+            marked,
+        })
+    }
+}
+
+/// queues are serialized with their slots, generations and roots as they are,
+/// so that the structure of the trees is kept, but not with their identity,
+/// since identities are only unique within a single run of a program
+#[cfg(feature = "serde")]
+impl<T, Priority> serde::Serialize for ArenaQueue<T, Priority>
+where
+    T: Eq + serde::Serialize,
+    Priority: Ord + serde::Serialize,
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let queue = (self.slots.as_slice(), &self.generations, &self.roots);
+        serde::Serialize::serialize(&queue, serializer)
+    }
+}
+
+/// queues are rebuilt from their slots, generations and roots,
+/// which are rejected unless they form a forest ordered by priority,
+/// and draw a fresh identity, so that handles given out before serializing are not accepted
+#[cfg(feature = "serde")]
+impl<'de, T, Priority> serde::Deserialize<'de> for ArenaQueue<T, Priority>
+where
+    T: Eq + serde::Deserialize<'de>,
+    Priority: Ord + serde::Deserialize<'de>,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error as _;
+
+        let (slots, generations, roots) = serde::Deserialize::deserialize(deserializer)?;
+        let mut queue = Self {
+            slots: Slab::from_slots(slots),
+            generations,
+            roots,
+            first: None,
+            node_count: 0,
+            identity: 0,
+        };
+        if queue.slots.len() != queue.generations.len() || u32::try_from(queue.slots.len()).is_err()
+        {
+            return Err(D::Error::custom("slots and generations differ in length"));
+        }
+
+        // walk the trees from their roots, visiting every node exactly once
+        let mut visited = alloc::vec![false; queue.slots.len()];
+        let mut nodes: Vec<(u32, Option<u32>)> =
+            queue.roots.iter().map(|&root| (root, None)).collect();
+        while let Some((index, parent)) = nodes.pop() {
+            let slot = queue
+                .slots
+                .get(index)
+                .map_err(|_| D::Error::custom("link to a vacant slot"))?;
+            if slot.parent != parent || core::mem::replace(&mut visited[index as usize], true) {
+                return Err(D::Error::custom("nodes do not form a forest"));
+            }
+            for &child in &slot.children {
+                if queue.slots.is_lower(child, index).unwrap_or(false) {
+                    return Err(D::Error::custom("child has lower priority than its parent"));
+                }
+                nodes.push((child, Some(index)));
+            }
+        }
+
+        for (index, visited) in visited.into_iter().enumerate() {
+            match (queue.slots.get(index as u32).ok(), visited) {
+                (Some(_), false) => return Err(D::Error::custom("node unreachable from roots")),
+                (Some(_), true) => queue.node_count += 1,
+                // slots which ran out of generations stay retired
+                (None, _) if queue.generations[index] == u32::MAX => {}
+                // the length was checked to fit above
+                (None, _) => queue.slots.vacate(index as u32),
+            }
+        }
+        queue.first = queue.find_first().map_err(D::Error::custom)?;
+        Ok(queue)
+    }
+}
@@ -0,0 +1,366 @@
+use crate::{error::Error, queue::PriorityQueue};
+use alloc::{
+    rc::{Rc, Weak},
+    vec::Vec,
+};
+use core::{cell::RefCell, mem::swap};
+
+/* # binomial queue */
+
+/// binomial tree, shared so that children can refer back to their parent
+type Tree<T, Priority> = Rc<RefCell<Node<T, Priority>>>;
+
+/// node of a binomial tree
+struct Node<T, Priority> {
+    /// held value
+    t: T,
+    /// priority of the held value
+    priority: Priority,
+    /// parent node in the tree structure, weak so that parents and children do not keep each other alive
+    parent: Option<Weak<RefCell<Self>>>,
+    /// children in the tree structure, the child at every index being the root of a tree of that rank
+    children: Vec<Tree<T, Priority>>,
+}
+
+impl<T, Priority> Node<T, Priority>
+where
+    Priority: Ord,
+{
+    /// create tree of a single node
+    fn new_tree(t: T, priority: Priority) -> Tree<T, Priority> {
+        Rc::new(RefCell::new(Self {
+            t,
+            priority,
+            parent: None,
+            children: Vec::new(),
+        }))
+    }
+
+    /// link two trees of equal rank into one of the next rank, keeping the smaller root on top
+    fn link(one: Tree<T, Priority>, other: Tree<T, Priority>) -> Tree<T, Priority> {
+        let (parent, child) = if other.borrow().priority < one.borrow().priority {
+            (other, one)
+        } else {
+            (one, other)
+        };
+        child.borrow_mut().parent = Some(Rc::downgrade(&parent));
+        parent.borrow_mut().children.push(child);
+        parent
+    }
+}
+
+/**
+binomial queue, which keeps at most one tree of every rank, like the digits of a binary number
+
+unlike the fibonacci queues it never defers any work, so that every operation other than a lookup
+takes logarithmic time in the worst case, not just on average, which suits callers that cannot
+tolerate an occasional expensive pop
+
+```
+use fbheap::binomial::BinomialQueue;
+use fbheap::error::Error::Empty;
+
+let mut queue = BinomialQueue::new();
+queue.push("i was first", 3);
+queue.push("i am important", 1);
+queue.push("i was not important at first", 4);
+assert_eq!(queue.pop(), Ok(("i am important", 1)));
+queue.decrease_priority(&"i was not important at first", 2);
+assert_eq!(queue.pop(), Ok(("i was not important at first", 2)));
+
+let mut other = BinomialQueue::new();
+other.push("i came from elsewhere", 0);
+queue.meld(other);
+assert_eq!(queue.pop(), Ok(("i came from elsewhere", 0)));
+assert_eq!(queue.pop(), Ok(("i was first", 3)));
+assert!(queue.is_empty());
+assert_eq!(queue.pop(), Err(Empty));
+```
+*/
+pub struct BinomialQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /// roots of the trees, the tree at every index having that rank
+    roots: Vec<Option<Tree<T, Priority>>>,
+    /// number of nodes in the queue
+    node_count: usize,
+}
+
+impl<T, Priority> Default for BinomialQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, Priority> BinomialQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /* # helper functions */
+
+    /// add trees given by rank to the queue, carrying linked trees over to the next rank
+    fn merge_roots(&mut self, trees: Vec<Option<Tree<T, Priority>>>) {
+        let mut trees = trees.into_iter();
+        let mut carry = None;
+        for rank in 0.. {
+            let incoming = match trees.next() {
+                Some(tree) => tree,
+                None if carry.is_none() => break,
+                None => None,
+            };
+            if rank == self.roots.len() {
+                self.roots.push(None);
+            }
+            let mut present = [self.roots[rank].take(), incoming, carry.take()]
+                .into_iter()
+                .flatten();
+            match (present.next(), present.next(), present.next()) {
+                (Some(one), Some(other), kept) => {
+                    self.roots[rank] = kept;
+                    carry = Some(Node::link(one, other));
+                }
+                (kept, _, _) => self.roots[rank] = kept,
+            }
+        }
+        while self.roots.last().is_some_and(Option::is_none) {
+            self.roots.pop();
+        }
+    }
+
+    /// rank of the tree whose root has the lowest priority
+    fn first_rank(&self) -> Option<usize> {
+        let mut first: Option<(usize, &Tree<T, Priority>)> = None;
+        for (rank, root) in self.roots.iter().enumerate() {
+            let Some(root) = root else {
+                continue;
+            };
+            match first {
+                Some((_, node)) if node.borrow().priority <= root.borrow().priority => {}
+                _ => first = Some((rank, root)),
+            }
+        }
+        first.map(|(rank, _)| rank)
+    }
+
+    /**
+    take the root of the tree of given rank out of the queue, merging its children back in
+
+    # Errors
+    NotFound => there is no tree of that rank\n
+    ImpossibleRcRelease => internal reference counting error
+    */
+    fn remove_root(&mut self, rank: usize) -> Result<(T, Priority), Error> {
+        let root = self
+            .roots
+            .get_mut(rank)
+            .and_then(Option::take)
+            .ok_or(Error::NotFound("node"))?;
+        self.node_count = self.node_count.checked_sub(1).ok_or(Error::Empty)?;
+
+        let children: Vec<_> = root.borrow_mut().children.drain(..).collect();
+        for child in &children {
+            child.borrow_mut().parent = None;
+        }
+        self.merge_roots(children.into_iter().map(Some).collect());
+
+        let node = Rc::into_inner(root)
+            .ok_or(Error::ImpossibleRcRelease)?
+            .into_inner();
+        Ok((node.t, node.priority))
+    }
+
+    fn get_node(&self, t: &T) -> Option<Tree<T, Priority>> {
+        let mut nodes: Vec<_> = self.roots.iter().flatten().cloned().collect();
+        while let Some(node) = nodes.pop() {
+            if node.borrow().t == *t {
+                return Some(node);
+            }
+            nodes.extend(node.borrow().children.iter().cloned());
+        }
+        None
+    }
+
+    /* # heap functionality */
+
+    /// construct empty queue
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            roots: Vec::new(),
+            node_count: 0,
+        }
+    }
+
+    /// returns true if the queue is empty
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.node_count == 0
+    }
+
+    /// returns the number of elements in the queue
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.node_count
+    }
+
+    /**
+    push a value onto the queue with given priority
+
+    # Errors
+    will error if the queue is already at capacity
+    */
+    pub fn push(&mut self, t: T, priority: Priority) -> Result<(), Error> {
+        self.node_count = self
+            .node_count
+            .checked_add(1)
+            .ok_or(Error::ReachedCapacity)?;
+        self.merge_roots(Vec::from([Some(Node::new_tree(t, priority))]));
+        Ok(())
+    }
+
+    /**
+    return the element with the lowest priority
+
+    # Errors
+    Empty => cannot return element from empty queue\n
+    ImpossibleRcRelease => internal reference counting error
+    */
+    pub fn pop(&mut self) -> Result<(T, Priority), Error> {
+        let rank = self.first_rank().ok_or(Error::Empty)?;
+        self.remove_root(rank)
+    }
+
+    /**
+    decreases the priority of the item with given value,
+    moving it towards the root of its tree by swapping it with its ancestors
+
+    # Errors
+    NotFound => index with given value was not found in the queue\n
+    CannotIncreasePriority => the give prioprity is higher than the current one for the index of that value
+    */
+    pub fn decrease_priority(&mut self, value: &T, new_priority: Priority) -> Result<(), Error> {
+        let mut node = self.get_node(value).ok_or(Error::NotFound("value"))?;
+        if node.borrow().priority <= new_priority {
+            return Err(Error::CannotIncreasePriority {
+                current_was_lower: node.borrow().priority < new_priority,
+            });
+        }
+        node.borrow_mut().priority = new_priority;
+
+        loop {
+            let parent = node.borrow().parent.as_ref().and_then(Weak::upgrade);
+            let Some(parent) = parent else {
+                break;
+            };
+            {
+                let (mut lower, mut upper) = (node.borrow_mut(), parent.borrow_mut());
+                if lower.priority >= upper.priority {
+                    break;
+                }
+                swap(&mut lower.t, &mut upper.t);
+                swap(&mut lower.priority, &mut upper.priority);
+            }
+            node = parent;
+        }
+        Ok(())
+    }
+
+    /**
+    remove the item with given value from the queue, regardless of its priority,
+    by swapping it all the way up to the root of its tree and taking that root out
+
+    # Errors
+    NotFound => index with given value was not found in the queue\n
+    ImpossibleRcRelease => internal reference counting error
+    */
+    pub fn delete(&mut self, value: &T) -> Result<(T, Priority), Error> {
+        let mut node = self.get_node(value).ok_or(Error::NotFound("value"))?;
+        loop {
+            let parent = node.borrow().parent.as_ref().and_then(Weak::upgrade);
+            let Some(parent) = parent else {
+                break;
+            };
+            {
+                // every ancestor moves down along the path, which keeps it above its new subtree
+                let (mut lower, mut upper) = (node.borrow_mut(), parent.borrow_mut());
+                swap(&mut lower.t, &mut upper.t);
+                swap(&mut lower.priority, &mut upper.priority);
+            }
+            node = parent;
+        }
+        let rank = self
+            .roots
+            .iter()
+            .position(|root| root.as_ref().is_some_and(|root| Rc::ptr_eq(root, &node)))
+            .ok_or(Error::NotFound("node"))?;
+        drop(node);
+        self.remove_root(rank)
+    }
+
+    /**
+    move all elements of another queue into this one, linking trees of equal rank
+
+    # Errors
+    will error if the combined queue would exceed capacity
+    */
+    pub fn meld(&mut self, other: Self) -> Result<(), Error> {
+        self.node_count = self
+            .node_count
+            .checked_add(other.node_count)
+            .ok_or(Error::ReachedCapacity)?;
+        self.merge_roots(other.roots);
+        Ok(())
+    }
+}
+
+/* # priority queue */
+
+impl<T, Priority> PriorityQueue<T, Priority> for BinomialQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    fn push(&mut self, t: T, priority: Priority) -> Result<(), Error> {
+        Self::push(self, t, priority)
+    }
+
+    fn pop(&mut self) -> Result<(T, Priority), Error> {
+        Self::pop(self)
+    }
+
+    fn peek(&self) -> Option<(T, Priority)>
+    where
+        T: Clone,
+        Priority: Clone,
+    {
+        let node = self.roots[self.first_rank()?].as_ref()?.borrow();
+        Some((node.t.clone(), node.priority.clone()))
+    }
+
+    fn decrease_priority(&mut self, value: &T, new_priority: Priority) -> Result<(), Error> {
+        Self::decrease_priority(self, value, new_priority)
+    }
+
+    fn delete(&mut self, value: &T) -> Result<(T, Priority), Error> {
+        Self::delete(self, value)
+    }
+
+    fn is_empty(&self) -> bool {
+        Self::is_empty(self)
+    }
+
+    fn len(&self) -> usize {
+        Self::len(self)
+    }
+
+    fn meld(&mut self, other: Self) -> Result<(), Error> {
+        Self::meld(self, other)
+    }
+}
@@ -0,0 +1,136 @@
+use crate::{error::Error, minmax::MinMaxQueue, policy::EvictionPolicy};
+
+/* # bounded queue */
+
+/**
+fibonacci queue holding at most a fixed number of elements,
+giving up one element according to its eviction policy whenever a push would exceed that number
+
+the elements are kept in a double ended queue, so that whichever end the policy evicts from
+is reached as cheaply as the end elements are popped from
+
+```
+use fbheap::bounded::BoundedQueue;
+use fbheap::policy::EvictionPolicy;
+
+let mut packets = BoundedQueue::with_capacity(2, EvictionPolicy::EvictLargest);
+assert_eq!(packets.push("control", 0)?, None);
+assert_eq!(packets.push("bulk", 9)?, None);
+// the least urgent packet is dropped to make room
+assert_eq!(packets.push("voice", 1)?, Some(("bulk", 9)));
+assert_eq!(packets.pop(), Ok(("control", 0)));
+
+let mut strict = BoundedQueue::with_capacity(1, EvictionPolicy::RejectNew);
+strict.push("first", 5)?;
+assert_eq!(strict.push("second", 1)?, Some(("second", 1)));
+# Ok::<(), fbheap::error::Error>(())
+```
+*/
+pub struct BoundedQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /// underlying queue, which never holds more elements than the capacity
+    queue: MinMaxQueue<T, Priority>,
+    /// number of elements the queue holds at most
+    capacity: usize,
+    /// element given up when the queue is full
+    policy: EvictionPolicy,
+}
+
+impl<T, Priority> BoundedQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /// construct empty queue holding at most the given number of elements
+    #[must_use]
+    pub const fn with_capacity(capacity: usize, policy: EvictionPolicy) -> Self {
+        Self {
+            queue: MinMaxQueue::new(),
+            capacity,
+            policy,
+        }
+    }
+
+    /// returns the number of elements the queue holds at most
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// returns the policy deciding which element is given up when the queue is full
+    #[must_use]
+    pub const fn policy(&self) -> EvictionPolicy {
+        self.policy
+    }
+
+    /// returns true if the queue is empty
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// returns true if another push would give up an element
+    #[must_use]
+    pub const fn is_full(&self) -> bool {
+        self.queue.len() >= self.capacity
+    }
+
+    /// returns the number of elements in the queue
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /**
+    push a value onto the queue with given priority,
+    returning the element given up to stay within capacity, if any
+
+    # Errors
+    ReachedCapacity => the underlying queue cannot account for additional nodes\n
+    NotFound => internal indexing error
+    */
+    pub fn push(&mut self, t: T, priority: Priority) -> Result<Option<(T, Priority)>, Error>
+    where
+        Priority: Clone,
+    {
+        if !self.is_full() {
+            self.queue.push(t, priority)?;
+            return Ok(None);
+        }
+        match self.policy {
+            EvictionPolicy::RejectNew => Ok(Some((t, priority))),
+            EvictionPolicy::EvictLargest => {
+                self.queue.push(t, priority)?;
+                self.queue.pop_max().map(Some)
+            }
+            EvictionPolicy::EvictSmallest => {
+                self.queue.push(t, priority)?;
+                self.queue.pop_min().map(Some)
+            }
+        }
+    }
+
+    /**
+    return the element with the lowest priority
+
+    # Errors
+    Empty => cannot return element from empty queue\n
+    NotFound => internal indexing error
+    */
+    pub fn pop(&mut self) -> Result<(T, Priority), Error> {
+        self.queue.pop_min()
+    }
+
+    /// returns a copy of the element with the lowest priority, if there is any
+    #[must_use]
+    pub fn peek(&self) -> Option<(T, Priority)>
+    where
+        T: Clone,
+        Priority: Clone,
+    {
+        self.queue.peek_min()
+    }
+}
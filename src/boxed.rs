@@ -0,0 +1,74 @@
+use crate::{error::Error, handle::Handle, heap::BareQueue};
+use alloc::boxed::Box;
+
+/* # boxed queue */
+
+/**
+fibonacci queue of boxed values, usually trait objects of different types behind one trait
+
+values are moved in and out of the queue, so they have to be sized, which boxing takes care of,
+and trait objects rarely implement `Eq`, so elements are reached through handles instead of by value,
+which needs no bounds on the values at all
+
+```
+use fbheap::boxed::DynQueue;
+
+trait Task {
+    fn run(&self) -> String;
+}
+
+struct Print(&'static str);
+impl Task for Print {
+    fn run(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+struct Sum(u32, u32);
+impl Task for Sum {
+    fn run(&self) -> String {
+        (self.0 + self.1).to_string()
+    }
+}
+
+let mut scheduler: DynQueue<dyn Task, u64> = DynQueue::new();
+scheduler.push_boxed(Box::new(Print("later")), 20);
+let sum = scheduler.push_boxed(Box::new(Sum(1, 2)), 30);
+scheduler.decrease_priority_at(&sum, 10)?;
+let output: Vec<_> = scheduler.into_iter().map(|(task, _)| task.run()).collect();
+assert_eq!(output, vec!["3", "later"]);
+# Ok::<(), fbheap::error::Error>(())
+```
+*/
+pub type DynQueue<Task, Priority> = BareQueue<Box<Task>, Priority>;
+
+impl<Task, Priority> BareQueue<Box<Task>, Priority>
+where
+    Task: ?Sized,
+    Priority: Ord,
+{
+    /**
+    push a boxed value onto the queue with given priority, returning a handle to it,
+    by which the value can be reached later without comparing it to others
+
+    # Panics
+    panics if the queue is already at capacity, see `push`
+    */
+    pub fn push_boxed(&mut self, t: Box<Task>, priority: Priority) -> Handle<Box<Task>, Priority> {
+        self.push_with_handle(t, priority)
+    }
+
+    /**
+    push a boxed value onto the queue with given priority, returning a handle to it
+
+    # Errors
+    ReachedCapacity => the queue is already at capacity
+    */
+    pub fn try_push_boxed(
+        &mut self,
+        t: Box<Task>,
+        priority: Priority,
+    ) -> Result<Handle<Box<Task>, Priority>, Error> {
+        self.try_push_with_handle(t, priority)
+    }
+}
@@ -0,0 +1,660 @@
+use crate::{arena::ArenaQueue, bounded::BoundedQueue, error::Error, policy::EvictionPolicy};
+use std::{
+    future::Future,
+    marker::PhantomData,
+    mem::replace,
+    pin::Pin,
+    sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError},
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+
+/* # configuration */
+
+/// channel which accepts every item sent, as long as its receiver is there
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Unbounded;
+
+/// channel holding a limited number of pending items, which settles overflows by an eviction policy
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Bounded;
+
+/// channel with a single sender, which cannot be cloned
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SingleProducer;
+
+/// channel whose sender can be cloned to send from several places
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MultiProducer;
+
+/// channel whose receiver waits through futures, driven by any executor
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Async;
+
+/// channel whose receiver waits by blocking its thread
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Blocking;
+
+/// sending and receiving ends of a channel configured as given
+pub type Channel<T, Priority, Capacity = Unbounded, Producers = MultiProducer, Waiting = Async> = (
+    PrioritySender<T, Priority, Capacity, Producers>,
+    PriorityReceiver<T, Priority, Waiting>,
+);
+
+/**
+builder of a priority channel, whose configuration shows in the types of the ends it builds
+
+a channel is unbounded, multi producer and waited on asynchronously unless configured otherwise,
+which is what `channel` builds, and every choice changes the type of the ends, so that bounded senders
+hand back what overflowed, only multi producer senders can be cloned,
+and receivers either return futures or block their thread while waiting
+
+```
+use fbheap::channel::ChannelBuilder;
+use fbheap::error::Error::Disconnected;
+use fbheap::policy::EvictionPolicy;
+
+let (sender, mut receiver) = ChannelBuilder::new()
+    .bounded(2, EvictionPolicy::EvictLargest)
+    .single_producer()
+    .blocking()
+    .build();
+let worker = std::thread::spawn(move || {
+    sender.send("rotate logs", 5)?;
+    sender.send("rebuild index", 3)?;
+    // the channel is full, so the least urgent pending item gives way
+    assert_eq!(sender.send("page on-call", 0)?, Some(("rotate logs", 5)));
+    Ok::<_, fbheap::error::Error>(())
+});
+worker.join().unwrap()?;
+
+assert_eq!(receiver.recv(), Ok(("page on-call", 0)));
+assert_eq!(receiver.recv(), Ok(("rebuild index", 3)));
+assert_eq!(receiver.recv(), Err(Disconnected));
+# Ok::<(), fbheap::error::Error>(())
+```
+*/
+#[derive(Clone, Copy, Debug)]
+pub struct ChannelBuilder<Capacity = Unbounded, Producers = MultiProducer, Waiting = Async> {
+    /// largest number of pending items
+    capacity: usize,
+    /// what a full channel does with another item
+    policy: EvictionPolicy,
+    /// configuration of the channel, carried in the types of its ends
+    marker: PhantomData<(Capacity, Producers, Waiting)>,
+}
+
+impl Default for ChannelBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChannelBuilder {
+    /// construct builder of an unbounded, multi producer, asynchronous channel
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            capacity: usize::MAX,
+            policy: EvictionPolicy::RejectNew,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<Capacity, Producers, Waiting> ChannelBuilder<Capacity, Producers, Waiting> {
+    /// keep the configuration, but carry it in other types
+    const fn configure<C, P, W>(self) -> ChannelBuilder<C, P, W> {
+        ChannelBuilder {
+            capacity: self.capacity,
+            policy: self.policy,
+            marker: PhantomData,
+        }
+    }
+
+    /// hold at most the given number of pending items, settling overflows by the given policy
+    #[must_use]
+    pub const fn bounded(
+        mut self,
+        capacity: usize,
+        policy: EvictionPolicy,
+    ) -> ChannelBuilder<Bounded, Producers, Waiting> {
+        self.capacity = capacity;
+        self.policy = policy;
+        self.configure()
+    }
+
+    /// accept every item sent
+    #[must_use]
+    pub const fn unbounded(mut self) -> ChannelBuilder<Unbounded, Producers, Waiting> {
+        self.capacity = usize::MAX;
+        self.configure()
+    }
+
+    /// send through a single sender, which cannot be cloned
+    #[must_use]
+    pub const fn single_producer(self) -> ChannelBuilder<Capacity, SingleProducer, Waiting> {
+        self.configure()
+    }
+
+    /// send through a sender which can be cloned
+    #[must_use]
+    pub const fn multi_producer(self) -> ChannelBuilder<Capacity, MultiProducer, Waiting> {
+        self.configure()
+    }
+
+    /// wait for items by blocking the thread of the receiver
+    #[must_use]
+    pub const fn blocking(self) -> ChannelBuilder<Capacity, Producers, Blocking> {
+        self.configure()
+    }
+
+    /// wait for items through futures
+    #[must_use]
+    pub const fn asynchronous(self) -> ChannelBuilder<Capacity, Producers, Async> {
+        self.configure()
+    }
+
+    /// create the channel, returning its sending and receiving ends
+    #[must_use]
+    pub fn build<T, Priority>(self) -> Channel<T, Priority, Capacity, Producers, Waiting>
+    where
+        T: Eq,
+        Priority: Ord,
+    {
+        let pending = if self.capacity == usize::MAX {
+            Pending::Unbounded(ArenaQueue::new())
+        } else {
+            Pending::Bounded(BoundedQueue::with_capacity(self.capacity, self.policy))
+        };
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State {
+                pending,
+                sent: 0,
+                waker: None,
+                senders: 1,
+                receiving: true,
+            }),
+            arrived: Condvar::new(),
+        });
+        (
+            PrioritySender {
+                shared: Arc::clone(&shared),
+                marker: PhantomData,
+            },
+            PriorityReceiver {
+                shared,
+                marker: PhantomData,
+            },
+        )
+    }
+}
+
+/* # priority channel */
+
+/// items sent but not yet received, by priority and then in the order they were sent
+enum Pending<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /// queue of an unbounded channel
+    Unbounded(ArenaQueue<T, (Priority, u64)>),
+    /// queue of a bounded channel, which settles overflows by its eviction policy
+    Bounded(BoundedQueue<T, (Priority, u64)>),
+}
+
+impl<T, Priority> Pending<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    const fn len(&self) -> usize {
+        match self {
+            Self::Unbounded(queue) => queue.len(),
+            Self::Bounded(queue) => queue.len(),
+        }
+    }
+
+    const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn pop(&mut self) -> Result<(T, Priority), Error> {
+        let (t, (priority, _)) = match self {
+            Self::Unbounded(queue) => queue.pop()?,
+            Self::Bounded(queue) => queue.pop()?,
+        };
+        Ok((t, priority))
+    }
+}
+
+/// state shared by both ends of a channel
+struct State<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /// items sent but not yet received
+    pending: Pending<T, Priority>,
+    /// number of items sent so far, which orders items of equal priority
+    sent: u64,
+    /// waker of the receiver waiting for an item, if it is waiting asynchronously
+    waker: Option<Waker>,
+    /// number of senders which were not dropped yet
+    senders: usize,
+    /// flag for whether the receiver was not dropped yet
+    receiving: bool,
+}
+
+impl<T, Priority> State<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /// tag a priority with the next sequence number, placing it behind pending ones of equal priority
+    fn sequenced(&mut self, priority: Priority) -> Result<(Priority, u64), Error> {
+        let sent = self.sent;
+        self.sent = sent.checked_add(1).ok_or(Error::Numerical)?;
+        Ok((priority, sent))
+    }
+
+    /// queue an item of an unbounded channel
+    fn push(&mut self, t: T, priority: Priority) -> Result<(), Error> {
+        let priority = self.sequenced(priority)?;
+        match &mut self.pending {
+            Pending::Unbounded(queue) => queue.push(t, priority),
+            // senders of bounded channels offer their items instead
+            Pending::Bounded(_) => Err(Error::ReachedCapacity),
+        }
+    }
+
+    /// queue an item of a bounded channel, returning the one given up by its eviction policy
+    fn offer(&mut self, t: T, priority: Priority) -> Result<Option<(T, Priority)>, Error>
+    where
+        Priority: Clone,
+    {
+        let priority = self.sequenced(priority)?;
+        match &mut self.pending {
+            Pending::Unbounded(queue) => queue.push(t, priority).map(|()| None),
+            Pending::Bounded(queue) => Ok(queue
+                .push(t, priority)?
+                .map(|(t, (priority, _))| (t, priority))),
+        }
+    }
+
+    /// take out the pending item with the lowest priority, telling an empty channel from a disconnected one
+    fn receive(&mut self) -> Result<(T, Priority), Error> {
+        match self.pending.pop() {
+            Err(Error::Empty) if self.senders == 0 => Err(Error::Disconnected),
+            popped => popped,
+        }
+    }
+
+    /// whether a receiver has to keep waiting for an item
+    fn waiting(&self) -> bool {
+        self.pending.is_empty() && self.senders > 0
+    }
+}
+
+/// state of a channel, locked for the duration of every operation
+struct Shared<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /// the state itself
+    state: Mutex<State<T, Priority>>,
+    /// signalled whenever an item arrives or the last sender is gone, for blocked receivers
+    arrived: Condvar,
+}
+
+impl<T, Priority> Shared<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /// lock the state of the channel
+    fn lock(&self) -> Result<MutexGuard<'_, State<T, Priority>>, Error> {
+        self.state.lock().map_err(|_| Error::Poisoned)
+    }
+
+    /// wake the receiver, however it waits
+    fn notify(&self, waker: Option<Waker>) {
+        waker.into_iter().for_each(Waker::wake);
+        self.arrived.notify_all();
+    }
+}
+
+/**
+create a multi producer channel whose receiver hands out the pending item with the lowest priority,
+and among those the one sent first
+
+waiting for an item only relies on the `Waker` of the polling task, so any executor can drive it,
+see `ChannelBuilder` for channels configured otherwise
+
+```
+use fbheap::channel::channel;
+use fbheap::error::Error::Disconnected;
+# use std::{future::Future, pin::pin, sync::Arc, task::{Context, Poll, Wake}, thread::Thread};
+#
+# struct Unpark(Thread);
+# impl Wake for Unpark {
+#     fn wake(self: Arc<Self>) {
+#         self.0.unpark();
+#     }
+# }
+#
+# fn block_on<F: Future>(future: F) -> F::Output {
+#     let mut future = pin!(future);
+#     let waker = Arc::new(Unpark(std::thread::current())).into();
+#     let mut context = Context::from_waker(&waker);
+#     loop {
+#         match future.as_mut().poll(&mut context) {
+#             Poll::Ready(output) => return output,
+#             Poll::Pending => std::thread::park(),
+#         }
+#     }
+# }
+
+let (sender, mut receiver) = channel();
+let worker = sender.clone();
+std::thread::spawn(move || {
+    worker.send("rebuild index", 3)?;
+    worker.send("page on-call", 0)
+});
+sender.send("rotate logs", 5)?;
+drop(sender);
+
+block_on(async {
+    let mut received = Vec::new();
+    while let Ok((task, _)) = receiver.recv().await {
+        received.push(task);
+    }
+    // all senders are gone, so nothing more can arrive
+    assert_eq!(receiver.recv().await, Err(Disconnected));
+    assert_eq!(received.len(), 3);
+});
+# Ok::<(), fbheap::error::Error>(())
+```
+*/
+#[must_use]
+pub fn channel<T, Priority>() -> Channel<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    ChannelBuilder::new().build()
+}
+
+/* # sender */
+
+/// sending end of a priority channel, which can be cloned to send from several places if it is multi producer
+pub struct PrioritySender<T, Priority, Capacity = Unbounded, Producers = MultiProducer>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /// state shared with the receiver
+    shared: Arc<Shared<T, Priority>>,
+    /// configuration of the channel
+    marker: PhantomData<(Capacity, Producers)>,
+}
+
+impl<T, Priority, Capacity, Producers> PrioritySender<T, Priority, Capacity, Producers>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /// queue an item as given and wake the receiver, however it waits
+    fn deliver<R>(
+        &self,
+        queue: impl FnOnce(&mut State<T, Priority>) -> Result<R, Error>,
+    ) -> Result<R, Error> {
+        let (waker, queued) = {
+            let mut state = self.shared.lock()?;
+            if !state.receiving {
+                return Err(Error::Disconnected);
+            }
+            let queued = queue(&mut state)?;
+            (state.waker.take(), queued)
+        };
+        // the lock is released before waking, so the receiver does not contend for it
+        self.shared.notify(waker);
+        Ok(queued)
+    }
+}
+
+impl<T, Priority, Producers> PrioritySender<T, Priority, Unbounded, Producers>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /**
+    send a value with given priority, waking the receiver if it is waiting
+
+    # Errors
+    Disconnected => the receiver was dropped\n
+    Numerical => the channel ran out of sequence numbers\n
+    Poisoned => the lock was poisoned
+    */
+    pub fn send(&self, t: T, priority: Priority) -> Result<(), Error> {
+        self.deliver(|state| state.push(t, priority))
+    }
+}
+
+impl<T, Priority, Producers> PrioritySender<T, Priority, Bounded, Producers>
+where
+    T: Eq,
+    Priority: Ord + Clone,
+{
+    /**
+    send a value with given priority, waking the receiver if it is waiting,
+    and returning the element given up by the eviction policy if the channel was full
+
+    # Errors
+    Disconnected => the receiver was dropped\n
+    Numerical => the channel ran out of sequence numbers\n
+    Poisoned => the lock was poisoned
+    */
+    pub fn send(&self, t: T, priority: Priority) -> Result<Option<(T, Priority)>, Error> {
+        self.deliver(|state| state.offer(t, priority))
+    }
+}
+
+impl<T, Priority, Capacity> Clone for PrioritySender<T, Priority, Capacity, MultiProducer>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    fn clone(&self) -> Self {
+        self.shared
+            .state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .senders += 1;
+        Self {
+            shared: Arc::clone(&self.shared),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T, Priority, Capacity, Producers> Drop for PrioritySender<T, Priority, Capacity, Producers>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /// wake the receiver once the last sender is gone, so that it stops waiting
+    fn drop(&mut self) {
+        let waker = {
+            let mut state = self
+                .shared
+                .state
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            state.senders -= 1;
+            if state.senders > 0 {
+                return;
+            }
+            state.waker.take()
+        };
+        self.shared.notify(waker);
+    }
+}
+
+/* # receiver */
+
+/// receiving end of a priority channel, which waits for items as configured
+pub struct PriorityReceiver<T, Priority, Waiting = Async>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /// state shared with the senders
+    shared: Arc<Shared<T, Priority>>,
+    /// how the receiver waits for items
+    marker: PhantomData<Waiting>,
+}
+
+impl<T, Priority, Waiting> PriorityReceiver<T, Priority, Waiting>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /**
+    returns the number of items sent but not yet received
+
+    # Errors
+    will error if the lock was poisoned
+    */
+    pub fn len(&self) -> Result<usize, Error> {
+        Ok(self.shared.lock()?.pending.len())
+    }
+
+    /**
+    returns true if no items are pending
+
+    # Errors
+    will error if the lock was poisoned
+    */
+    pub fn is_empty(&self) -> Result<bool, Error> {
+        Ok(self.shared.lock()?.pending.is_empty())
+    }
+
+    /**
+    return the pending item with the lowest priority, without waiting
+
+    # Errors
+    Empty => no item is pending\n
+    Disconnected => no item is pending and all senders were dropped\n
+    Poisoned => the lock was poisoned
+    */
+    pub fn try_recv(&mut self) -> Result<(T, Priority), Error> {
+        self.shared.lock()?.receive()
+    }
+}
+
+impl<T, Priority> PriorityReceiver<T, Priority, Async>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /// wait for the pending item with the lowest priority, resolving to the same results as `try_recv`,
+    /// except that it keeps waiting while the channel is empty but still connected
+    pub fn recv(&mut self) -> Recv<'_, T, Priority> {
+        Recv { receiver: self }
+    }
+}
+
+impl<T, Priority> PriorityReceiver<T, Priority, Blocking>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /**
+    block until an item is pending, then return the one with the lowest priority
+
+    # Errors
+    Disconnected => no item is pending and all senders were dropped\n
+    Poisoned => the lock was poisoned
+    */
+    pub fn recv(&mut self) -> Result<(T, Priority), Error> {
+        let state = self.shared.lock()?;
+        self.shared
+            .arrived
+            .wait_while(state, |state| state.waiting())
+            .map_err(|_| Error::Poisoned)?
+            .receive()
+    }
+
+    /**
+    block until an item is pending or the timeout elapses, then return the one with the lowest priority
+
+    # Errors
+    Empty => no item arrived before the timeout\n
+    Disconnected => no item is pending and all senders were dropped\n
+    Poisoned => the lock was poisoned
+    */
+    pub fn recv_timeout(&mut self, timeout: Duration) -> Result<(T, Priority), Error> {
+        let state = self.shared.lock()?;
+        self.shared
+            .arrived
+            .wait_timeout_while(state, timeout, |state| state.waiting())
+            .map_err(|_| Error::Poisoned)?
+            .0
+            .receive()
+    }
+}
+
+impl<T, Priority, Waiting> Drop for PriorityReceiver<T, Priority, Waiting>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /// stop accepting items, dropping the pending ones once the lock is released
+    fn drop(&mut self) {
+        let _pending = {
+            let mut state = self
+                .shared
+                .state
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            state.receiving = false;
+            state.waker = None;
+            replace(&mut state.pending, Pending::Unbounded(ArenaQueue::new()))
+        };
+    }
+}
+
+/// future resolving to the next item of a priority channel, returned by [`PriorityReceiver::recv`]
+#[must_use = "futures do nothing unless polled"]
+pub struct Recv<'a, T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /// receiver being waited on
+    receiver: &'a mut PriorityReceiver<T, Priority, Async>,
+}
+
+impl<T, Priority> Future for Recv<'_, T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    type Output = Result<(T, Priority), Error>;
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.receiver.shared.lock()?;
+        if state.waiting() {
+            // registered while locked, so no item sent in between goes unnoticed
+            match &mut state.waker {
+                Some(waker) => waker.clone_from(context.waker()),
+                None => state.waker = Some(context.waker().clone()),
+            }
+            return Poll::Pending;
+        }
+        Poll::Ready(state.receive())
+    }
+}
@@ -1,12 +1,54 @@
+/**
+errors returned by the queues, carrying enough context to tell apart why an operation failed
+
+```
+use fbheap::error::Error;
+use fbheap::heap::BareQueue;
+
+let mut queue = BareQueue::new();
+queue.push("a", 2);
+assert_eq!(queue.decrease_priority(&"b", 1), Err(Error::NotFound("value")));
+assert_eq!(
+    queue.decrease_priority(&"a", 3),
+    Err(Error::CannotIncreasePriority { current_was_lower: true })
+);
+assert_eq!(
+    queue.decrease_priority(&"a", 2),
+    Err(Error::CannotIncreasePriority { current_was_lower: false })
+);
+```
+*/
 #[derive(Debug, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum Error {
+    /// a node was still shared when the queue expected to hold its only reference
     ImpossibleRcRelease,
-    InvalidIndex,
+    /// what was looked up, such as a value, a handle or a vertex, and not found
+    NotFound(&'static str),
+    /// the queue is full, or cannot count any more nodes
     ReachedCapacity,
+    /// an arithmetic operation on priorities or counters overflowed
     Numerical,
+    /// the operation needs at least one element in the queue
     Empty,
-    CannotIncreasePriority,
+    /// whether the current priority was lower than the given one, rather than equal to it
+    CannotIncreasePriority {
+        current_was_lower: bool,
+    },
+    /// whether the current priority was higher than the given one, rather than equal to it
+    CannotDecreasePriority {
+        current_was_higher: bool,
+    },
+    /// a thread panicked while holding the lock of the queue
+    Poisoned,
+    /// the priority is lower than that of the last popped element
+    NonMonotonePush,
+    /// the handle points to an element which has since left the queue
+    StaleHandle,
+    /// the other end of the channel was dropped
+    Disconnected,
+    /// the value is already queued and the queue keeps values unique
+    DuplicateValue,
 }
 
 impl core::fmt::Display for Error {
@@ -15,8 +57,8 @@ impl core::fmt::Display for Error {
             Self::ImpossibleRcRelease => {
                 write!(f, "cannot release rc due to outstanding reference")
             }
-            Self::InvalidIndex => {
-                write!(f, "requested value not found in queue")
+            Self::NotFound(what) => {
+                write!(f, "requested {what} not found in queue")
             }
             Self::ReachedCapacity => {
                 write!(f, "cannot account for additional nodes")
@@ -27,11 +69,115 @@ impl core::fmt::Display for Error {
             Self::Numerical => {
                 write!(f, "failed numerical operation")
             }
-            Self::CannotIncreasePriority => {
+            Self::CannotIncreasePriority {
+                current_was_lower: true,
+            } => {
                 write!(f, "cannot change priority to a higher value")
             }
+            Self::CannotDecreasePriority {
+                current_was_higher: true,
+            } => {
+                write!(f, "cannot change priority to a lower value")
+            }
+            Self::CannotIncreasePriority { .. } | Self::CannotDecreasePriority { .. } => {
+                write!(f, "priority already has the given value")
+            }
+            Self::Poisoned => {
+                write!(f, "queue lock was poisoned by a panicking thread")
+            }
+            Self::NonMonotonePush => {
+                write!(
+                    f,
+                    "cannot queue priority lower than that of the last popped element"
+                )
+            }
+            Self::StaleHandle => {
+                write!(f, "handle points to an element no longer in the queue")
+            }
+            Self::Disconnected => {
+                write!(f, "other end of the channel was dropped")
+            }
+            Self::DuplicateValue => {
+                write!(f, "value is already queued")
+            }
+        }
+    }
+}
+
+impl core::error::Error for Error {}
+
+/**
+broad categories of errors, so that applications can map them into their own error types
+without matching on every variant of the non exhaustive `Error`
+
+```
+use fbheap::error::{Error, ErrorKind};
+use fbheap::heap::BareQueue;
+
+let mut queue: BareQueue<&str, u32> = BareQueue::new();
+assert_eq!(queue.pop().map_err(|error| error.kind()), Err(ErrorKind::Logic));
+assert_eq!(Error::ReachedCapacity.kind(), ErrorKind::Capacity);
+```
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    /// the operation does not fit the contents of the queue, such as popping from an empty one,
+    /// which points to a mistake on the side of the caller
+    Logic,
+    /// the queue or one of its counters cannot grow any further
+    Capacity,
+    /// the queue cannot be reached any more, as its lock was poisoned or the other end of its
+    /// channel was dropped
+    Unavailable,
+    /// internal invariants of the queue were broken, which points to a bug in this crate
+    Internal,
+}
+
+impl Error {
+    /// returns the category of the error
+    #[must_use]
+    pub const fn kind(&self) -> ErrorKind {
+        match self {
+            Self::NotFound(_)
+            | Self::Empty
+            | Self::CannotIncreasePriority { .. }
+            | Self::CannotDecreasePriority { .. }
+            | Self::NonMonotonePush
+            | Self::StaleHandle
+            | Self::DuplicateValue => ErrorKind::Logic,
+            Self::ReachedCapacity | Self::Numerical => ErrorKind::Capacity,
+            Self::Poisoned | Self::Disconnected => ErrorKind::Unavailable,
+            Self::ImpossibleRcRelease => ErrorKind::Internal,
         }
     }
 }
 
-impl std::error::Error for Error {}
+/**
+wrap the error into an io error of the closest kind, keeping it as the source
+
+```
+use fbheap::error::Error;
+use std::io;
+
+let error = io::Error::from(Error::Disconnected);
+assert_eq!(error.kind(), io::ErrorKind::BrokenPipe);
+assert_eq!(error.into_inner().unwrap().downcast::<Error>().ok().map(|error| *error), Some(Error::Disconnected));
+```
+*/
+#[cfg(feature = "std")]
+impl From<Error> for std::io::Error {
+    fn from(error: Error) -> Self {
+        use std::io::ErrorKind as Io;
+        let kind = match error {
+            Error::NotFound(_) | Error::StaleHandle => Io::NotFound,
+            Error::DuplicateValue => Io::AlreadyExists,
+            Error::Disconnected => Io::BrokenPipe,
+            Error::ReachedCapacity => Io::OutOfMemory,
+            _ => match error.kind() {
+                ErrorKind::Logic => Io::InvalidInput,
+                ErrorKind::Capacity | ErrorKind::Unavailable | ErrorKind::Internal => Io::Other,
+            },
+        };
+        Self::new(kind, error)
+    }
+}
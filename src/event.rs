@@ -0,0 +1,157 @@
+use crate::{error::Error, heap::BareQueue};
+
+/* # event queue */
+
+/**
+scheduler for discrete event simulations, handing out events in order of the time they are due at
+
+the clock only moves forward, as events are handed out or time is advanced,
+so events cannot be scheduled before the current time,
+and events due at the same time are handed out in the order they were scheduled
+
+```
+use fbheap::error::Error::NonMonotonePush;
+use fbheap::event::EventQueue;
+
+let mut events = EventQueue::new();
+events.schedule(5, "departure")?;
+events.schedule(2, "arrival")?;
+events.schedule(5, "boarding")?;
+events.schedule(9, "delay")?;
+assert_eq!(events.cancel(&"delay"), Ok(9));
+let due: Vec<_> = events.advance_until(6).collect();
+assert_eq!(due, vec![("arrival", 2), ("departure", 5), ("boarding", 5)]);
+assert_eq!(events.now(), 6);
+// the clock does not go back
+assert_eq!(events.schedule(4, "too late"), Err(NonMonotonePush));
+assert!(events.is_empty());
+# Ok::<(), fbheap::error::Error>(())
+```
+*/
+pub struct EventQueue<E>
+where
+    E: Eq,
+{
+    /// underlying queue ordered by the due times, then by sequence numbers
+    queue: BareQueue<E, (u64, u64)>,
+    /// current time, which no event may be scheduled before
+    now: u64,
+    /// sequence number given to the next event
+    sequence: u64,
+}
+
+impl<E> Default for EventQueue<E>
+where
+    E: Eq,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E> EventQueue<E>
+where
+    E: Eq,
+{
+    /// construct empty queue, starting at time zero
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::starting_at(0)
+    }
+
+    /// construct empty queue, starting at the given time
+    #[must_use]
+    pub const fn starting_at(now: u64) -> Self {
+        Self {
+            queue: BareQueue::new(),
+            now,
+            sequence: 0,
+        }
+    }
+
+    /// returns the current time
+    #[must_use]
+    pub const fn now(&self) -> u64 {
+        self.now
+    }
+
+    /// returns true if no events are scheduled
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// returns the number of scheduled events
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /**
+    schedule an event to be due at the given time
+
+    # Errors
+    NonMonotonePush => the time is before the current time\n
+    ReachedCapacity => the queue is already at capacity\n
+    Numerical => the queue ran out of sequence numbers
+    */
+    pub fn schedule(&mut self, at: u64, event: E) -> Result<(), Error> {
+        if at < self.now {
+            return Err(Error::NonMonotonePush);
+        }
+        let sequence = self.sequence;
+        self.sequence = sequence.checked_add(1).ok_or(Error::Numerical)?;
+        self.queue.try_push(event, (at, sequence))
+    }
+
+    /**
+    remove a scheduled event, returning the time it was due at
+
+    # Errors
+    NotFound => the event is not scheduled
+    */
+    pub fn cancel(&mut self, event: &E) -> Result<u64, Error> {
+        self.queue.delete(event).map(|(_, (at, _))| at)
+    }
+
+    /**
+    hand out the events due until the given time, moving the clock to the time of each,
+    and to the given time itself once they are exhausted
+
+    events left in the queue when the iterator is dropped early stay scheduled
+    */
+    pub fn advance_until(&mut self, until: u64) -> AdvanceUntil<'_, E> {
+        AdvanceUntil {
+            events: self,
+            until,
+        }
+    }
+}
+
+/* # iterator */
+
+/// iterator over the events due until some time, together with the time each was due at
+pub struct AdvanceUntil<'a, E>
+where
+    E: Eq,
+{
+    events: &'a mut EventQueue<E>,
+    until: u64,
+}
+
+impl<E> Iterator for AdvanceUntil<'_, E>
+where
+    E: Eq,
+{
+    type Item = (E, u64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bound = (self.until, u64::MAX);
+        if let Ok(Some((event, (at, _)))) = self.events.queue.pop_with_priority_at_most(&bound) {
+            self.events.now = at;
+            return Some((event, at));
+        }
+        self.events.now = self.events.now.max(self.until);
+        None
+    }
+}
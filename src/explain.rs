@@ -0,0 +1,55 @@
+use alloc::vec::Vec;
+
+/// structural step taken by a queue while performing an operation
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Step<T, Priority> {
+    /// the tree rooted at `child` was linked below the root `parent` during consolidation
+    Link {
+        parent: (T, Priority),
+        child: (T, Priority),
+    },
+    /// `node` was cut from `parent` and moved to the list of roots,
+    /// a cut of the parent of the previously cut node is a cascading cut
+    Cut {
+        node: (T, Priority),
+        parent: (T, Priority),
+    },
+}
+
+/**
+record of what happened inside a queue during a single operation
+
+```
+use fbheap::explain::Step;
+use fbheap::heap::BareQueue;
+
+let mut queue = BareQueue::new();
+queue.push("a", 1);
+queue.push("b", 2);
+queue.push("c", 3);
+
+let trace = queue.explain_pop().unwrap();
+assert_eq!(trace.first_before, Some(("a", 1)));
+assert_eq!(trace.first_after, Some(("b", 2)));
+assert_eq!(
+    trace.steps,
+    vec![Step::Link { parent: ("b", 2), child: ("c", 3) }]
+);
+
+let trace = queue.explain_decrease(&"c", 0).unwrap();
+assert_eq!(
+    trace.steps,
+    vec![Step::Cut { node: ("c", 0), parent: ("b", 2) }]
+);
+assert_eq!(trace.first_after, Some(("c", 0)));
+```
+*/
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Trace<T, Priority> {
+    /// structural steps in the order they were taken
+    pub steps: Vec<Step<T, Priority>>,
+    /// element with the lowest priority before the operation
+    pub first_before: Option<(T, Priority)>,
+    /// element with the lowest priority after the operation
+    pub first_after: Option<(T, Priority)>,
+}
@@ -0,0 +1,165 @@
+use crate::{error::Error, hash::HashQueue, heap::Upsert, priority::Priority};
+use core::hash::Hash;
+use std::collections::HashMap;
+
+/* # crawl frontier */
+
+/// priority of a queued url, its score aged by the tick at which it was queued
+type UrlPriority = Priority<u64>;
+
+/**
+frontier of a web crawler, handing out urls by score while staying polite to every host
+
+every host has its own queue of urls, a host which was just crawled rests for the politeness
+delay before it is handed out again, and among hosts which are not resting the one with the best
+url goes first, with waiting urls catching up at the aging rate
+
+```
+use fbheap::frontier::CrawlFrontier;
+
+// hosts rest for 10 ticks, urls gain one point of score per tick waited
+let mut frontier = CrawlFrontier::new(10, 1);
+frontier.push("a.org", "a.org/1", 5, 0).unwrap();
+frontier.push("a.org", "a.org/2", 1, 0).unwrap();
+frontier.push("b.org", "b.org/1", 3, 0).unwrap();
+
+assert_eq!(frontier.pop(0), Ok(Some(("a.org", "a.org/2"))));
+// a.org is resting, so b.org goes next even though its url scores worse
+assert_eq!(frontier.pop(1), Ok(Some(("b.org", "b.org/1"))));
+assert_eq!(frontier.pop(2), Ok(None));
+assert_eq!(frontier.next_ready_at(), Some(10));
+assert_eq!(frontier.pop(10), Ok(Some(("a.org", "a.org/1"))));
+assert!(frontier.is_empty());
+```
+*/
+pub struct CrawlFrontier<Host, Url>
+where
+    Host: Eq + Hash + Clone,
+    Url: Eq + Hash + Clone,
+{
+    /// ticks a host rests after being crawled
+    politeness: u64,
+    /// points of score a url gains for every tick it waits
+    aging: u64,
+    /// queue of urls for every host
+    hosts: HashMap<Host, HashQueue<Url, UrlPriority>>,
+    /// hosts which were crawled recently, by the tick they can be crawled again
+    resting: HashQueue<Host, u64>,
+    /// hosts which can be crawled, by the priority of their best url
+    ready: HashQueue<Host, UrlPriority>,
+    /// number of queued urls
+    url_count: usize,
+}
+
+impl<Host, Url> CrawlFrontier<Host, Url>
+where
+    Host: Eq + Hash + Clone,
+    Url: Eq + Hash + Clone,
+{
+    /* # helper functions */
+
+    /// offer the host for crawling, provided it is not resting and has urls left
+    fn refresh_host(&mut self, host: &Host) -> Result<(), Error> {
+        if self.resting.contains(host) {
+            return Ok(());
+        }
+        match self.hosts.get(host).and_then(HashQueue::peek_cloned) {
+            Some((_, priority)) => {
+                self.ready.push(host.clone(), priority)?;
+            }
+            None => {
+                self.hosts.remove(host);
+                if self.ready.contains(host) {
+                    self.ready.delete(host)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// let hosts which have rested long enough be crawled again
+    fn wake_hosts(&mut self, now: u64) -> Result<(), Error> {
+        while let Some((host, until)) = self.resting.peek_cloned() {
+            if until > now {
+                break;
+            }
+            self.resting.delete(&host)?;
+            self.refresh_host(&host)?;
+        }
+        Ok(())
+    }
+
+    /* # frontier functionality */
+
+    /// construct empty frontier with given politeness delay and aging rate
+    #[must_use]
+    pub fn new(politeness: u64, aging: u64) -> Self {
+        Self {
+            politeness,
+            aging,
+            hosts: HashMap::new(),
+            resting: HashQueue::new(),
+            ready: HashQueue::new(),
+            url_count: 0,
+        }
+    }
+
+    /// returns true if no urls are queued
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.url_count == 0
+    }
+
+    /// returns the number of queued urls
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.url_count
+    }
+
+    /// returns the tick at which the next resting host can be crawled, if any host is resting
+    #[must_use]
+    pub fn next_ready_at(&self) -> Option<u64> {
+        self.resting.peek_cloned().map(|(_, until)| until)
+    }
+
+    /**
+    queue a url of a host with given score at the current tick, lower scores are crawled first,
+    a url which is already queued keeps the better of its two priorities
+
+    # Errors
+    will error if the frontier is already at capacity
+    */
+    pub fn push(&mut self, host: Host, url: Url, score: u64, now: u64) -> Result<(), Error> {
+        let priority = Priority::by(score).aged_by(self.aging, now);
+        let urls = self.hosts.entry(host.clone()).or_default();
+        if urls.push_or_decrease(url, priority)? == Upsert::Pushed {
+            self.url_count += 1;
+        }
+        self.refresh_host(&host)
+    }
+
+    /**
+    take the best url of the best host which is not resting at the current tick,
+    or nothing if every host with urls is resting
+
+    # Errors
+    NotFound => internal indexing error
+    */
+    pub fn pop(&mut self, now: u64) -> Result<Option<(Host, Url)>, Error> {
+        self.wake_hosts(now)?;
+        let (host, _) = match self.ready.pop() {
+            Ok(best) => best,
+            Err(Error::Empty) => return Ok(None),
+            Err(error) => return Err(error),
+        };
+        let (url, _) = self
+            .hosts
+            .get_mut(&host)
+            .ok_or(Error::NotFound("host"))?
+            .pop()?;
+        self.url_count -= 1;
+        self.resting
+            .push(host.clone(), now.saturating_add(self.politeness))?;
+        Ok(Some((host, url)))
+    }
+}
@@ -0,0 +1,232 @@
+use crate::{error::Error, handle::Handle, heap::BareQueue};
+use alloc::{vec, vec::Vec};
+
+/* # shortest paths */
+
+/**
+lengths of the shortest paths from the source to every vertex of a graph with weighted edges,
+or nothing for vertices which cannot be reached
+
+the graph is given as a list of outgoing edges for every vertex, each a target vertex and a weight
+
+```
+use fbheap::graph::dijkstra;
+
+let adjacency = vec![
+    vec![(1, 7), (2, 2)],
+    vec![(3, 1)],
+    vec![(1, 3), (3, 8)],
+    vec![],
+    vec![(0, 1)],
+];
+assert_eq!(
+    dijkstra(&adjacency, 0),
+    Ok(vec![Some(0), Some(5), Some(2), Some(6), None])
+);
+```
+
+# Errors
+NotFound => the source or the target of an edge is not a vertex of the graph\n
+Numerical => the length of a path does not fit into the weight type
+*/
+pub fn dijkstra(adjacency: &[Vec<(usize, u64)>], source: usize) -> Result<Vec<Option<u64>>, Error> {
+    if source >= adjacency.len() {
+        return Err(Error::NotFound("vertex"));
+    }
+    let mut distances = vec![None; adjacency.len()];
+    let mut handles: Vec<Option<Handle<usize, u64>>> = vec![None; adjacency.len()];
+    let mut queue = BareQueue::new();
+    handles[source] = Some(queue.try_push_with_handle(source, 0)?);
+
+    loop {
+        let (vertex, distance) = match queue.pop() {
+            Ok(closest) => closest,
+            Err(Error::Empty) => return Ok(distances),
+            Err(error) => return Err(error),
+        };
+        distances[vertex] = Some(distance);
+        for &(target, weight) in &adjacency[vertex] {
+            if distances
+                .get(target)
+                .ok_or(Error::NotFound("vertex"))?
+                .is_some()
+            {
+                continue;
+            }
+            let candidate = distance.checked_add(weight).ok_or(Error::Numerical)?;
+            match &handles[target] {
+                Some(handle) => match queue.decrease_priority_at(handle, candidate) {
+                    Ok(()) | Err(Error::CannotIncreasePriority { .. }) => {}
+                    Err(error) => return Err(error),
+                },
+                None => handles[target] = Some(queue.try_push_with_handle(target, candidate)?),
+            }
+        }
+    }
+}
+
+/**
+cheapest path from the source to the target of a graph with weighted edges, guided by a heuristic
+estimating the remaining cost from every vertex, together with the cost of the path,
+or nothing if the target cannot be reached
+
+vertices are queued by the cost of reaching them plus the estimate for them,
+so that an estimate which never exceeds the actual remaining cost yields a cheapest path,
+and a vertex is queued again whenever a cheaper way to it turns up after it was popped
+
+```
+use fbheap::graph::astar;
+
+// a grid of three by three cells, with a wall in the middle column except for the bottom row
+let (width, wall) = (3, [1, 4]);
+let adjacency: Vec<Vec<(usize, u64)>> = (0..9)
+    .map(|cell: usize| {
+        let (x, y) = (cell % width, cell / width);
+        [(x > 0, cell.wrapping_sub(1)), (x < 2, cell + 1), (y > 0, cell.wrapping_sub(width)), (y < 2, cell + width)]
+            .into_iter()
+            .filter(|&(inside, next)| inside && !wall.contains(&next) && !wall.contains(&cell))
+            .map(|(_, next)| (next, 1))
+            .collect()
+    })
+    .collect();
+let manhattan = |cell: usize| ((2 - cell % width) + cell / width) as u64;
+assert_eq!(
+    astar(&adjacency, 0, 2, manhattan),
+    Ok(Some((6, vec![0, 3, 6, 7, 8, 5, 2])))
+);
+assert_eq!(astar(&adjacency, 0, 4, manhattan), Ok(None));
+```
+
+# Errors
+NotFound => the source, the target or the target of an edge is not a vertex of the graph\n
+Numerical => the cost of a path does not fit into the weight type
+*/
+pub fn astar(
+    adjacency: &[Vec<(usize, u64)>],
+    source: usize,
+    target: usize,
+    heuristic: impl Fn(usize) -> u64,
+) -> Result<Option<(u64, Vec<usize>)>, Error> {
+    if source >= adjacency.len() || target >= adjacency.len() {
+        return Err(Error::NotFound("vertex"));
+    }
+    let mut costs = vec![None; adjacency.len()];
+    let mut parents = vec![None; adjacency.len()];
+    let mut handles: Vec<Option<Handle<usize, u64>>> = vec![None; adjacency.len()];
+    let mut queue = BareQueue::new();
+    costs[source] = Some(0);
+    handles[source] = Some(queue.try_push_with_handle(source, heuristic(source))?);
+
+    loop {
+        let (vertex, _) = match queue.pop() {
+            Ok(closest) => closest,
+            Err(Error::Empty) => return Ok(None),
+            Err(error) => return Err(error),
+        };
+        let cost = costs[vertex].ok_or(Error::NotFound("vertex"))?;
+        if vertex == target {
+            let mut path = vec![target];
+            while let Some(parent) = parents[path[path.len() - 1]] {
+                path.push(parent);
+            }
+            path.reverse();
+            return Ok(Some((cost, path)));
+        }
+        for &(next, weight) in &adjacency[vertex] {
+            let candidate = cost.checked_add(weight).ok_or(Error::Numerical)?;
+            let current = costs.get(next).ok_or(Error::NotFound("vertex"))?;
+            if current.is_some_and(|current| current <= candidate) {
+                continue;
+            }
+            costs[next] = Some(candidate);
+            parents[next] = Some(vertex);
+            let estimate = candidate
+                .checked_add(heuristic(next))
+                .ok_or(Error::Numerical)?;
+            match &handles[next] {
+                Some(handle) if !handle.is_stale() => {
+                    match queue.decrease_priority_at(handle, estimate) {
+                        Ok(()) | Err(Error::CannotIncreasePriority { .. }) => {}
+                        Err(error) => return Err(error),
+                    }
+                }
+                _ => handles[next] = Some(queue.try_push_with_handle(next, estimate)?),
+            }
+        }
+    }
+}
+
+/* # spanning trees */
+
+/// edge of a spanning tree, as the vertex it was reached from, the vertex itself and the weight of the edge
+pub type TreeEdge = (usize, usize, u64);
+
+/**
+edges of a minimum spanning forest of a graph with weighted undirected edges,
+together with their total weight
+
+every edge is expected in the list of both its vertices, and each tree is grown from its lowest vertex
+
+```
+use fbheap::graph::prim_mst;
+
+let edges = [(0, 1, 4), (0, 2, 1), (1, 2, 2), (1, 3, 5), (2, 3, 8), (4, 5, 3)];
+let mut adjacency = vec![vec![]; 6];
+for (a, b, weight) in edges {
+    adjacency[a].push((b, weight));
+    adjacency[b].push((a, weight));
+}
+assert_eq!(
+    prim_mst(&adjacency),
+    Ok((vec![(0, 2, 1), (2, 1, 2), (1, 3, 5), (4, 5, 3)], 11))
+);
+```
+
+# Errors
+NotFound => the target of an edge is not a vertex of the graph\n
+Numerical => the total weight does not fit into the weight type
+*/
+pub fn prim_mst(adjacency: &[Vec<(usize, u64)>]) -> Result<(Vec<TreeEdge>, u64), Error> {
+    let mut spanned = vec![false; adjacency.len()];
+    let mut parents = vec![None; adjacency.len()];
+    let mut handles: Vec<Option<Handle<usize, u64>>> = vec![None; adjacency.len()];
+    let mut queue = BareQueue::new();
+    let mut edges = Vec::new();
+    let mut total: u64 = 0;
+
+    for root in 0..adjacency.len() {
+        if spanned[root] {
+            continue;
+        }
+        handles[root] = Some(queue.try_push_with_handle(root, 0)?);
+        loop {
+            let (vertex, weight) = match queue.pop() {
+                Ok(closest) => closest,
+                Err(Error::Empty) => break,
+                Err(error) => return Err(error),
+            };
+            spanned[vertex] = true;
+            if let Some(parent) = parents[vertex] {
+                edges.push((parent, vertex, weight));
+                total = total.checked_add(weight).ok_or(Error::Numerical)?;
+            }
+            for &(target, weight) in &adjacency[vertex] {
+                if *spanned.get(target).ok_or(Error::NotFound("vertex"))? {
+                    continue;
+                }
+                match &handles[target] {
+                    Some(handle) => match queue.decrease_priority_at(handle, weight) {
+                        Ok(()) => parents[target] = Some(vertex),
+                        Err(Error::CannotIncreasePriority { .. }) => {}
+                        Err(error) => return Err(error),
+                    },
+                    None => {
+                        handles[target] = Some(queue.try_push_with_handle(target, weight)?);
+                        parents[target] = Some(vertex);
+                    }
+                }
+            }
+        }
+    }
+    Ok((edges, total))
+}
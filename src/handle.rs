@@ -0,0 +1,111 @@
+use crate::node::{NRef, NWeak};
+use alloc::rc::{Rc, Weak};
+use core::cell::RefCell;
+
+/// entry kept by a queue for every handle it gave out
+pub type HandleEntry<T, Priority> = Weak<NWeak<T, Priority>>;
+
+/**
+reference to a single element of a queue, which does not keep the element alive
+
+once the element leaves the queue the handle becomes stale, and operations using it fail with `StaleHandle`
+*/
+pub struct Handle<T, Priority>
+where
+    Priority: Eq,
+{
+    /// weak reference to the node, shared between clones of the handle
+    node: Rc<NWeak<T, Priority>>,
+}
+
+impl<T, Priority> Clone for Handle<T, Priority>
+where
+    Priority: Eq,
+{
+    fn clone(&self) -> Self {
+        Self {
+            node: Rc::clone(&self.node),
+        }
+    }
+}
+
+/// handles are equal when they point to the same element
+impl<T, Priority> PartialEq for Handle<T, Priority>
+where
+    Priority: Eq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.node, &other.node) || Weak::ptr_eq(&*self.node, &*other.node)
+    }
+}
+
+impl<T, Priority> Eq for Handle<T, Priority> where Priority: Eq {}
+
+impl<T, Priority> Handle<T, Priority>
+where
+    Priority: Eq,
+{
+    pub(crate) fn new(node: &NRef<T, Priority>) -> Self {
+        Self {
+            node: Rc::new(Rc::downgrade(node)),
+        }
+    }
+
+    /// entry by which the queue can tell whether the handle is still around
+    pub(crate) fn entry(&self) -> HandleEntry<T, Priority> {
+        Rc::downgrade(&self.node)
+    }
+
+    pub(crate) fn node(&self) -> Option<NRef<T, Priority>> {
+        self.node.upgrade()
+    }
+
+    /// returns true if the element this handle points to is no longer in any queue
+    #[must_use]
+    pub fn is_stale(&self) -> bool {
+        self.node.strong_count() == 0
+    }
+}
+
+/**
+identity of a queue, held by each of its nodes, so that a handle can be checked against a queue
+without walking the queue
+
+a queue melded into another one forwards its identity to the other, instead of visiting its nodes,
+and following the forwards points every identity on the way directly at the one they end in
+*/
+pub(crate) struct Owner {
+    /// identity of the queue this one was melded into
+    forward: RefCell<Option<Rc<Owner>>>,
+}
+
+impl Owner {
+    pub(crate) fn new() -> Rc<Self> {
+        Rc::new(Self {
+            forward: RefCell::new(None),
+        })
+    }
+
+    fn next(&self) -> Option<Rc<Self>> {
+        self.forward.borrow().clone()
+    }
+
+    /// mark this identity as melded into the given one
+    pub(crate) fn forward_to(&self, owner: &Rc<Self>) {
+        *self.forward.borrow_mut() = Some(Rc::clone(owner));
+    }
+
+    /// returns the identity the forwards end in, shortening the chain on the way
+    pub(crate) fn resolve(owner: &Rc<Self>) -> Rc<Self> {
+        let mut end = Rc::clone(owner);
+        while let Some(next) = end.next() {
+            end = next;
+        }
+        let mut current = Rc::clone(owner);
+        while !Rc::ptr_eq(&current, &end) {
+            let next = current.forward.replace(Some(Rc::clone(&end)));
+            current = next.expect("every identity before the end of the chain forwards");
+        }
+        end
+    }
+}
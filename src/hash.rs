@@ -0,0 +1,212 @@
+use crate::{
+    error::Error,
+    heap::{BareQueue, Upsert},
+    node::{NPrpt, NRef, NWeak},
+};
+use core::hash::Hash;
+use std::{collections::HashMap, rc::Rc};
+
+/* # hash queue */
+
+/**
+fibonacci queue for hashable values, which keeps an index from values to nodes
+
+looking up a value is constant time, instead of a search through the whole queue,
+so `decrease_priority`, `contains` and `delete` are cheap
+
+```
+use fbheap::error::Error::Empty;
+use fbheap::hash::HashQueue;
+
+let mut queue = HashQueue::new();
+queue.push("i was first", 3);
+queue.push("i am important", 1);
+queue.push("i was not important at first", 4);
+assert_eq!(queue.pop(), Ok(("i am important", 1)));
+queue.decrease_priority(&"i was not important at first", 2);
+assert!(queue.contains(&"i was not important at first"));
+assert_eq!(queue.priority_of(&"i was not important at first"), Some(2));
+assert_eq!(queue.pop(), Ok(("i was not important at first", 2)));
+assert_eq!(queue.push("i was first", 5), Ok(Some(3)));
+assert_eq!(queue.pop(), Ok(("i was first", 5)));
+assert!(queue.is_empty());
+assert_eq!(queue.pop(), Err(Empty));
+```
+*/
+pub struct HashQueue<T, Priority>
+where
+    T: Eq + Hash + Clone,
+    Priority: Ord,
+{
+    /// underlying queue holding the nodes
+    queue: BareQueue<T, Priority>,
+    /// references to nodes by their value, weak so that popped nodes can be released
+    index: HashMap<T, NWeak<T, Priority>>,
+}
+
+impl<T, Priority> Default for HashQueue<T, Priority>
+where
+    T: Eq + Hash + Clone,
+    Priority: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, Priority> HashQueue<T, Priority>
+where
+    T: Eq + Hash + Clone,
+    Priority: Ord,
+{
+    /* # helper functions */
+
+    fn get_node(&self, t: &T) -> Option<NRef<T, Priority>> {
+        self.index.get(t).and_then(NWeak::upgrade)
+    }
+
+    /* # heap functionality */
+
+    /// construct empty queue
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            queue: BareQueue::new(),
+            index: HashMap::new(),
+        }
+    }
+
+    /// returns true if the queue is empty
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// returns the number of elements in the queue
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// returns true if the queue holds the given value
+    #[must_use]
+    pub fn contains(&self, t: &T) -> bool {
+        self.index.contains_key(t)
+    }
+
+    /**
+    push a value onto the queue with given priority,
+    replacing and returning the priority of the value if it was already queued
+
+    # Errors
+    will error if the queue is already at capacity
+    */
+    pub fn push(&mut self, t: T, priority: Priority) -> Result<Option<Priority>, Error> {
+        let previous = if self.contains(&t) {
+            Some(self.delete(&t)?.1)
+        } else {
+            None
+        };
+        let node = self.queue.push_node(t.clone(), priority)?;
+        self.index.insert(t, Rc::downgrade(&node));
+        Ok(previous)
+    }
+
+    /**
+    push a value onto the queue, or decrease its priority if it is already queued with a higher one
+
+    # Errors
+    will error if the queue is already at capacity
+    */
+    pub fn push_or_decrease(&mut self, t: T, priority: Priority) -> Result<Upsert, Error> {
+        match self.get_node(&t) {
+            Some(node) if node.has_higher_priority(&priority) => {
+                self.queue.decrease_node_priority(node, priority)?;
+                Ok(Upsert::Decreased)
+            }
+            Some(_) => Ok(Upsert::Kept),
+            None => {
+                let node = self.queue.push_node(t.clone(), priority)?;
+                self.index.insert(t, Rc::downgrade(&node));
+                Ok(Upsert::Pushed)
+            }
+        }
+    }
+
+    /**
+    return the element with the lowest priority
+
+    # Errors
+    Empty => cannot return element from empty queue\n
+    NotFound => internal indexing error
+    */
+    pub fn pop(&mut self) -> Result<(T, Priority), Error> {
+        let (t, priority) = self.queue.pop()?;
+        self.index.remove(&t);
+        Ok((t, priority))
+    }
+
+    /**
+    decreases the priority of the item with given value
+
+    # Errors
+    NotFound => index with given value was not found in the queue\n
+    CannotIncreasePriority => the give prioprity is higher than the current one for the index of that value
+    */
+    pub fn decrease_priority(&mut self, value: &T, new_priority: Priority) -> Result<(), Error> {
+        let node = self.get_node(value).ok_or(Error::NotFound("value"))?;
+        self.queue.decrease_node_priority(node, new_priority)
+    }
+
+    /**
+    increases the priority of the item with given value
+
+    # Errors
+    NotFound => index with given value was not found in the queue\n
+    CannotDecreasePriority => the given priority is lower than the current one for the index of that value
+    */
+    pub fn increase_priority(&mut self, value: &T, new_priority: Priority) -> Result<(), Error> {
+        let node = self.get_node(value).ok_or(Error::NotFound("value"))?;
+        self.queue.increase_node_priority(&node, new_priority)
+    }
+
+    /**
+    changes the priority of the item with given value, in whichever direction
+
+    # Errors
+    NotFound => index with given value was not found in the queue
+    */
+    pub fn update_priority(&mut self, value: &T, new_priority: Priority) -> Result<(), Error> {
+        let node = self.get_node(value).ok_or(Error::NotFound("value"))?;
+        self.queue.update_node_priority(node, new_priority)
+    }
+
+    /**
+    remove the item with given value from the queue, regardless of its priority
+
+    # Errors
+    NotFound => index with given value was not found in the queue
+    */
+    pub fn delete(&mut self, value: &T) -> Result<(T, Priority), Error> {
+        let node = self.get_node(value).ok_or(Error::NotFound("value"))?;
+        self.index.remove(value);
+        self.queue.remove_node(&node)?;
+        node.pair()
+    }
+}
+
+impl<T, Priority> HashQueue<T, Priority>
+where
+    T: Eq + Hash + Clone,
+    Priority: Ord + Clone,
+{
+    /// returns the priority of the given value, if the queue holds it
+    #[must_use]
+    pub fn priority_of(&self, value: &T) -> Option<Priority> {
+        self.get_node(value).map(|node| node.priority_ref().clone())
+    }
+
+    pub(crate) fn peek_cloned(&self) -> Option<(T, Priority)> {
+        self.queue.peek_cloned()
+    }
+}
@@ -1,12 +1,80 @@
+#[cfg(feature = "fuzzing")]
+use crate::model::{Model, Op};
 use crate::{
     error::Error,
-    node::{NPrpt, NRef},
+    explain::{Step, Trace},
+    handle::{Handle, HandleEntry, Owner},
+    inspect::NodeRef,
+    lookup::{Lookup, LookupIndex},
+    node::{NList, NPrpt, NRef, NWeak},
+    policy::{Consolidation, Workload},
+    queue::PriorityQueue,
+    stats::{Metrics, QueueStats},
+};
+use alloc::{
+    boxed::Box,
+    collections::{BinaryHeap, VecDeque},
+    rc::Rc,
+    string::String,
+    vec::Vec,
+};
+use core::{
+    cell::{Cell, Ref, RefMut},
+    cmp::Reverse,
+    fmt,
+    marker::PhantomData,
+    mem::{replace, swap, take},
+    ops::{Bound, RangeBounds, Sub},
 };
-use core::mem::swap;
-use std::collections::VecDeque;
 
 /* # bare queue */
 
+/**
+upper bound on the number of distinct ranks of trees in a queue holding the given number of nodes
+
+a tree of rank r holds at least ϕ^r nodes, so ranks never exceed log_ϕ(n),
+which is bounded by one and a half times the number of binary digits of n,
+computed without ever overflowing or casting to floats
+
+```
+use fbheap::heap::max_rank;
+
+assert_eq!(max_rank(0), 0);
+for node_count in [1, 2, 3, 1_000, 2_642_246, 1 << 40, usize::MAX / 3, usize::MAX] {
+    let log_phi = (node_count as f64).ln() / 1.618_033_988_749_895_f64.ln();
+    assert!(max_rank(node_count) as f64 >= log_phi + 1.0);
+    assert!((max_rank(node_count) as f64) < 1.1 * log_phi + 4.0);
+}
+```
+*/
+#[must_use]
+pub const fn max_rank(node_count: usize) -> usize {
+    match node_count.checked_ilog2() {
+        None => 0,
+        Some(log) => (log as usize + 1) * 3 / 2 + 1,
+    }
+}
+
+/// structural event recorded while an operation is being explained
+enum Event<T, Priority>
+where
+    Priority: Eq,
+{
+    Link(NWeak<T, Priority>, NWeak<T, Priority>),
+    Cut(NWeak<T, Priority>, NWeak<T, Priority>),
+}
+
+/// outcome of pushing a value which might already be queued
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Upsert {
+    /// the value was not queued, so it was pushed
+    Pushed,
+    /// the value was queued with a higher priority, which was decreased
+    Decreased,
+    /// the value was queued with a priority no higher than the given one, which was kept
+    Kept,
+}
+
 /**
 fibonacci queue implemented for values that do not implement copy or hash
 
@@ -25,23 +93,60 @@ assert_eq!(queue.pop(), Ok(("i was first", 3)));
 assert!(queue.is_empty());
 assert_eq!(queue.pop(), Err(Empty));
 ```
+
+values only need to implement `Eq` for the operations which look them up,
+such as `decrease_priority` or `delete`, while pushing, popping and handles work for any value
+
+the queue is a multiset, so equal values can be queued several times, each with its own priority,
+and a lookup by value then affects some matching element, not a particular one,
+while a handle always reaches its own element, and `UniqueQueue` keeps every value at most once
+
+```
+use fbheap::heap::BareQueue;
+
+let mut jobs: BareQueue<Box<dyn Fn() -> u32>, u8> = BareQueue::new();
+jobs.push(Box::new(|| 2), 5);
+let urgent = jobs.push_with_handle(Box::new(|| 1), 9);
+jobs.decrease_priority_at(&urgent, 0)?;
+let results: Vec<_> = jobs.into_iter().map(|(job, _)| job()).collect();
+assert_eq!(results, vec![1, 2]);
+# Ok::<(), fbheap::error::Error>(())
+```
 */
 pub struct BareQueue<T, Priority>
 where
-    T: Eq,
     Priority: Ord,
 {
     /// list of roots
-    roots: Vec<NRef<T, Priority>>,
+    roots: NList<T, Priority>,
     /// reference to the node with the lowest priority, it such exists
     first: Option<NRef<T, Priority>>,
     /// number of nodes in the queue
     node_count: usize,
+    /// strategy deciding when to consolidate roots
+    consolidation: Consolidation,
+    /// operations performed so far
+    workload: Workload,
+    /// structural events, recorded only while explaining an operation
+    journal: Option<Vec<Event<T, Priority>>>,
+    /// whether lookups visit every node instead of stopping at the first match
+    exhaustive_lookup: bool,
+    /// sequence number given to the next pushed node, if ties are broken deterministically
+    sequence: Option<u64>,
+    /// strategy for finding values, searching through the trees if none is set
+    lookup: Option<Box<dyn LookupIndex<T, Priority>>>,
+    /// entries for handles given out, used to account for the ones still alive
+    handles: Vec<HandleEntry<T, Priority>>,
+    /// identity held by every node of the queue, created with the first node
+    owner: Option<Rc<Owner>>,
+    /// roots by rank while consolidating, kept empty in between so that its allocation is reused
+    ranks: Vec<Option<NRef<T, Priority>>>,
+    /// counters of elementary steps, only bumped with the instrument feature
+    metrics: Cell<Metrics>,
 }
 
 impl<T, Priority> Default for BareQueue<T, Priority>
 where
-    T: Eq,
     Priority: Ord,
 {
     fn default() -> Self {
@@ -51,7 +156,6 @@ where
 
 impl<T, Priority> BareQueue<T, Priority>
 where
-    T: Eq,
     Priority: Ord,
 {
     /* # helper functions */
@@ -71,23 +175,8 @@ where
         Ok(())
     }
 
-    fn max_node_rank(&self) -> Result<usize, Error> {
-        match self.node_count {
-            0 => Ok(0),
-            node_count => {
-                // this is never less than log_ϕ(x)+1
-                // and for x below 100 000 is only ever bigger by one
-                // and we never cast to floats
-                usize::try_from(
-                    node_count
-                        .pow(3)
-                        .ilog(4)
-                        .checked_add(1)
-                        .ok_or(Error::Numerical)?,
-                )
-                .map_err(|_| Error::Numerical)
-            }
-        }
+    const fn max_node_rank(&self) -> usize {
+        max_rank(self.node_count)
     }
 
     /* ## first element functions */
@@ -109,7 +198,19 @@ where
     }
 
     fn find_first(&self) -> Option<NRef<T, Priority>> {
-        self.roots.iter().min().cloned()
+        self.count(|metrics| metrics.comparisons += self.roots.len().saturating_sub(1));
+        self.roots.iter().min()
+    }
+
+    /// make a root the first node if its priority is lower than that of the current first node
+    fn offer_first(&mut self, node: NRef<T, Priority>) {
+        if self.first.is_some() {
+            self.count(|metrics| metrics.comparisons += 1);
+        }
+        match self.get_first() {
+            Some(first) if first <= &node => {}
+            _ => self.set_first(node),
+        }
     }
 
     /* ## root functions */
@@ -119,69 +220,333 @@ where
     }
 
     fn remove_root(&mut self, node: NRef<T, Priority>) -> Result<(), Error> {
-        // TODO : this should be O(1), but is not, would be if we had a proper linked list
-        self.roots.swap_remove(
-            self.roots
-                .iter()
-                .position(|x| x == &node)
-                .ok_or(Error::InvalidIndex)?,
-        );
-        Ok(())
+        self.roots.remove(&node)
+    }
+
+    /// take all nodes apart into their elements, in no particular order, leaving the queue empty
+    fn take_pairs(&mut self) -> Result<Vec<(T, Priority)>, Error> {
+        self.take_nodes().into_iter().map(NPrpt::pair).collect()
+    }
+
+    /// detach every node from the queue and from each other, leaving the queue empty
+    fn take_nodes(&mut self) -> Vec<NRef<T, Priority>> {
+        self.first = None;
+        self.node_count = 0;
+        let mut taken = Vec::new();
+        let mut nodes = self.drain_roots();
+        while let Some(node) = nodes.pop() {
+            nodes.extend(node.drain_children());
+            taken.push(node);
+        }
+        taken
     }
 
     fn drain_roots(&mut self) -> Vec<NRef<T, Priority>> {
-        self.roots.drain(..).collect()
+        if let Some(lookup) = &mut self.lookup {
+            lookup.clear();
+        }
+        self.roots.drain()
+    }
+
+    /* ## lookup functions */
+
+    fn is_tracking(&self) -> bool {
+        self.lookup
+            .as_ref()
+            .is_some_and(|lookup| lookup.is_tracking())
+    }
+
+    /// report a node entering the queue to the lookup strategy
+    fn index_insert(&mut self, node: &NRef<T, Priority>) {
+        if let Some(lookup) = self.lookup.as_mut().filter(|lookup| lookup.is_tracking()) {
+            lookup.insert(&node.value_ref(), Handle::new(node));
+        }
+    }
+
+    /// report a node leaving the queue to the lookup strategy
+    fn index_remove(&mut self, node: &NRef<T, Priority>) {
+        if let Some(lookup) = self.lookup.as_mut().filter(|lookup| lookup.is_tracking()) {
+            lookup.remove(&node.value_ref(), &Handle::new(node));
+        }
+    }
+
+    /* ## structural hooks */
+
+    /// bump the instrumentation counters, which does nothing without the instrument feature
+    #[cfg_attr(not(feature = "instrument"), allow(unused_variables))]
+    fn count(&self, bump: impl FnOnce(&mut Metrics)) {
+        #[cfg(feature = "instrument")]
+        {
+            let mut metrics = self.metrics.get();
+            bump(&mut metrics);
+            self.metrics.set(metrics);
+        }
+    }
+
+    fn record(&mut self, event: Event<T, Priority>) {
+        if let Some(journal) = &mut self.journal {
+            journal.push(event);
+        }
+    }
+
+    fn on_link(&mut self, parent: &NRef<T, Priority>, child: &NRef<T, Priority>) {
+        self.count(|metrics| {
+            metrics.links += 1;
+            metrics.comparisons += 1;
+        });
+        self.record(Event::Link(Rc::downgrade(parent), Rc::downgrade(child)));
+    }
+
+    fn on_cut(&mut self, node: &NRef<T, Priority>, parent: &NRef<T, Priority>, cascading: bool) {
+        self.count(|metrics| {
+            metrics.cuts += 1;
+            metrics.cascading_cuts += usize::from(cascading);
+        });
+        #[cfg(feature = "tracing")]
+        tracing::trace!(cascading, rank = node.rank(), "cut node");
+        self.record(Event::Cut(Rc::downgrade(node), Rc::downgrade(parent)));
     }
 
     /* ## structural functions */
 
-    fn consolidate(&mut self) -> Result<(), Error> {
-        let mut ranks: Vec<Option<NRef<T, Priority>>> =
-            (0..self.max_node_rank()?).map(|_| None).collect();
+    /// link roots of equal rank until all ranks differ, returning the root with the lowest priority
+    fn consolidate(&mut self) -> Result<Option<NRef<T, Priority>>, Error> {
+        let mut ranks = take(&mut self.ranks);
+        ranks.resize_with(self.max_node_rank(), || None);
+        self.count(|metrics| metrics.consolidations += 1);
+        #[cfg(feature = "tracing")]
+        let (roots_before, mut incoming_ranks) = (self.roots.len(), Vec::new());
 
-        for mut root in self.drain_roots() {
+        // roots are detached one at a time, so that draining them does not allocate either
+        while let Some(mut root) = self.roots.pop_front() {
             let mut rank = root.rank();
+            #[cfg(feature = "tracing")]
+            {
+                if incoming_ranks.len() <= rank {
+                    incoming_ranks.resize(rank + 1, 0_usize);
+                }
+                incoming_ranks[rank] += 1;
+            }
             // indexing is safe, since structural guarantees
-            while let Some(node) = &mut ranks[rank] {
-                root.link(node);
-                ranks[rank] = None;
+            while let Some(mut node) = ranks[rank].take() {
+                // keep the smaller of the two as the root of the linked tree
+                if node < root {
+                    swap(&mut root, &mut node);
+                }
+                root.link(&mut node);
+                self.on_link(&root, &node);
                 rank = root.rank();
             }
             ranks[rank] = Some(root);
         }
 
-        for node in ranks.into_iter().flatten() {
+        let mut first: Option<NRef<T, Priority>> = None;
+        for node in ranks.drain(..).flatten() {
+            if first.is_some() {
+                self.count(|metrics| metrics.comparisons += 1);
+            }
+            if first.as_ref().is_none_or(|first| &node < first) {
+                first = Some(node.clone());
+            }
             self.insert_root(node);
         }
-        Ok(())
+        self.ranks = ranks;
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            nodes = self.node_count,
+            roots_before,
+            roots_after = self.roots.len(),
+            ?incoming_ranks,
+            "consolidated roots"
+        );
+        Ok(first)
     }
 
-    /// separate node from its parent and add it to the list of roots
-    /// possibly recursively to satisfy structural bounds of the queue
-    fn cut_node(&mut self, node: NRef<T, Priority>) {
-        if let Some(parent) = node.get_parent() {
-            parent.mark();
+    /// separate node from its parent and add it to the list of roots,
+    /// continuing with every ancestor which has already lost a child
+    /// to satisfy structural bounds of the queue
+    fn cut_node(&mut self, node: NRef<T, Priority>) -> Result<(), Error> {
+        let mut node = node;
+        let mut cascading = false;
+        while let Some(parent) = node.get_parent() {
+            parent.remove_child(&node)?;
             node.remove_parent();
             self.insert_root(node.clone());
             node.unmark();
-            if parent.is_marked() {
-                self.cut_node(parent);
+            self.on_cut(&node, &parent, cascading);
+            if !parent.is_marked() {
+                parent.mark();
+                break;
             }
+            node = parent;
+            cascading = true;
         }
+        Ok(())
     }
 
-    fn get_node(&self, t: &T) -> Option<NRef<T, Priority>> {
-        // bfs on nodes
-        let mut q = self.roots.iter().cloned().collect::<VecDeque<_>>();
-        while let Some(node) = q.pop_front() {
-            if node.has_value(t) {
-                return Some(node);
-            }
-            for child in node.get_children() {
-                q.push_back(child);
-            }
+    /// move all children of a node to the list of roots
+    fn promote_children(&mut self, node: &NRef<T, Priority>) {
+        for child in node.drain_children() {
+            child.remove_parent();
+            child.unmark();
+            self.insert_root(child);
+        }
+    }
+
+    /// add a detached node to the list of roots
+    fn insert_node(&mut self, node: NRef<T, Priority>) -> Result<(), Error> {
+        self.increment_node_count()?;
+        node.set_owner(&self.owner());
+        self.index_insert(&node);
+        self.insert_root(node.clone());
+
+        if self.first.is_some() {
+            self.count(|metrics| metrics.comparisons += 1);
+        }
+        match self.get_first() {
+            Some(first) if first < &node => {}
+            _ => self.set_first(node),
+        }
+        Ok(())
+    }
+
+    /// detach a node from the queue, leaving it without parent or children
+    pub(crate) fn remove_node(&mut self, node: &NRef<T, Priority>) -> Result<(), Error> {
+        self.cut_node(node.clone())?;
+        self.promote_children(node);
+        self.remove_root(node.clone())?;
+        self.decrement_node_count()?;
+        self.index_remove(node);
+        if self
+            .get_first()
+            .is_some_and(|first| Rc::ptr_eq(first, node))
+        {
+            self.first = self.find_first();
+        }
+        Ok(())
+    }
+
+    fn decrease_node(&mut self, node: NRef<T, Priority>, priority: Priority) -> Result<(), Error> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("decrease_priority", nodes = self.node_count).entered();
+        self.lower_node(&node, priority)?;
+        // whether the node was a root already or was just cut, it may now beat the first node,
+        // while a node left below its parent cannot, as the parent does not beat the first node
+        if node.get_parent().is_none() {
+            self.offer_first(node);
+        }
+        Ok(())
+    }
+
+    /// set a lower priority and cut the node if it now beats its parent, leaving the first node as is
+    fn lower_node(&mut self, node: &NRef<T, Priority>, priority: Priority) -> Result<(), Error> {
+        node.set_priority(priority);
+        if node.get_parent().is_some() {
+            self.count(|metrics| metrics.comparisons += 1);
+        }
+        if node.get_parent().is_some_and(|parent| node < &parent) {
+            self.cut_node(node.clone())?;
+        }
+        self.workload.decreases = self.workload.decreases.saturating_add(1);
+        Ok(())
+    }
+
+    fn increase_node(&mut self, node: &NRef<T, Priority>, priority: Priority) -> Result<(), Error> {
+        // the node may now be bigger than its children, so it has to be isolated
+        self.cut_node(node.clone())?;
+        self.promote_children(node);
+        node.set_priority(priority);
+        if self
+            .get_first()
+            .is_some_and(|first| Rc::ptr_eq(first, node))
+        {
+            self.first = self.find_first();
+        }
+        Ok(())
+    }
+
+    /// restore the structure after the priority of the first node was changed in place
+    fn reposition_first(&mut self) {
+        if let Some(first) = self.first.clone() {
+            self.promote_children(&first);
+            self.first = self.find_first();
+        }
+    }
+
+    /// returns the identity of the queue, creating it if the queue never held a node
+    fn owner(&mut self) -> Rc<Owner> {
+        Rc::clone(self.owner.get_or_insert_with(Owner::new))
+    }
+
+    /// returns the node a handle points to, provided it belongs to this queue
+    pub(crate) fn get_handle_node(
+        &self,
+        handle: &Handle<T, Priority>,
+    ) -> Result<NRef<T, Priority>, Error> {
+        let node = handle.node().ok_or(Error::StaleHandle)?;
+        match (node.owner(), &self.owner) {
+            (Some(owner), Some(own)) if Rc::ptr_eq(&Owner::resolve(&owner), own) => Ok(node),
+            _ => Err(Error::NotFound("handle")),
+        }
+    }
+
+    /* ## node level functions */
+
+    pub(crate) fn push_node(
+        &mut self,
+        t: T,
+        priority: Priority,
+    ) -> Result<NRef<T, Priority>, Error> {
+        let node = NRef::<T, Priority>::new_node(t, priority);
+        if let Some(sequence) = self.sequence {
+            node.set_sequence(sequence);
+            self.sequence = Some(sequence.checked_add(1).ok_or(Error::Numerical)?);
+        }
+        self.insert_node(node.clone())?;
+        self.workload.pushes = self.workload.pushes.saturating_add(1);
+        Ok(node)
+    }
+
+    pub(crate) fn decrease_node_priority(
+        &mut self,
+        node: NRef<T, Priority>,
+        priority: Priority,
+    ) -> Result<(), Error> {
+        if node.has_higher_priority(&priority) {
+            self.decrease_node(node, priority)
+        } else {
+            Err(Error::CannotIncreasePriority {
+                current_was_lower: node.has_lower_priority(&priority),
+            })
+        }
+    }
+
+    pub(crate) fn increase_node_priority(
+        &mut self,
+        node: &NRef<T, Priority>,
+        priority: Priority,
+    ) -> Result<(), Error> {
+        if node.has_lower_priority(&priority) {
+            self.increase_node(node, priority)
+        } else {
+            Err(Error::CannotDecreasePriority {
+                current_was_higher: node.has_higher_priority(&priority),
+            })
+        }
+    }
+
+    pub(crate) fn update_node_priority(
+        &mut self,
+        node: NRef<T, Priority>,
+        priority: Priority,
+    ) -> Result<(), Error> {
+        if node.has_higher_priority(&priority) {
+            self.decrease_node(node, priority)
+        } else if node.has_lower_priority(&priority) {
+            self.increase_node(&node, priority)
+        } else {
+            Ok(())
         }
-        None
     }
 
     /* # heap functionality */
@@ -189,10 +554,267 @@ where
     /// construct empty queue
     #[must_use]
     pub const fn new() -> Self {
+        Self::with_consolidation(Consolidation::Eager)
+    }
+
+    /// construct empty queue with the given consolidation strategy
+    #[must_use]
+    pub const fn with_consolidation(consolidation: Consolidation) -> Self {
         Self {
-            roots: Vec::new(),
+            roots: NList::new(),
             first: None,
             node_count: 0,
+            consolidation,
+            workload: Workload::new(),
+            journal: None,
+            exhaustive_lookup: false,
+            sequence: None,
+            lookup: None,
+            handles: Vec::new(),
+            owner: None,
+            ranks: Vec::new(),
+            metrics: Cell::new(Metrics::new()),
+        }
+    }
+
+    /**
+    construct empty queue, with room for consolidating the given number of elements
+    without allocating
+
+    roots and children are linked through the nodes themselves, so that only the buffer
+    sorting roots by rank while consolidating depends on the number of elements
+
+    ```
+    use fbheap::heap::BareQueue;
+
+    let mut queue = BareQueue::with_capacity(1_000);
+    for x in 0..1_000 {
+        queue.push(x, x);
+    }
+    assert_eq!(queue.pop(), Ok((0, 0)));
+    ```
+    */
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut queue = Self::new();
+        queue.reserve(capacity);
+        queue
+    }
+
+    /// make room for consolidating at least the given number of elements more than are queued
+    pub fn reserve(&mut self, additional: usize) {
+        let ranks = max_rank(self.node_count.saturating_add(additional));
+        self.ranks.reserve(ranks.saturating_sub(self.ranks.len()));
+    }
+
+    /// change the strategy deciding when to consolidate roots
+    pub fn set_consolidation(&mut self, consolidation: Consolidation) {
+        self.consolidation = consolidation;
+    }
+
+    /**
+    make value lookups visit every node instead of stopping at the first match,
+    so that their duration does not reveal where in the queue a value sits
+
+    this only covers lookups, other operations still leak through timing:
+    comparisons take as long as the `Eq` and `Ord` implementations of values and priorities take,
+    `push` and `decrease_priority` branch on whether the new priority beats the current minimum,
+    and `pop` links trees depending on their priorities, so its duration follows the shape of the queue
+    */
+    pub fn set_exhaustive_lookup(&mut self, exhaustive: bool) {
+        self.exhaustive_lookup = exhaustive;
+    }
+
+    /**
+    change the strategy for finding values, recording all elements already queued in it,
+    see `LookupIndex` for the available strategies
+    */
+    pub fn set_lookup(&mut self, lookup: impl LookupIndex<T, Priority> + 'static) {
+        self.lookup = Some(Box::new(lookup));
+        let mut nodes: Vec<_> = self.roots.iter().collect();
+        while let Some(node) = nodes.pop() {
+            self.index_insert(&node);
+            nodes.extend(node.get_children());
+        }
+    }
+
+    /**
+    break ties between equal priorities by the order in which elements were pushed,
+    so that the order of popped elements depends only on the operations performed,
+    and not on how the trees of the queue happen to be linked or on how this crate links them
+
+    elements keep their place among equal priorities when their priority changes,
+    elements melded in from another queue are placed after all elements of this one,
+    keeping their order among themselves, and elements pushed while the mode was off
+    come before all others and tie among themselves as usual,
+    so the mode is best set right after constructing the queue
+
+    ```
+    use fbheap::heap::BareQueue;
+
+    let mut queue = BareQueue::new();
+    queue.set_deterministic(true);
+    for (value, priority) in [("a", 1), ("b", 0), ("c", 1), ("d", 0), ("e", 1)] {
+        queue.push(value, priority);
+    }
+    let mut other = BareQueue::new();
+    other.set_deterministic(true);
+    other.push("f", 0);
+    queue.meld(other)?;
+    queue.decrease_priority(&"e", 0)?;
+    let order: Vec<_> = queue.into_iter().map(|(value, _)| value).collect();
+    assert_eq!(order, vec!["b", "d", "e", "f", "a", "c"]);
+    # Ok::<(), fbheap::error::Error>(())
+    ```
+    */
+    pub fn set_deterministic(&mut self, deterministic: bool) {
+        self.sequence = match (deterministic, self.sequence) {
+            (true, Some(sequence)) => Some(sequence),
+            (true, None) => Some(1),
+            (false, _) => None,
+        };
+    }
+
+    /// returns the operations performed so far together with the current shape of the queue
+    #[must_use]
+    pub const fn workload(&self) -> Workload {
+        Workload {
+            roots: self.roots.len(),
+            nodes: self.node_count,
+            ..self.workload
+        }
+    }
+
+    /// returns the counters of elementary steps performed since the queue was built or last reset
+    #[cfg(feature = "instrument")]
+    #[must_use]
+    pub fn metrics(&self) -> Metrics {
+        self.metrics.get()
+    }
+
+    /// zero the counters of elementary steps
+    #[cfg(feature = "instrument")]
+    pub fn reset_metrics(&self) {
+        self.metrics.set(Metrics::new());
+    }
+
+    /**
+    iterate over the roots of the trees of the queue, in the order they are kept,
+    for looking at the structure from outside through `NodeView`
+
+    ```
+    use fbheap::heap::BareQueue;
+    use fbheap::inspect::NodeView;
+
+    let queue: BareQueue<_, _> = (0..4).map(|x| (x, x)).collect();
+    let ranks: Vec<_> = queue.roots().map(|root| root.rank()).collect();
+    assert_eq!(ranks, vec![0, 0, 0, 0]);
+    ```
+    */
+    pub fn roots(&self) -> impl Iterator<Item = NodeRef<'_, T, Priority>> {
+        self.roots.iter().map(NodeRef::new)
+    }
+
+    /// returns statistics about the current shape of the trees, taking time linear in their size
+    #[must_use]
+    pub fn stats(&self) -> QueueStats {
+        let mut stats = QueueStats {
+            nodes: self.node_count,
+            roots: self.roots.len(),
+            ..QueueStats::default()
+        };
+        let mut nodes: Vec<_> = self.roots.iter().map(|root| (root, 0)).collect();
+        while let Some((node, depth)) = nodes.pop() {
+            stats.max_rank = stats.max_rank.max(node.rank());
+            if node.is_marked() {
+                stats.marked += 1;
+            }
+            if stats.depths.len() == depth {
+                stats.depths.push(0);
+            }
+            stats.depths[depth] += 1;
+            nodes.extend(node.get_children().map(|child| (child, depth + 1)));
+        }
+        stats
+    }
+
+    /**
+    check the invariants of the trees, taking time linear in their size,
+    which is meant for tests and debugging rather than for regular operation
+
+    every child must point back to its parent and have no lower priority than it,
+    the rank of every node must match its number of children and stay below `max_rank`,
+    every node must be reachable from the roots, and `first` must be the root of lowest priority
+
+    ```
+    use fbheap::heap::BareQueue;
+
+    let mut queue: BareQueue<_, _> = (0..100).map(|value| (value, value)).collect();
+    queue.pop().unwrap();
+    queue.decrease_priority(&50, 0).unwrap();
+    queue.delete(&20).unwrap();
+    queue.assert_valid();
+    ```
+
+    # Panics
+    panics with a description of the first violated invariant
+    */
+    pub fn assert_valid(&self) {
+        assert_eq!(
+            self.roots.len(),
+            self.roots.iter().count(),
+            "length of root list does not match its roots"
+        );
+        let mut nodes: Vec<_> = self.roots.iter().collect();
+        for root in &nodes {
+            assert!(root.get_parent().is_none(), "root has a parent");
+        }
+        let mut reachable = 0;
+        while let Some(node) = nodes.pop() {
+            reachable += 1;
+            let children: Vec<_> = node.get_children().collect();
+            assert_eq!(
+                node.rank(),
+                children.len(),
+                "rank does not match number of children"
+            );
+            assert!(
+                node.rank() < self.max_node_rank(),
+                "rank exceeds bound for number of nodes"
+            );
+            for child in &children {
+                assert!(
+                    child
+                        .get_parent()
+                        .is_some_and(|parent| Rc::ptr_eq(&parent, &node)),
+                    "child does not point back to its parent"
+                );
+                assert!(
+                    *child.priority_ref() >= *node.priority_ref(),
+                    "child has lower priority than its parent"
+                );
+            }
+            nodes.extend(children);
+        }
+        assert_eq!(
+            reachable, self.node_count,
+            "node count does not match reachable nodes"
+        );
+
+        match self.get_first() {
+            Some(first) => {
+                assert!(
+                    self.roots.iter().any(|root| Rc::ptr_eq(&root, first)),
+                    "first is not a root"
+                );
+                assert!(
+                    self.roots
+                        .iter()
+                        .all(|root| *root.priority_ref() >= *first.priority_ref()),
+                    "first is not the root of lowest priority"
+                );
+            }
+            None => assert_eq!(self.node_count, 0, "non empty queue has no first"),
         }
     }
 
@@ -202,82 +824,2053 @@ where
         self.node_count == 0
     }
 
+    /// returns the number of elements in the queue
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.node_count
+    }
+
+    /**
+    returns how many elements the queue can hold while consolidating without allocating,
+    which grows through `with_capacity`, `reserve` and consolidating ever more elements
+
+    ```
+    use fbheap::heap::BareQueue;
+
+    let mut queue: BareQueue<u32, u32> = BareQueue::with_capacity(1_000);
+    assert!(queue.capacity() >= 1_000);
+    queue.extend((0..10).map(|x| (x, x)));
+    assert_eq!(queue.remaining_capacity(), queue.capacity() - 10);
+    ```
+    */
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        let ranks = self.ranks.capacity();
+        // every count of fewer bits than the first one whose ranks do not fit is covered
+        let mut bits = 0;
+        while bits < usize::BITS && max_rank(1 << bits) <= ranks {
+            bits += 1;
+        }
+        if bits == usize::BITS {
+            usize::MAX
+        } else {
+            (1 << bits) - 1
+        }
+    }
+
+    /// returns how many more elements the queue can take before consolidating has to allocate
+    #[must_use]
+    pub fn remaining_capacity(&self) -> usize {
+        self.capacity().saturating_sub(self.node_count)
+    }
+
     // fn peek(&self) -> Option<(&T, &Priority)>;
 
     /**
     push a value onto the queue with given priority
 
-    # Errors
-    will error if the queue is already at capacity
+    # Panics
+    panics if the queue is already at capacity,
+    which cannot happen in practice, as allocation fails long before the node count could overflow
     */
-    pub fn push(&mut self, t: T, priority: Priority) -> Result<(), Error> {
-        let next = NRef::<T, Priority>::new_node(t, priority);
-        self.insert_root(next.clone());
+    pub fn push(&mut self, t: T, priority: Priority) {
+        self.try_push(t, priority)
+            .expect("queue cannot account for additional nodes");
+    }
 
-        // there has to be a better way to write this conditional
-        if let Some(first) = self.get_first() && first < &next {
-                                } else {
-                                    self.set_first(next);
-                                }
-        self.increment_node_count()?;
+    /**
+    push a value onto the queue with given priority
+
+    # Errors
+    ReachedCapacity => the queue is already at capacity
+    */
+    pub fn try_push(&mut self, t: T, priority: Priority) -> Result<(), Error> {
+        self.push_node(t, priority)?;
         Ok(())
     }
 
+    /**
+    push a value onto the queue only if its priority is lower than the given bound,
+    otherwise hand the rejected pair back to the caller
+
+    # Errors
+    will error if the queue is already at capacity
+    */
+    pub fn try_push_within(
+        &mut self,
+        t: T,
+        priority: Priority,
+        bound: &Priority,
+    ) -> Result<Option<(T, Priority)>, Error> {
+        if priority < *bound {
+            self.try_push(t, priority)?;
+            Ok(None)
+        } else {
+            Ok(Some((t, priority)))
+        }
+    }
+
     /**
     return the element with the lowest priority
 
     # Errors
     Empty => cannot return element from empty queue\n
-    InvalidIndex => internal indexing error
+    NotFound => internal indexing error
     */
     pub fn pop(&mut self) -> Result<(T, Priority), Error> {
-        let mut extractee = None;
-        self.swap_first(&mut extractee);
-
-        let Some(first) = extractee else {
-            return Err(Error::Empty);
-        };
-
-        self.decrement_node_count()?;
-        self.remove_root(first.clone())?;
+        self.pop_node()?.pair()
+    }
 
-        for child in first.drain_children() {
-            child.remove_parent();
-            self.insert_root(child);
-        }
+    /**
+    return the value with the lowest priority, dropping its priority
 
-        self.consolidate()?;
+    ```
+    use fbheap::heap::BareQueue;
 
-        if let Some(new_first) = self.find_first() {
-            self.set_first(new_first);
-        }
+    let mut jobs = BareQueue::new();
+    jobs.push("compile", 2);
+    jobs.push("fetch", 1);
+    assert_eq!(jobs.pop_value(), Ok("fetch"));
+    assert_eq!(jobs.pop_value(), Ok("compile"));
+    ```
 
-        first.pair()
+    # Errors
+    Empty => cannot return element from empty queue\n
+    NotFound => internal indexing error
+    */
+    pub fn pop_value(&mut self) -> Result<T, Error> {
+        self.pop().map(|(t, _)| t)
     }
 
     /**
-    decreases the priority of the item with given value
+    return the lowest priority, dropping the value holding it
+
+    ```
+    use fbheap::heap::BareQueue;
+
+    let mut timestamps: BareQueue<_, _> = [((), 30), ((), 10), ((), 20)].into_iter().collect();
+    assert_eq!(timestamps.pop_priority(), Ok(10));
+    assert_eq!(timestamps.pop_priority(), Ok(20));
+    ```
 
     # Errors
-    InvalidIndex => index with given value was not found in the queue\n
-    CannotIncreasePriority => the give prioprity is higher than the current one for the index of that value
+    Empty => cannot return element from empty queue\n
+    NotFound => internal indexing error
     */
-    pub fn decrease_priority(&mut self, value: &T, new_priority: Priority) -> Result<(), Error> {
-        if let Some(node) = self.get_node(value) {
-            if node.has_higher_priority(&new_priority) {
-                node.set_priority(new_priority);
-                if let Some(parent) = node.get_parent() && node < parent {
-                                            self.cut_node(node.clone());
-                                            if let Some(first) = self.get_first() && &node < first {
-                                            self.set_first(node);
-                                            }
-                                        }
-                Ok(())
+    pub fn pop_priority(&mut self) -> Result<Priority, Error> {
+        self.pop().map(|(_, priority)| priority)
+    }
+
+    /**
+    pop the element with the lowest priority into caller provided storage,
+    returning whether there was one, and clearing the storage when the queue is empty
+
+    once the storage holds an element, the popped one is swapped into it in place
+    instead of being moved out of its node into a new pair,
+    so the node is freed along with the element the storage held before
+
+    ```
+    use fbheap::heap::BareQueue;
+
+    let mut events: BareQueue<_, _> = (0..1000).map(|tick| (tick % 7, tick)).collect();
+    let mut event = None;
+    let mut last = None;
+    while events.pop_min_into(&mut event)? {
+        let (_, tick) = event.as_ref().unwrap();
+        assert!(last < Some(*tick));
+        last = Some(*tick);
+    }
+    assert_eq!(event, None);
+    assert_eq!(last, Some(999));
+    # Ok::<(), fbheap::error::Error>(())
+    ```
+
+    # Errors
+    NotFound => internal indexing error\n
+    ImpossibleRcRelease => the popped node is still referenced elsewhere, while the storage is empty
+    */
+    pub fn pop_min_into(&mut self, out: &mut Option<(T, Priority)>) -> Result<bool, Error> {
+        if self.first.is_none() {
+            *out = None;
+            return Ok(false);
+        }
+        let node = self.pop_node()?;
+        match out {
+            Some(pair) => node.swap_pair(pair),
+            None => *out = Some(node.pair()?),
+        }
+        Ok(true)
+    }
+
+    /// detach the node with the lowest priority and restore the first node among the remaining ones
+    fn pop_node(&mut self) -> Result<NRef<T, Priority>, Error> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("pop", nodes = self.node_count, roots = self.roots.len())
+            .entered();
+        let mut extractee = None;
+        self.swap_first(&mut extractee);
+
+        let Some(first) = extractee else {
+            return Err(Error::Empty);
+        };
+
+        self.decrement_node_count()?;
+        self.remove_root(first.clone())?;
+        self.index_remove(&first);
+
+        self.promote_children(&first);
+
+        self.workload.pops = self.workload.pops.saturating_add(1);
+        self.first = if self.consolidation.should_consolidate(&self.workload()) {
+            self.consolidate()?
+        } else {
+            self.find_first()
+        };
+
+        Ok(first)
+    }
+
+    /**
+    return the element with the lowest priority, but only if that priority is at most the given bound,
+    leaving the queue untouched otherwise
+
+    ```
+    use fbheap::heap::BareQueue;
+
+    let mut events = BareQueue::new();
+    events.push("arrival", 3);
+    events.push("departure", 8);
+    let now = 5;
+    assert_eq!(events.pop_with_priority_at_most(&now), Ok(Some(("arrival", 3))));
+    assert_eq!(events.pop_with_priority_at_most(&now), Ok(None));
+    assert_eq!(events.len(), 1);
+    ```
+
+    # Errors
+    NotFound => internal indexing error
+    */
+    pub fn pop_with_priority_at_most(
+        &mut self,
+        bound: &Priority,
+    ) -> Result<Option<(T, Priority)>, Error> {
+        match self.get_first() {
+            Some(first) if !first.has_higher_priority(bound) => self.pop().map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /**
+    return all elements tied at the lowest priority, in no particular order
+    unless ties are broken deterministically, in which case they come in the order they were pushed,
+    consolidating the roots once after all of them are removed
+
+    ```
+    use fbheap::heap::BareQueue;
+
+    let mut frontier: BareQueue<_, _> = [("a", 3), ("b", 1), ("c", 1), ("d", 2), ("e", 1)]
+        .into_iter()
+        .collect();
+    let mut best = frontier.pop_equal_min()?;
+    best.sort();
+    assert_eq!(best, vec![("b", 1), ("c", 1), ("e", 1)]);
+    assert_eq!(frontier.pop_equal_min(), Ok(vec![("d", 2)]));
+    # Ok::<(), fbheap::error::Error>(())
+    ```
+
+    # Errors
+    Empty => cannot return elements from empty queue\n
+    ImpossibleRcRelease => a popped node is still referenced elsewhere
+    */
+    pub fn pop_equal_min(&mut self) -> Result<Vec<(T, Priority)>, Error> {
+        let first = self.first.take().ok_or(Error::Empty)?;
+
+        // ancestors of a tied node are tied as well, so the ties are reached from the roots down,
+        // every one of them after its parent
+        let mut tied = Vec::new();
+        let mut nodes: Vec<_> = self.roots.iter().collect();
+        while let Some(node) = nodes.pop() {
+            if *node.priority_ref() == *first.priority_ref() {
+                nodes.extend(node.get_children());
+                tied.push(node);
+            }
+        }
+        drop(first);
+
+        for node in &tied {
+            self.decrement_node_count()?;
+            self.remove_root(node.clone())?;
+            self.index_remove(node);
+            self.promote_children(node);
+        }
+        if self.sequence.is_some() {
+            tied.sort_by_key(NPrpt::sequence);
+        }
+
+        self.workload.pops = self.workload.pops.saturating_add(tied.len());
+        self.first = if self.consolidation.should_consolidate(&self.workload()) {
+            self.consolidate()?
+        } else {
+            self.find_first()
+        };
+
+        tied.into_iter().map(NPrpt::pair).collect()
+    }
+
+    /**
+    access the element with the lowest priority, allowing its priority to be changed in place
+
+    the queue is restored once the returned guard is dropped
+
+    ```
+    use fbheap::heap::BareQueue;
+
+    let mut queue = BareQueue::new();
+    queue.push("tick", 10);
+    queue.push("tock", 15);
+    if let Some(mut timer) = queue.peek_mut() {
+        assert_eq!(*timer.value(), "tick");
+        // re-arm the timer
+        *timer.priority_mut() += 10;
+    }
+    assert_eq!(queue.pop(), Ok(("tock", 15)));
+    assert_eq!(queue.pop(), Ok(("tick", 20)));
+    ```
+    */
+    pub fn peek_mut(&mut self) -> Option<PeekMut<'_, T, Priority>> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(PeekMut {
+                queue: self,
+                changed: false,
+            })
+        }
+    }
+
+    /**
+    iterate over the elements in order of increasing priority, removing them from the queue,
+    elements not taken from the iterator are removed once it is dropped
+
+    ```
+    use fbheap::heap::BareQueue;
+
+    let mut queue = BareQueue::new();
+    queue.push("b", 2);
+    queue.push("a", 1);
+    queue.push("c", 3);
+    assert_eq!(queue.drain_sorted().next(), Some(("a", 1)));
+    assert!(queue.is_empty());
+
+    queue.push("b", 2);
+    queue.push("a", 1);
+    let sorted: Vec<_> = queue.into_iter().collect();
+    assert_eq!(sorted, vec![("a", 1), ("b", 2)]);
+    ```
+    */
+    pub fn drain_sorted(&mut self) -> DrainSorted<'_, T, Priority> {
+        DrainSorted { queue: self }
+    }
+
+    /**
+    remove every element for which the given predicate returns false,
+    consolidating the roots at most once after all of them are removed, if the strategy asks for it
+
+    ```
+    use fbheap::{heap::BareQueue, policy::Consolidation};
+
+    let mut packets: BareQueue<_, _> = (0..20).map(|id| ((id % 3, id), id)).collect();
+    packets.pop();
+    // connection 1 was closed
+    packets.retain(|&(connection, _), _| connection != 1);
+    assert_eq!(packets.len(), 12);
+    assert_eq!(packets.pop(), Ok(((2, 2), 2)));
+    assert_eq!(packets.pop(), Ok(((0, 3), 3)));
+
+    // a lazy queue leaves its roots as they are
+    let mut lazy = BareQueue::with_consolidation(Consolidation::Lazy(usize::MAX));
+    lazy.extend((0..6).map(|value| (value, value)));
+    lazy.retain(|value, _| value % 2 == 1);
+    assert_eq!(lazy.stats().roots, 3);
+    ```
+
+    # Errors
+    NotFound => internal indexing error
+    */
+    pub fn retain(&mut self, mut keep: impl FnMut(&T, &Priority) -> bool) -> Result<(), Error> {
+        let mut nodes: Vec<_> = self.roots.iter().collect();
+        let mut removed = Vec::new();
+        while let Some(node) = nodes.pop() {
+            nodes.extend(node.get_children());
+            if !keep(&node.value_ref(), &node.priority_ref()) {
+                removed.push(node);
+            }
+        }
+        if removed.is_empty() {
+            return Ok(());
+        }
+
+        for node in &removed {
+            self.cut_node(node.clone())?;
+            self.promote_children(node);
+            self.remove_root(node.clone())?;
+            self.decrement_node_count()?;
+            self.index_remove(node);
+        }
+        self.first = if self.consolidation.should_consolidate(&self.workload()) {
+            self.consolidate()?
+        } else {
+            self.find_first()
+        };
+        Ok(())
+    }
+
+    /**
+    split the queue at the given bound, returning a queue of all elements with priority at or
+    above it and keeping the ones below, with the same consolidation strategy
+
+    by heap order a node at or above the bound carries its whole subtree along,
+    so trees are cut where they cross the bound and moved over as they are, without linking any
+
+    ```
+    use fbheap::heap::BareQueue;
+
+    let mut jobs: BareQueue<_, _> = (0..20).map(|job| (job, job * 10)).collect();
+    assert_eq!(jobs.pop(), Ok((0, 0)));
+    // shed everything due past the cutoff
+    let mut late = jobs.split_off(&125)?;
+    assert_eq!(jobs.len(), 12);
+    assert_eq!(late.len(), 7);
+    assert_eq!(jobs.into_sorted_vec()?.last(), Some(&(12, 120)));
+    assert_eq!(late.pop(), Ok((13, 130)));
+    # Ok::<(), fbheap::error::Error>(())
+    ```
+
+    # Errors
+    NotFound => internal indexing error
+    */
+    pub fn split_off(&mut self, bound: &Priority) -> Result<Self, Error> {
+        let mut split = Self::with_consolidation(self.consolidation);
+        split.sequence = self.sequence;
+        split.lookup = self.lookup.as_ref().map(|lookup| lookup.new_empty());
+        // every element is at or above the bound, so the whole queue moves over
+        if self
+            .get_first()
+            .is_some_and(|first| *first.priority_ref() >= *bound)
+        {
+            split.append(self)?;
+            return Ok(split);
+        }
+
+        // otherwise the first node stays, so only the nodes below the bound need to be visited
+        let mut nodes: Vec<_> = self.roots.iter().collect();
+        let mut moved = Vec::new();
+        while let Some(node) = nodes.pop() {
+            if *node.priority_ref() >= *bound {
+                moved.push(node);
             } else {
-                Err(Error::CannotIncreasePriority)
+                nodes.extend(node.get_children());
             }
+        }
+
+        for node in moved {
+            self.cut_node(node.clone())?;
+            self.remove_root(node.clone())?;
+            let mut subtree = alloc::vec![node.clone()];
+            while let Some(descendant) = subtree.pop() {
+                subtree.extend(descendant.get_children());
+                self.decrement_node_count()?;
+                split.increment_node_count()?;
+                self.index_remove(&descendant);
+                split.index_insert(&descendant);
+                descendant.set_owner(&split.owner());
+            }
+            split.insert_root(node.clone());
+            split.offer_first(node);
+        }
+        Ok(split)
+    }
+
+    /**
+    return up to the given number of elements with the lowest priorities, in order of increasing priority,
+    consolidating the roots once after all of them are removed
+
+    by heap order the lowest elements are found among the roots and the children of elements already taken,
+    so they are picked from a frontier of candidates without changing the trees
+
+    ```
+    use fbheap::heap::BareQueue;
+
+    let mut queue: BareQueue<_, _> = [("c", 3), ("a", 1), ("d", 4), ("b", 2)].into_iter().collect();
+    assert_eq!(queue.pop_k(2), Ok(vec![("a", 1), ("b", 2)]));
+    assert_eq!(queue.pop_k(5), Ok(vec![("c", 3), ("d", 4)]));
+    assert_eq!(queue.pop_k(1), Ok(vec![]));
+    ```
+
+    # Errors
+    NotFound => internal indexing error\n
+    ImpossibleRcRelease => a popped node is still referenced elsewhere
+    */
+    pub fn pop_k(&mut self, k: usize) -> Result<Vec<(T, Priority)>, Error> {
+        let k = k.min(self.len());
+        if k == 0 {
+            return Ok(Vec::new());
+        }
+        self.first = None;
+
+        // every node is taken after its parent, by which time it is a root
+        let mut frontier: BinaryHeap<_> = self.roots.iter().map(Reverse).collect();
+        let mut taken = Vec::with_capacity(k);
+        while taken.len() < k {
+            let Some(Reverse(node)) = frontier.pop() else {
+                break;
+            };
+            frontier.extend(node.get_children().map(Reverse));
+            taken.push(node);
+        }
+        drop(frontier);
+
+        for node in &taken {
+            self.decrement_node_count()?;
+            self.remove_root(node.clone())?;
+            self.index_remove(node);
+            self.promote_children(node);
+        }
+
+        self.workload.pops = self.workload.pops.saturating_add(taken.len());
+        self.first = if self.consolidation.should_consolidate(&self.workload()) {
+            self.consolidate()?
         } else {
-            Err(Error::InvalidIndex)
+            self.find_first()
+        };
+
+        taken.into_iter().map(NPrpt::pair).collect()
+    }
+
+    /**
+    consume the queue into a vector of its elements in order of increasing priority,
+    taking the nodes apart and sorting them once instead of popping them one by one
+
+    ```
+    use fbheap::heap::BareQueue;
+
+    let mut queue: BareQueue<_, _> = [("c", 3), ("a", 1), ("b", 2)].into_iter().collect();
+    queue.pop();
+    queue.push("d", 0);
+    assert_eq!(queue.into_sorted_vec(), Ok(vec![("d", 0), ("b", 2), ("c", 3)]));
+    ```
+
+    # Errors
+    ImpossibleRcRelease => internal reference counting error
+    */
+    pub fn into_sorted_vec(mut self) -> Result<Vec<(T, Priority)>, Error> {
+        // nodes order by priority and then by push order, just as they are popped
+        let mut nodes = self.take_nodes();
+        nodes.sort();
+        nodes.into_iter().map(NPrpt::pair).collect()
+    }
+
+    /**
+    consume the queue into a vector of its elements in no particular order, in linear time
+
+    ```
+    use fbheap::heap::BareQueue;
+
+    let queue: BareQueue<_, _> = [("a", 3), ("b", 1), ("c", 2)].into_iter().collect();
+    let mut elements = queue.into_vec_unsorted().unwrap();
+    elements.sort();
+    assert_eq!(elements, vec![("a", 3), ("b", 1), ("c", 2)]);
+    ```
+
+    # Errors
+    ImpossibleRcRelease => internal reference counting error
+    */
+    pub fn into_vec_unsorted(mut self) -> Result<Vec<(T, Priority)>, Error> {
+        self.take_pairs()
+    }
+
+    /**
+    consume the queue into a binary heap from the standard library, in linear time,
+    with every element wrapped so that the binary heap also hands out the lowest priority first
+
+    ```
+    use fbheap::heap::BareQueue;
+    use std::cmp::Reverse;
+    use std::collections::BinaryHeap;
+
+    let heap = BinaryHeap::from([Reverse((2, "b")), Reverse((1, "a"))]);
+    let mut queue = BareQueue::from(heap);
+    queue.push("c", 0);
+    let mut heap = queue.into_binary_heap().unwrap();
+    assert_eq!(heap.pop(), Some(Reverse((0, "c"))));
+    assert_eq!(heap.pop(), Some(Reverse((1, "a"))));
+    ```
+
+    # Errors
+    ImpossibleRcRelease => internal reference counting error
+    */
+    pub fn into_binary_heap(mut self) -> Result<BinaryHeap<Reverse<(Priority, T)>>, Error>
+    where
+        T: Ord,
+    {
+        Ok(self
+            .take_pairs()?
+            .into_iter()
+            .map(|(t, priority)| Reverse((priority, t)))
+            .collect())
+    }
+
+    /**
+    remove all elements from the queue at once, without ordering them
+
+    ```
+    use fbheap::heap::BareQueue;
+
+    let mut queue: BareQueue<_, _> = (0..100).map(|x| (x, x)).collect();
+    queue.pop();
+    queue.clear();
+    assert!(queue.is_empty());
+    assert_eq!(queue.pop(), Err(fbheap::error::Error::Empty));
+    ```
+    */
+    pub fn clear(&mut self) {
+        self.first = None;
+        self.node_count = 0;
+        // trees are released one node at a time, as dropping a deep tree recursively would overflow the stack
+        let mut nodes = self.drain_roots();
+        while let Some(node) = nodes.pop() {
+            nodes.extend(node.drain_children());
+        }
+    }
+
+    /**
+    move all elements of another queue into this one
+
+    # Errors
+    will error if the combined queue would exceed capacity
+    */
+    pub fn meld(&mut self, mut other: Self) -> Result<(), Error> {
+        self.append(&mut other)
+    }
+
+    /**
+    move all elements of another queue into this one, leaving the other one empty,
+    which only concatenates the lists of roots, unless ties are broken deterministically
+    or values are indexed, in which case the elements of the other queue are also visited
+
+    ```
+    use fbheap::heap::BareQueue;
+
+    let mut queue: BareQueue<_, _> = [("b", 2), ("d", 4)].into_iter().collect();
+    let mut other: BareQueue<_, _> = [("a", 1), ("c", 3)].into_iter().collect();
+    queue.append(&mut other).unwrap();
+    assert!(other.is_empty());
+    assert_eq!(queue.len(), 4);
+    assert_eq!(queue.pop(), Ok(("a", 1)));
+    ```
+
+    # Errors
+    ReachedCapacity => the combined queue would exceed capacity, in which case both are left unchanged\n
+    Numerical => the sequence numbers breaking ties would overflow, in which case both are left unchanged
+    */
+    pub fn append(&mut self, other: &mut Self) -> Result<(), Error> {
+        // when breaking ties deterministically, the other elements are placed after all of these
+        let sequences = match self.sequence {
+            Some(offset) => Some((
+                offset,
+                offset
+                    .checked_add(other.sequence.unwrap_or(1))
+                    .ok_or(Error::Numerical)?,
+            )),
+            None => None,
+        };
+        self.node_count = self
+            .node_count
+            .checked_add(other.node_count)
+            .ok_or(Error::ReachedCapacity)?;
+        other.node_count = 0;
+
+        // shifting their sequence numbers or recording them in the index takes a walk over them
+        if sequences.is_some() || self.is_tracking() {
+            let mut nodes: Vec<_> = other.roots.iter().collect();
+            while let Some(node) = nodes.pop() {
+                if let Some((offset, _)) = sequences {
+                    node.set_sequence(node.sequence() + offset);
+                }
+                self.index_insert(&node);
+                nodes.extend(node.get_children());
+            }
+        }
+        if let Some((_, sequence)) = sequences {
+            self.sequence = Some(sequence);
+        }
+        if let Some(lookup) = &mut other.lookup {
+            lookup.clear();
+        }
+
+        // the nodes of the other queue now belong to this one, which it keeps track of for handles
+        if let Some(other_owner) = other.owner.take() {
+            other_owner.forward_to(&self.owner());
+        }
+        self.roots.append(&mut other.roots);
+        if let Some(other_first) = other.first.take() {
+            self.offer_first(other_first);
+        }
+        Ok(())
+    }
+
+    /**
+    meld any number of queues into one, concatenating their lists of roots,
+    which keeps the consolidation strategy of the first queue
+
+    ```
+    use fbheap::heap::BareQueue;
+
+    // every shard builds a local queue, which are unioned at the end
+    let shards = (0..4).map(|shard| (0..10).map(|x| (x, 4 * x + shard)).collect::<BareQueue<_, _>>());
+    let mut queue = BareQueue::from_queues(shards.collect::<Vec<_>>())?;
+    assert_eq!(queue.len(), 40);
+    assert_eq!(queue.pop(), Ok((0, 0)));
+    assert_eq!(queue.pop(), Ok((0, 1)));
+    # Ok::<(), fbheap::error::Error>(())
+    ```
+
+    # Errors
+    ReachedCapacity => the combined queue would exceed capacity
+    */
+    pub fn from_queues(queues: impl IntoIterator<Item = Self>) -> Result<Self, Error> {
+        let mut queues = queues.into_iter();
+        let mut queue = queues.next().unwrap_or_default();
+        for mut other in queues {
+            queue.append(&mut other)?;
+        }
+        Ok(queue)
+    }
+
+    /* # handle functionality */
+
+    /**
+    push a value onto the queue with given priority, returning a handle to it
+
+    # Panics
+    panics if the queue is already at capacity, see `push`
+    */
+    pub fn push_with_handle(&mut self, t: T, priority: Priority) -> Handle<T, Priority> {
+        self.try_push_with_handle(t, priority)
+            .expect("queue cannot account for additional nodes")
+    }
+
+    /**
+    push a value onto the queue with given priority, returning a handle to it
+
+    # Errors
+    ReachedCapacity => the queue is already at capacity
+    */
+    pub fn try_push_with_handle(
+        &mut self,
+        t: T,
+        priority: Priority,
+    ) -> Result<Handle<T, Priority>, Error> {
+        let handle = Handle::new(&self.push_node(t, priority)?);
+        // forget about dropped handles before the registry would need to grow
+        if self.handles.len() == self.handles.capacity() {
+            self.handles.retain(|entry| entry.strong_count() > 0);
+        }
+        self.handles.push(handle.entry());
+        Ok(handle)
+    }
+
+    /**
+    decreases the priority of the item the handle points to
+
+    # Errors
+    StaleHandle => the element the handle points to is no longer in any queue\n
+    NotFound => the handle points into another queue\n
+    CannotIncreasePriority => the give prioprity is higher than the current one for the index of that value
+    */
+    pub fn decrease_priority_at(
+        &mut self,
+        handle: &Handle<T, Priority>,
+        new_priority: Priority,
+    ) -> Result<(), Error> {
+        let node = self.get_handle_node(handle)?;
+        self.decrease_node_priority(node, new_priority)
+    }
+
+    /**
+    remove the item the handle points to from the queue, regardless of its priority
+
+    # Errors
+    StaleHandle => the element the handle points to is no longer in any queue\n
+    NotFound => the handle points into another queue
+    */
+    pub fn delete_at(&mut self, handle: &Handle<T, Priority>) -> Result<(T, Priority), Error> {
+        let node = self.get_handle_node(handle)?;
+        self.remove_node(&node)?;
+        node.pair()
+    }
+
+    /**
+    swap the value of the item the handle points to, keeping its priority and place in the queue,
+    returning the previous value
+
+    # Errors
+    StaleHandle => the element the handle points to is no longer in any queue\n
+    NotFound => the handle points into another queue
+    */
+    pub fn replace_at(&mut self, handle: &Handle<T, Priority>, new_value: T) -> Result<T, Error> {
+        let node = self.get_handle_node(handle)?;
+        self.index_remove(&node);
+        let previous = replace(&mut *node.value_mut(), new_value);
+        self.index_insert(&node);
+        Ok(previous)
+    }
+
+    /// returns how many handles given out by this queue have not been dropped yet
+    #[must_use]
+    pub fn live_handles(&self) -> usize {
+        self.handles
+            .iter()
+            .filter(|entry| entry.strong_count() > 0)
+            .count()
+    }
+
+    /// returns how many handles which have not been dropped point to elements no longer in the queue
+    #[must_use]
+    pub fn stale_handles(&self) -> usize {
+        self.handles
+            .iter()
+            .filter_map(HandleEntry::upgrade)
+            .filter(|node| node.strong_count() == 0)
+            .count()
+    }
+}
+
+impl<T, Priority> BareQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /* # value lookup functionality */
+
+    /// find the node holding the given value, through the lookup strategy if one is set
+    pub(crate) fn get_node(&self, t: &T) -> Option<NRef<T, Priority>> {
+        match self
+            .lookup
+            .as_ref()
+            .map_or(Lookup::Search, |lookup| lookup.find(t))
+        {
+            Lookup::Found(handle) => handle.node().filter(|node| node.has_value(t)),
+            Lookup::Missing => None,
+            Lookup::Search => self.search_node(t),
+        }
+    }
+
+    /// find the node holding the given value, searching through the whole queue
+    fn search_node(&self, t: &T) -> Option<NRef<T, Priority>> {
+        self.count(|metrics| metrics.searches += 1);
+        // bfs on nodes
+        let mut q = self.roots.iter().collect::<VecDeque<_>>();
+        let mut found = None;
+        while let Some(node) = q.pop_front() {
+            let matches = node.has_value(t);
+            q.extend(node.get_children());
+            if matches {
+                if !self.exhaustive_lookup {
+                    return Some(node);
+                }
+                found.get_or_insert(node);
+            }
+        }
+        found
+    }
+
+    /// find the nodes holding any of the given values in a single walk through the queue,
+    /// pairing each of them with the priority given for its value
+    fn search_nodes(&self, mut pending: Vec<(T, Priority)>) -> Vec<(NRef<T, Priority>, Priority)> {
+        self.count(|metrics| metrics.searches += 1);
+        let mut found = Vec::new();
+        // bfs on nodes, so that every value is matched with the node `search_node` would find
+        let mut q = self.roots.iter().collect::<VecDeque<_>>();
+        while let Some(node) = q.pop_front() {
+            if pending.is_empty() && !self.exhaustive_lookup {
+                break;
+            }
+            if let Some(position) = pending.iter().position(|(t, _)| node.has_value(t)) {
+                found.push((node.clone(), pending.swap_remove(position).1));
+            }
+            q.extend(node.get_children());
         }
+        found
+    }
+
+    /// returns true if the queue holds the given value, searching through the whole queue
+    /// unless a lookup strategy says otherwise
+    #[must_use]
+    pub fn contains(&self, value: &T) -> bool {
+        self.get_node(value).is_some()
+    }
+
+    /**
+    push a value onto the queue, or decrease its priority if it is already queued with a higher one,
+    searching through the whole queue once
+
+    ```
+    use fbheap::heap::{BareQueue, Upsert};
+
+    let mut queue = BareQueue::new();
+    assert_eq!(queue.push_or_decrease("a", 5), Ok(Upsert::Pushed));
+    assert_eq!(queue.push_or_decrease("a", 3), Ok(Upsert::Decreased));
+    assert_eq!(queue.push_or_decrease("a", 4), Ok(Upsert::Kept));
+    assert_eq!(queue.pop(), Ok(("a", 3)));
+    ```
+
+    # Errors
+    will error if the queue is already at capacity
+    */
+    pub fn push_or_decrease(&mut self, t: T, priority: Priority) -> Result<Upsert, Error> {
+        match self.get_node(&t) {
+            Some(node) if node.has_higher_priority(&priority) => {
+                self.decrease_node(node, priority)?;
+                Ok(Upsert::Decreased)
+            }
+            Some(_) => Ok(Upsert::Kept),
+            None => {
+                self.push_node(t, priority)?;
+                Ok(Upsert::Pushed)
+            }
+        }
+    }
+
+    /**
+    look up the given value, for acting on it depending on whether it is queued,
+    searching through the whole queue once
+
+    ```
+    use fbheap::heap::{BareQueue, Entry};
+
+    let mut queue = BareQueue::new();
+    queue.push("a", 5);
+    if let Entry::Occupied(mut entry) = queue.entry("a") {
+        assert_eq!(*entry.priority(), 5);
+        entry.decrease_priority(3)?;
+    }
+    if let Entry::Vacant(entry) = queue.entry("b") {
+        entry.insert(4)?;
+    }
+    if let Entry::Occupied(entry) = queue.entry("a") {
+        assert_eq!(entry.delete(), Ok(("a", 3)));
+    }
+    assert_eq!(queue.pop(), Ok(("b", 4)));
+    assert!(queue.is_empty());
+    # Ok::<(), fbheap::error::Error>(())
+    ```
+    */
+    pub fn entry(&mut self, value: T) -> Entry<'_, T, Priority> {
+        match self.get_node(&value) {
+            Some(node) => Entry::Occupied(OccupiedEntry { queue: self, node }),
+            None => Entry::Vacant(VacantEntry { queue: self, value }),
+        }
+    }
+
+    /**
+    decreases the priority of the item with given value
+
+    # Errors
+    NotFound => index with given value was not found in the queue\n
+    CannotIncreasePriority => the give prioprity is higher than the current one for the index of that value
+    */
+    pub fn decrease_priority(&mut self, value: &T, new_priority: Priority) -> Result<(), Error> {
+        let node = self.get_node(value).ok_or(Error::NotFound("value"))?;
+        self.decrease_node_priority(node, new_priority)
+    }
+
+    /**
+    increases the priority of the item with given value
+
+    # Errors
+    NotFound => index with given value was not found in the queue\n
+    CannotDecreasePriority => the given priority is lower than the current one for the index of that value
+    */
+    pub fn increase_priority(&mut self, value: &T, new_priority: Priority) -> Result<(), Error> {
+        let node = self.get_node(value).ok_or(Error::NotFound("value"))?;
+        self.increase_node_priority(&node, new_priority)
+    }
+
+    /**
+    changes the priority of the item with given value, in whichever direction
+
+    # Errors
+    NotFound => index with given value was not found in the queue
+    */
+    pub fn update_priority(&mut self, value: &T, new_priority: Priority) -> Result<(), Error> {
+        let node = self.get_node(value).ok_or(Error::NotFound("value"))?;
+        self.update_node_priority(node, new_priority)
+    }
+
+    /**
+    remove the item with given value from the queue, regardless of its priority
+
+    # Errors
+    NotFound => index with given value was not found in the queue
+    */
+    pub fn delete(&mut self, value: &T) -> Result<(T, Priority), Error> {
+        let node = self.get_node(value).ok_or(Error::NotFound("value"))?;
+        self.remove_node(&node)?;
+        node.pair()
+    }
+
+    /**
+    swap the given value for a new one, keeping its priority and place in the queue,
+    returning the previous value
+
+    ```
+    use fbheap::heap::BareQueue;
+
+    let mut tasks = BareQueue::new();
+    tasks.push(("sync", 1), 3);
+    let retry = tasks.push_with_handle(("fetch", 1), 5);
+    assert_eq!(tasks.replace(&("sync", 1), ("sync", 2)), Ok(("sync", 1)));
+    assert_eq!(tasks.replace_at(&retry, ("fetch", 2)), Ok(("fetch", 1)));
+    assert_eq!(tasks.pop(), Ok((("sync", 2), 3)));
+    assert_eq!(tasks.pop(), Ok((("fetch", 2), 5)));
+    ```
+
+    # Errors
+    NotFound => index with given value was not found in the queue
+    */
+    pub fn replace(&mut self, value: &T, new_value: T) -> Result<T, Error> {
+        let node = self.get_node(value).ok_or(Error::NotFound("value"))?;
+        self.index_remove(&node);
+        let previous = replace(&mut *node.value_mut(), new_value);
+        self.index_insert(&node);
+        Ok(previous)
+    }
+
+    /**
+    move the item with given value from this queue into another one, keeping its priority
+
+    # Errors
+    NotFound => index with given value was not found in this queue\n
+    ReachedCapacity => the other queue cannot account for additional nodes
+    */
+    pub fn transfer(&mut self, to: &mut Self, value: &T) -> Result<(), Error> {
+        let node = self.get_node(value).ok_or(Error::NotFound("value"))?;
+        self.remove_node(&node)?;
+        to.insert_node(node)
+    }
+}
+
+impl<T, Priority> BareQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /**
+    decreases the priorities of many items at once, finding those the lookup strategy cannot find
+    directly in a single walk through the trees, and looking for the new first node only among
+    the decreased ones, after all of them were cut, returning how many values had their priority decreased
+
+    values which are not queued, or whose new priority is not lower, are skipped,
+    as happens when relaxing edges towards vertices which are settled or already closer,
+    and a value given several times is decreased to the lowest of its priorities
+
+    ```
+    use fbheap::heap::BareQueue;
+
+    let mut queue: BareQueue<_, _> = (0..8).map(|vertex| (vertex, 100 + vertex)).collect();
+    assert_eq!(queue.pop(), Ok((0, 100)));
+    let relaxed = queue.decrease_priority_many([(3, 40), (5, 70), (3, 30), (0, 10), (6, 200)])?;
+    assert_eq!(relaxed, 2);
+    assert_eq!(queue.pop(), Ok((3, 30)));
+    assert_eq!(queue.pop(), Ok((5, 70)));
+    # Ok::<(), fbheap::error::Error>(())
+    ```
+
+    # Errors
+    NotFound => internal indexing error
+    */
+    pub fn decrease_priority_many(
+        &mut self,
+        decreases: impl IntoIterator<Item = (T, Priority)>,
+    ) -> Result<usize, Error> {
+        // a value given several times only keeps the lowest of its priorities
+        let mut pending: Vec<(T, Priority)> = Vec::new();
+        for (t, priority) in decreases {
+            match pending.iter_mut().find(|(value, _)| *value == t) {
+                Some((_, lowest)) if priority < *lowest => *lowest = priority,
+                Some(_) => {}
+                None => pending.push((t, priority)),
+            }
+        }
+
+        let mut nodes = Vec::new();
+        let mut searched = Vec::new();
+        for (t, priority) in pending {
+            match self
+                .lookup
+                .as_ref()
+                .map_or(Lookup::Search, |lookup| lookup.find(&t))
+            {
+                Lookup::Found(handle) => {
+                    if let Some(node) = handle.node().filter(|node| node.has_value(&t)) {
+                        nodes.push((node, priority));
+                    }
+                }
+                Lookup::Missing => {}
+                Lookup::Search => searched.push((t, priority)),
+            }
+        }
+        if !searched.is_empty() {
+            nodes.extend(self.search_nodes(searched));
+        }
+
+        let mut lowered = Vec::new();
+        for (node, priority) in nodes {
+            if node.has_higher_priority(&priority) {
+                self.lower_node(&node, priority)?;
+                lowered.push(node);
+            }
+        }
+
+        let decreased = lowered.len();
+        for node in lowered {
+            if node.get_parent().is_none() {
+                self.offer_first(node);
+            }
+        }
+        Ok(decreased)
+    }
+}
+
+impl<T, Priority> BareQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord + Clone,
+{
+    /**
+    returns the priority of the given value, if the queue holds it, searching through the whole queue
+
+    ```
+    use fbheap::heap::BareQueue;
+
+    let mut queue = BareQueue::new();
+    queue.push("open", 4);
+    assert!(queue.contains(&"open"));
+    assert_eq!(queue.priority_of(&"open"), Some(4));
+    assert_eq!(queue.priority_of(&"closed"), None);
+    ```
+    */
+    #[must_use]
+    pub fn priority_of(&self, value: &T) -> Option<Priority> {
+        self.get_node(value).map(|node| node.priority_ref().clone())
+    }
+
+    /**
+    decreases the priority of the item with given value by the given amount,
+    without the caller having to know the current priority
+
+    ```
+    use fbheap::error::Error::CannotIncreasePriority;
+    use fbheap::heap::BareQueue;
+
+    let mut tasks = BareQueue::new();
+    tasks.push("compact", 10);
+    tasks.push("flush", 7);
+    // waiting tasks age towards the front of the queue
+    tasks.decrease_priority_by(&"compact", 4)?;
+    assert_eq!(tasks.priority_of(&"compact"), Some(6));
+    assert_eq!(
+        tasks.decrease_priority_by(&"flush", 0),
+        Err(CannotIncreasePriority { current_was_lower: false })
+    );
+    assert_eq!(tasks.pop(), Ok(("compact", 6)));
+    # Ok::<(), fbheap::error::Error>(())
+    ```
+
+    # Errors
+    NotFound => index with given value was not found in the queue\n
+    CannotIncreasePriority => subtracting the amount does not lower the priority
+    */
+    pub fn decrease_priority_by(&mut self, value: &T, delta: Priority) -> Result<(), Error>
+    where
+        Priority: Sub<Output = Priority>,
+    {
+        let node = self.get_node(value).ok_or(Error::NotFound("value"))?;
+        let new_priority = node.priority_ref().clone() - delta;
+        self.decrease_node_priority(node, new_priority)
+    }
+}
+
+impl<T, Priority> BareQueue<T, Priority>
+where
+    T: Clone,
+    Priority: Ord + Clone,
+{
+    pub(crate) fn peek_cloned(&self) -> Option<(T, Priority)> {
+        self.get_first().map(NPrpt::pair_cloned)
+    }
+
+    /**
+    iterate over copies of all elements in no particular order, without changing the queue
+
+    ```
+    use fbheap::heap::BareQueue;
+
+    let mut queue: BareQueue<_, _> = (0..10).map(|x| (x, x)).collect();
+    queue.pop();
+    let mut open: Vec<_> = queue.iter().collect();
+    open.sort();
+    assert_eq!(open, (1..10).map(|x| (x, x)).collect::<Vec<_>>());
+    assert_eq!(queue.len(), 9);
+    ```
+    */
+    #[must_use]
+    pub fn iter(&self) -> Iter<'_, T, Priority> {
+        Iter {
+            nodes: self.roots.iter().collect(),
+            remaining: self.len(),
+            queue: PhantomData,
+        }
+    }
+
+    /**
+    iterate over copies of the elements with priority in the given range, in no particular order,
+    without changing the queue
+
+    by heap order every node below one past the upper bound is past it too,
+    so such subtrees are skipped without being visited
+
+    ```
+    use fbheap::heap::BareQueue;
+
+    // deadlines in seconds
+    let mut timers: BareQueue<_, _> = (0..100).map(|timer| (timer, timer * 3)).collect();
+    timers.pop();
+    let mut due: Vec<_> = timers.iter_priority_range(..=12).collect();
+    due.sort();
+    assert_eq!(due, vec![(1, 3), (2, 6), (3, 9), (4, 12)]);
+    assert_eq!(timers.iter_priority_range(100..103).count(), 1);
+    ```
+    */
+    pub fn iter_priority_range<R>(&self, range: R) -> PriorityRange<'_, T, Priority, R>
+    where
+        R: RangeBounds<Priority>,
+    {
+        PriorityRange {
+            nodes: self.roots.iter().collect(),
+            range,
+            queue: PhantomData,
+        }
+    }
+
+    /**
+    copy all elements into a vector in no particular order, in linear time,
+    for checkpointing a queue without popping it
+
+    ```
+    use fbheap::heap::BareQueue;
+
+    let mut queue: BareQueue<_, _> = [("a", 3), ("b", 1), ("c", 2)].into_iter().collect();
+    queue.pop();
+    let mut snapshot = queue.to_vec_unsorted();
+    snapshot.sort();
+    assert_eq!(snapshot, vec![("a", 3), ("c", 2)]);
+    assert_eq!(queue.len(), 2);
+    ```
+    */
+    #[must_use]
+    pub fn to_vec_unsorted(&self) -> Vec<(T, Priority)> {
+        self.iter().collect()
+    }
+
+    /* # explain functionality */
+
+    /// perform an operation while recording its structural steps
+    fn explain<R>(
+        &mut self,
+        operation: impl FnOnce(&mut Self) -> Result<R, Error>,
+    ) -> Result<(R, Trace<T, Priority>), Error> {
+        let first_before = self.get_first().map(NPrpt::pair_cloned);
+        self.journal = Some(Vec::new());
+        let result = operation(self);
+        let journal = self.journal.take().unwrap_or_default();
+
+        let steps = journal
+            .into_iter()
+            .filter_map(|event| match event {
+                Event::Link(parent, child) => Some(Step::Link {
+                    parent: parent.upgrade()?.pair_cloned(),
+                    child: child.upgrade()?.pair_cloned(),
+                }),
+                Event::Cut(node, parent) => Some(Step::Cut {
+                    node: node.upgrade()?.pair_cloned(),
+                    parent: parent.upgrade()?.pair_cloned(),
+                }),
+            })
+            .collect();
+        let trace = Trace {
+            steps,
+            first_before,
+            first_after: self.get_first().map(NPrpt::pair_cloned),
+        };
+        Ok((result?, trace))
+    }
+
+    /**
+    remove the element with the lowest priority, returning a trace of what happened,
+    the removed element is the first element before the operation
+
+    # Errors
+    Empty => cannot return element from empty queue\n
+    NotFound => internal indexing error
+    */
+    pub fn explain_pop(&mut self) -> Result<Trace<T, Priority>, Error> {
+        self.explain(Self::pop).map(|(_, trace)| trace)
+    }
+
+    /**
+    decreases the priority of the item with given value, returning a trace of what happened
+
+    # Errors
+    NotFound => index with given value was not found in the queue\n
+    CannotIncreasePriority => the give prioprity is higher than the current one for the index of that value
+    */
+    pub fn explain_decrease(
+        &mut self,
+        value: &T,
+        new_priority: Priority,
+    ) -> Result<Trace<T, Priority>, Error>
+    where
+        T: Eq,
+    {
+        self.explain(|queue| queue.decrease_priority(value, new_priority))
+            .map(|((), trace)| trace)
+    }
+
+    /* # handle functionality */
+
+    /**
+    returns the elements which live handles given out by this queue point to,
+    once for every handle, skipping stale ones
+
+    ```
+    use fbheap::error::Error::StaleHandle;
+    use fbheap::heap::BareQueue;
+
+    let mut queue = BareQueue::new();
+    let kept = queue.push_with_handle("kept", 1);
+    let dropped = queue.push_with_handle("dropped", 2);
+    let popped = queue.push_with_handle("popped", 0);
+    drop(dropped);
+    queue.pop().unwrap();
+
+    assert_eq!(queue.live_handles(), 2);
+    assert_eq!(queue.stale_handles(), 1);
+    assert!(popped.is_stale());
+    assert_eq!(queue.live_handle_entries(), vec![("kept", 1)]);
+    queue.decrease_priority_at(&kept, 0).unwrap();
+    assert_eq!(queue.delete_at(&kept), Ok(("kept", 0)));
+    assert!(kept.is_stale());
+    assert_eq!(queue.delete_at(&kept), Err(StaleHandle));
+    ```
+    */
+    #[must_use]
+    pub fn live_handle_entries(&self) -> Vec<(T, Priority)> {
+        self.handles
+            .iter()
+            .filter_map(HandleEntry::upgrade)
+            .filter_map(|node| node.upgrade())
+            .map(|node| node.pair_cloned())
+            .collect()
+    }
+}
+
+#[cfg(feature = "fuzzing")]
+impl<T, Priority> BareQueue<T, Priority>
+where
+    T: Eq + Clone + fmt::Debug,
+    Priority: Ord + Clone + fmt::Debug,
+{
+    /**
+    apply a sequence of operations to the queue,
+    checking every step against a `Model` holding the same elements, and the trees against `assert_valid`
+
+    ```
+    use fbheap::heap::BareQueue;
+    use fbheap::model::Op;
+
+    let mut queue = BareQueue::new();
+    queue.push(0, 0);
+    queue.apply_ops(&Op::decode(b"\x00\x07\x03\x00\x08\x04\x04\x06\x08\x01\x07"));
+    assert_eq!(queue.pop(), Ok((8, 1)));
+    ```
+
+    # Panics
+    panics if the queue disagrees with the model, or one of its invariants is violated
+    */
+    pub fn apply_ops(&mut self, ops: &[Op<T, Priority>]) {
+        let mut model = Model::new();
+        for (t, priority) in self.iter() {
+            model.push(t, priority);
+        }
+        for op in ops {
+            model.apply(self, op.clone());
+            self.assert_valid();
+        }
+    }
+}
+
+impl<T, Priority> Drop for BareQueue<T, Priority>
+where
+    Priority: Ord,
+{
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+/* # priority queue */
+
+impl<T, Priority> PriorityQueue<T, Priority> for BareQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    fn push(&mut self, t: T, priority: Priority) -> Result<(), Error> {
+        Self::try_push(self, t, priority)
+    }
+
+    fn pop(&mut self) -> Result<(T, Priority), Error> {
+        Self::pop(self)
+    }
+
+    fn peek(&self) -> Option<(T, Priority)>
+    where
+        T: Clone,
+        Priority: Clone,
+    {
+        self.peek_cloned()
+    }
+
+    fn decrease_priority(&mut self, value: &T, new_priority: Priority) -> Result<(), Error> {
+        Self::decrease_priority(self, value, new_priority)
+    }
+
+    fn delete(&mut self, value: &T) -> Result<(T, Priority), Error> {
+        Self::delete(self, value)
+    }
+
+    fn is_empty(&self) -> bool {
+        Self::is_empty(self)
+    }
+
+    fn len(&self) -> usize {
+        Self::len(self)
+    }
+
+    fn meld(&mut self, other: Self) -> Result<(), Error> {
+        Self::meld(self, other)
+    }
+}
+
+/* # peeking */
+
+/// guard giving mutable access to the priority of the first element of a queue,
+/// which restores the queue when dropped
+pub struct PeekMut<'a, T, Priority>
+where
+    Priority: Ord,
+{
+    queue: &'a mut BareQueue<T, Priority>,
+    /// whether the priority might have been changed
+    changed: bool,
+}
+
+impl<T, Priority> PeekMut<'_, T, Priority>
+where
+    Priority: Ord,
+{
+    fn node(&self) -> &NRef<T, Priority> {
+        // a guard is only handed out for a non empty queue
+        self.queue
+            .get_first()
+            .expect("peeked queue has a first element")
+    }
+
+    /// returns the value of the element
+    #[must_use]
+    pub fn value(&self) -> Ref<'_, T> {
+        self.node().value_ref()
+    }
+
+    /// returns the priority of the element
+    #[must_use]
+    pub fn priority(&self) -> Ref<'_, Priority> {
+        self.node().priority_ref()
+    }
+
+    /// returns the priority of the element for changing it in place
+    pub fn priority_mut(&mut self) -> RefMut<'_, Priority> {
+        self.changed = true;
+        self.node().priority_mut()
+    }
+
+    /**
+    remove the element from the queue, whatever its priority was changed to
+
+    # Errors
+    NotFound => internal indexing error
+    */
+    pub fn pop(mut self) -> Result<(T, Priority), Error> {
+        self.changed = false;
+        self.queue.pop()
+    }
+}
+
+impl<T, Priority> Drop for PeekMut<'_, T, Priority>
+where
+    Priority: Ord,
+{
+    fn drop(&mut self) {
+        if self.changed {
+            self.queue.reposition_first();
+        }
+    }
+}
+
+/* # entries */
+
+/// value looked up in a queue, which is either queued or not
+pub enum Entry<'a, T, Priority>
+where
+    Priority: Ord,
+{
+    /// the value is queued
+    Occupied(OccupiedEntry<'a, T, Priority>),
+    /// the value is not queued
+    Vacant(VacantEntry<'a, T, Priority>),
+}
+
+/// queued element, found by looking up its value
+pub struct OccupiedEntry<'a, T, Priority>
+where
+    Priority: Ord,
+{
+    queue: &'a mut BareQueue<T, Priority>,
+    node: NRef<T, Priority>,
+}
+
+impl<T, Priority> OccupiedEntry<'_, T, Priority>
+where
+    Priority: Ord,
+{
+    /// returns the value of the element
+    #[must_use]
+    pub fn value(&self) -> Ref<'_, T> {
+        self.node.value_ref()
+    }
+
+    /// returns the priority of the element
+    #[must_use]
+    pub fn priority(&self) -> Ref<'_, Priority> {
+        self.node.priority_ref()
+    }
+
+    /**
+    decreases the priority of the element
+
+    # Errors
+    CannotIncreasePriority => the given priority is not lower than the current one
+    */
+    pub fn decrease_priority(&mut self, new_priority: Priority) -> Result<(), Error> {
+        self.queue
+            .decrease_node_priority(self.node.clone(), new_priority)
+    }
+
+    /**
+    increases the priority of the element
+
+    # Errors
+    CannotDecreasePriority => the given priority is not higher than the current one
+    */
+    pub fn increase_priority(&mut self, new_priority: Priority) -> Result<(), Error> {
+        self.queue.increase_node_priority(&self.node, new_priority)
+    }
+
+    /**
+    remove the element from the queue, regardless of its priority
+
+    # Errors
+    ImpossibleRcRelease => the element is still referenced elsewhere
+    */
+    pub fn delete(self) -> Result<(T, Priority), Error> {
+        self.queue.remove_node(&self.node)?;
+        self.node.pair()
+    }
+}
+
+/// value which is not queued, found by looking it up
+pub struct VacantEntry<'a, T, Priority>
+where
+    Priority: Ord,
+{
+    queue: &'a mut BareQueue<T, Priority>,
+    value: T,
+}
+
+impl<T, Priority> VacantEntry<'_, T, Priority>
+where
+    Priority: Ord,
+{
+    /// returns the value which was looked up
+    #[must_use]
+    pub const fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// take back the value which was looked up
+    #[must_use]
+    pub fn into_value(self) -> T {
+        self.value
+    }
+
+    /**
+    push the value onto the queue with given priority
+
+    # Errors
+    ReachedCapacity => the queue is already at capacity
+    */
+    pub fn insert(self, priority: Priority) -> Result<(), Error> {
+        self.queue.try_push(self.value, priority)
+    }
+}
+
+/* # cloning */
+
+/**
+copies every node of the queue, so that the copy shares no structure with the original,
+while handles given out by the original keep pointing into the original
+
+```
+use fbheap::heap::BareQueue;
+
+let mut queue: BareQueue<_, _> = (0..10).map(|x| (x, x)).collect();
+queue.pop();
+queue.decrease_priority(&7, 0);
+
+let mut branch = queue.clone();
+assert_eq!(branch.fmt_tree(), queue.fmt_tree());
+assert_eq!(branch.pop(), Ok((7, 0)));
+branch.decrease_priority(&9, 0);
+assert_eq!(queue.len(), 9);
+assert_eq!(queue.pop(), Ok((7, 0)));
+assert_eq!(queue.pop(), Ok((1, 1)));
+```
+*/
+impl<T, Priority> Clone for BareQueue<T, Priority>
+where
+    T: Clone,
+    Priority: Ord + Clone,
+{
+    fn clone(&self) -> Self {
+        let mut queue = Self {
+            roots: NList::new(),
+            first: None,
+            node_count: self.node_count,
+            consolidation: self.consolidation,
+            workload: self.workload,
+            journal: None,
+            exhaustive_lookup: self.exhaustive_lookup,
+            sequence: self.sequence,
+            lookup: self.lookup.as_ref().map(|lookup| lookup.new_empty()),
+            handles: Vec::new(),
+            owner: None,
+            ranks: Vec::new(),
+            metrics: self.metrics.clone(),
+        };
+        let mut nodes: Vec<_> = self.roots.iter().map(|root| (root, None)).collect();
+        nodes.reverse();
+        while let Some((node, parent)) = nodes.pop() {
+            let (t, priority) = node.pair_cloned();
+            let copy = NRef::<T, Priority>::new_node(t, priority);
+            copy.set_sequence(node.sequence());
+            copy.set_owner(&queue.owner());
+            queue.index_insert(&copy);
+            if node.is_marked() {
+                copy.mark();
+            }
+            match &parent {
+                Some(parent) => {
+                    copy.set_parent(parent);
+                    parent.insert_child(copy.clone());
+                }
+                None => queue.insert_root(copy.clone()),
+            }
+            if self
+                .get_first()
+                .is_some_and(|first| Rc::ptr_eq(first, &node))
+            {
+                queue.set_first(copy.clone());
+            }
+            let children = node.get_children().collect::<Vec<_>>().into_iter().rev();
+            nodes.extend(children.map(|child| (child, Some(copy.clone()))));
+        }
+        queue
+    }
+}
+
+/* # formatting */
+
+impl<T, Priority> BareQueue<T, Priority>
+where
+    T: fmt::Debug,
+    Priority: Ord + fmt::Debug,
+{
+    /// write every tree of the queue, one node per line, with children indented below their parent
+    fn write_trees(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        let mut nodes: Vec<_> = self.roots.iter().map(|root| (root, 0)).collect();
+        nodes.reverse();
+        while let Some((node, depth)) = nodes.pop() {
+            write!(
+                f,
+                "{:indent$}{:?} : {:?}, rank {}",
+                "",
+                *node.value_ref(),
+                *node.priority_ref(),
+                node.rank(),
+                indent = 2 * depth
+            )?;
+            if node.is_marked() {
+                f.write_str(", marked")?;
+            }
+            f.write_char('\n')?;
+            let children = node.get_children().collect::<Vec<_>>().into_iter().rev();
+            nodes.extend(children.map(|child| (child, depth + 1)));
+        }
+        Ok(())
+    }
+
+    /// write every tree of the queue as a graphviz digraph, with edges from parents to children
+    #[cfg(feature = "viz")]
+    fn write_dot(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        use alloc::format;
+
+        f.write_str("digraph {\n    node [shape=box];\n")?;
+        let mut id = 0_usize;
+        let mut nodes: Vec<_> = self.roots.iter().map(|root| (root, None)).collect();
+        nodes.reverse();
+        while let Some((node, parent)) = nodes.pop() {
+            let label = format!("{:?} : {:?}", *node.value_ref(), *node.priority_ref());
+            let mut style = Vec::new();
+            if node.is_marked() {
+                style.push("dashed");
+            }
+            if self
+                .get_first()
+                .is_some_and(|first| Rc::ptr_eq(first, &node))
+            {
+                style.push("bold");
+            }
+            writeln!(
+                f,
+                "    n{id} [label=\"{}\\nrank {}\", style=\"{}\"];",
+                label.replace('\\', "\\\\").replace('"', "\\\""),
+                node.rank(),
+                style.join(",")
+            )?;
+            if let Some(parent) = parent {
+                writeln!(f, "    n{parent} -> n{id};")?;
+            }
+            let children = node.get_children().collect::<Vec<_>>().into_iter().rev();
+            nodes.extend(children.map(|child| (child, Some(id))));
+            id += 1;
+        }
+        f.write_str("}\n")
+    }
+
+    /**
+    render the trees of the queue, one node per line with its value, priority, rank and mark,
+    and children indented below their parent
+
+    ```
+    use fbheap::heap::BareQueue;
+
+    let mut queue = BareQueue::new();
+    queue.push("a", 1);
+    queue.push("b", 2);
+    queue.push("c", 3);
+    queue.pop();
+    assert_eq!(queue.fmt_tree(), "\"b\" : 2, rank 1\n  \"c\" : 3, rank 0\n");
+    ```
+    */
+    #[must_use]
+    pub fn fmt_tree(&self) -> String {
+        let mut tree = String::new();
+        self.write_trees(&mut tree)
+            .expect("writing to a string cannot fail");
+        tree
+    }
+
+    /**
+    render the trees of the queue as a graphviz digraph, labelling every node with its value,
+    priority and rank, with marked nodes dashed and the first node drawn bold
+
+    ```
+    use fbheap::heap::BareQueue;
+
+    let mut queue = BareQueue::new();
+    queue.push("a", 1);
+    queue.push("b", 2);
+    queue.push("c", 3);
+    queue.pop();
+    let dot = queue.to_dot();
+    assert!(dot.starts_with("digraph"));
+    assert!(dot.contains("n0 -> n1;"));
+    assert!(dot.contains("label=\"\\\"c\\\" : 3\\nrank 0\""));
+    ```
+    */
+    #[cfg(feature = "viz")]
+    #[must_use]
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::new();
+        self.write_dot(&mut dot)
+            .expect("writing to a string cannot fail");
+        dot
+    }
+}
+
+impl<T, Priority> fmt::Debug for BareQueue<T, Priority>
+where
+    T: fmt::Debug,
+    Priority: Ord + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "BareQueue of {} elements", self.len())?;
+        self.write_trees(f)
+    }
+}
+
+/* # serialization */
+
+/// queues are serialized as a list of their elements in no particular order,
+/// the consolidation strategy and the structure of the trees are not kept
+#[cfg(feature = "serde")]
+impl<T, Priority> serde::Serialize for BareQueue<T, Priority>
+where
+    T: serde::Serialize,
+    Priority: Ord + serde::Serialize,
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeSeq;
+
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        let mut nodes: Vec<_> = self.roots.iter().collect();
+        while let Some(node) = nodes.pop() {
+            seq.serialize_element(&(&*node.value_ref(), &*node.priority_ref()))?;
+            nodes.extend(node.get_children());
+        }
+        seq.end()
+    }
+}
+
+/// queues are rebuilt from a list of their elements, with the default consolidation strategy
+#[cfg(feature = "serde")]
+impl<'de, T, Priority> serde::Deserialize<'de> for BareQueue<T, Priority>
+where
+    T: serde::Deserialize<'de>,
+    Priority: Ord + serde::Deserialize<'de>,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<(T, Priority)>::deserialize(deserializer).map(Self::from_iter)
+    }
+}
+
+/* # collecting */
+
+/**
+collects pairs of values and priorities into a queue, which only adds roots and so takes linear time
+
+```
+use fbheap::heap::BareQueue;
+
+let mut queue: BareQueue<_, _> = [("b", 2), ("a", 1)].into_iter().collect();
+queue.extend([("c", 0)]);
+assert_eq!(queue.pop(), Ok(("c", 0)));
+assert_eq!(queue.pop(), Ok(("a", 1)));
+```
+*/
+impl<T, Priority> FromIterator<(T, Priority)> for BareQueue<T, Priority>
+where
+    Priority: Ord,
+{
+    fn from_iter<I: IntoIterator<Item = (T, Priority)>>(iter: I) -> Self {
+        let mut queue = Self::new();
+        queue.extend(iter);
+        queue
+    }
+}
+
+impl<T, Priority> Extend<(T, Priority)> for BareQueue<T, Priority>
+where
+    Priority: Ord,
+{
+    fn extend<I: IntoIterator<Item = (T, Priority)>>(&mut self, iter: I) {
+        for (t, priority) in iter {
+            self.push(t, priority);
+        }
+    }
+}
+
+/// builds a queue from a binary heap of the standard library ordered by lowest priority, in linear time
+impl<T, Priority> From<BinaryHeap<Reverse<(Priority, T)>>> for BareQueue<T, Priority>
+where
+    Priority: Ord,
+{
+    fn from(heap: BinaryHeap<Reverse<(Priority, T)>>) -> Self {
+        heap.into_iter()
+            .map(|Reverse((priority, t))| (t, priority))
+            .collect()
+    }
+}
+
+/* # iterators */
+
+impl<T, Priority> IntoIterator for BareQueue<T, Priority>
+where
+    Priority: Ord,
+{
+    type Item = (T, Priority);
+    type IntoIter = IntoIter<T, Priority>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { queue: self }
+    }
+}
+
+/// iterator over copies of the elements of a queue, in no particular order
+pub struct Iter<'a, T, Priority>
+where
+    Priority: Ord,
+{
+    /// nodes whose elements are still to be visited, breadth first
+    nodes: VecDeque<NRef<T, Priority>>,
+    /// number of elements still to be visited
+    remaining: usize,
+    /// the queue cannot change while it is being iterated over
+    queue: PhantomData<&'a BareQueue<T, Priority>>,
+}
+
+impl<T, Priority> Iterator for Iter<'_, T, Priority>
+where
+    T: Clone,
+    Priority: Ord + Clone,
+{
+    type Item = (T, Priority);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.nodes.pop_front()?;
+        self.nodes.extend(node.get_children());
+        self.remaining -= 1;
+        Some(node.pair_cloned())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+/// iterator over copies of the elements of a queue with priority in a range, built by `iter_priority_range`
+pub struct PriorityRange<'a, T, Priority, R>
+where
+    Priority: Ord,
+{
+    /// nodes whose subtrees are still to be visited, depth first
+    nodes: Vec<NRef<T, Priority>>,
+    /// range of the priorities handed out
+    range: R,
+    /// the queue cannot change while it is being iterated over
+    queue: PhantomData<&'a BareQueue<T, Priority>>,
+}
+
+impl<T, Priority, R> Iterator for PriorityRange<'_, T, Priority, R>
+where
+    T: Clone,
+    Priority: Ord + Clone,
+    R: RangeBounds<Priority>,
+{
+    type Item = (T, Priority);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(node) = self.nodes.pop() {
+            let past_end = match self.range.end_bound() {
+                Bound::Included(end) => *node.priority_ref() > *end,
+                Bound::Excluded(end) => *node.priority_ref() >= *end,
+                Bound::Unbounded => false,
+            };
+            if past_end {
+                continue;
+            }
+            self.nodes.extend(node.get_children());
+            if self.range.contains(&node.priority_ref()) {
+                return Some(node.pair_cloned());
+            }
+        }
+        None
+    }
+}
+
+/// owning iterator over the elements of a queue, in order of increasing priority
+pub struct IntoIter<T, Priority>
+where
+    Priority: Ord,
+{
+    queue: BareQueue<T, Priority>,
+}
+
+impl<T, Priority> Iterator for IntoIter<T, Priority>
+where
+    Priority: Ord,
+{
+    type Item = (T, Priority);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.queue.pop().ok()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.queue.len(), Some(self.queue.len()))
+    }
+}
+
+/// draining iterator over the elements of a queue, in order of increasing priority
+pub struct DrainSorted<'a, T, Priority>
+where
+    Priority: Ord,
+{
+    queue: &'a mut BareQueue<T, Priority>,
+}
+
+impl<T, Priority> Iterator for DrainSorted<'_, T, Priority>
+where
+    Priority: Ord,
+{
+    type Item = (T, Priority);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.queue.pop().ok()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.queue.len(), Some(self.queue.len()))
+    }
+}
+
+impl<T, Priority> Drop for DrainSorted<'_, T, Priority>
+where
+    Priority: Ord,
+{
+    /// the remaining elements are dropped unordered, as sorting them first would be wasted work
+    fn drop(&mut self) {
+        self.queue.clear();
     }
 }
@@ -0,0 +1,79 @@
+use crate::{error::Error, heap::BareQueue};
+use alloc::{vec, vec::Vec};
+
+/* # huffman coding */
+
+/// tree of the code, either a symbol or the merge of two lighter trees
+enum Tree {
+    Leaf(usize),
+    Branch(usize, usize),
+}
+
+/**
+optimal prefix code for symbols with the given frequencies, as the bits of the codeword of every symbol
+
+the two lightest trees are merged until only one is left, with ties broken by the order
+the trees were created in, so that the same frequencies always yield the same code,
+and a single symbol is given a codeword of one bit
+
+```
+use fbheap::huffman::code;
+
+let frequencies = [45, 13, 12, 16, 9, 5];
+let codewords = code(&frequencies)?;
+let lengths: Vec<_> = codewords.iter().map(Vec::len).collect();
+assert_eq!(lengths, vec![1, 3, 3, 3, 4, 4]);
+// no codeword is the prefix of another one
+for (a, first) in codewords.iter().enumerate() {
+    for (b, second) in codewords.iter().enumerate() {
+        assert!(a == b || !second.starts_with(first));
+    }
+}
+# Ok::<(), fbheap::error::Error>(())
+```
+
+# Errors
+Numerical => the total frequency does not fit into the frequency type
+*/
+pub fn code(frequencies: &[u64]) -> Result<Vec<Vec<bool>>, Error> {
+    let mut trees: Vec<Tree> = (0..frequencies.len()).map(Tree::Leaf).collect();
+    let mut queue = BareQueue::new();
+    for (index, &frequency) in frequencies.iter().enumerate() {
+        queue.try_push(index, (frequency, index))?;
+    }
+
+    let root = loop {
+        let (lighter, (weight, _)) = match queue.pop() {
+            Ok(lightest) => lightest,
+            Err(Error::Empty) => return Ok(Vec::new()),
+            Err(error) => return Err(error),
+        };
+        let (heavier, (other, _)) = match queue.pop() {
+            Ok(lightest) => lightest,
+            Err(Error::Empty) => break lighter,
+            Err(error) => return Err(error),
+        };
+        let merged = trees.len();
+        trees.push(Tree::Branch(lighter, heavier));
+        let weight = weight.checked_add(other).ok_or(Error::Numerical)?;
+        queue.try_push(merged, (weight, merged))?;
+    };
+
+    let mut codewords = vec![Vec::new(); frequencies.len()];
+    let mut pending = vec![(root, Vec::new())];
+    while let Some((tree, prefix)) = pending.pop() {
+        match trees[tree] {
+            Tree::Leaf(symbol) if prefix.is_empty() => codewords[symbol] = vec![false],
+            Tree::Leaf(symbol) => codewords[symbol] = prefix,
+            Tree::Branch(left, right) => {
+                let mut extended = prefix.clone();
+                extended.push(true);
+                pending.push((right, extended));
+                let mut prefix = prefix;
+                prefix.push(false);
+                pending.push((left, prefix));
+            }
+        }
+    }
+    Ok(codewords)
+}
@@ -0,0 +1,93 @@
+use crate::{
+    heap::BareQueue,
+    node::{NPrpt, NRef},
+};
+use core::{cell::Ref, marker::PhantomData};
+
+/* # node inspection */
+
+/**
+read-only view of a node in the tree structure of a queue, for visualizers and tests
+
+implementors only expose what a node holds and which nodes hang below it,
+so nothing done through this trait can break the structure of the heap
+
+```
+use fbheap::heap::BareQueue;
+use fbheap::inspect::NodeView;
+
+let mut queue = BareQueue::new();
+queue.push("a", 1);
+queue.push("b", 2);
+queue.push("c", 3);
+queue.pop();
+
+let roots: Vec<_> = queue.roots().collect();
+assert_eq!(roots.len(), 1);
+assert_eq!(*roots[0].value(), "b");
+assert_eq!(*roots[0].priority(), 2);
+assert_eq!(roots[0].rank(), 1);
+let children: Vec<_> = roots[0].children().map(|child| *child.value()).collect();
+assert_eq!(children, vec!["c"]);
+```
+*/
+pub trait NodeView<T, Priority>: Sized {
+    /// borrow the value held by the node
+    fn value(&self) -> Ref<'_, T>;
+    /// borrow the priority of the value held by the node
+    fn priority(&self) -> Ref<'_, Priority>;
+    /// number of children of the node
+    fn rank(&self) -> usize;
+    /// whether the node has lost a child since it last became a child itself
+    fn is_marked(&self) -> bool;
+    /// iterate over the children of the node, in the order they were linked
+    fn children(&self) -> impl Iterator<Item = Self>;
+}
+
+/// node of a queue, borrowed from it so that the queue cannot change while the node is looked at
+pub struct NodeRef<'a, T, Priority>
+where
+    Priority: Ord,
+{
+    /// the node itself
+    node: NRef<T, Priority>,
+    /// the queue cannot change while its nodes are being looked at
+    queue: PhantomData<&'a BareQueue<T, Priority>>,
+}
+
+impl<T, Priority> NodeRef<'_, T, Priority>
+where
+    Priority: Ord,
+{
+    pub(crate) const fn new(node: NRef<T, Priority>) -> Self {
+        Self {
+            node,
+            queue: PhantomData,
+        }
+    }
+}
+
+impl<T, Priority> NodeView<T, Priority> for NodeRef<'_, T, Priority>
+where
+    Priority: Ord,
+{
+    fn value(&self) -> Ref<'_, T> {
+        self.node.value_ref()
+    }
+
+    fn priority(&self) -> Ref<'_, Priority> {
+        self.node.priority_ref()
+    }
+
+    fn rank(&self) -> usize {
+        self.node.rank()
+    }
+
+    fn is_marked(&self) -> bool {
+        self.node.is_marked()
+    }
+
+    fn children(&self) -> impl Iterator<Item = Self> {
+        self.node.get_children().map(Self::new)
+    }
+}
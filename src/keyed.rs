@@ -0,0 +1,133 @@
+use crate::{error::Error, handle::Handle, heap::BareQueue, node::NPrpt};
+
+/* # keyed queue */
+
+/**
+fibonacci queue deriving the priority of every value from the value itself
+
+the key function is called whenever a value is pushed, and again after a value was changed
+through its handle, so that the priority always matches the value,
+and since values are never looked up, they need not implement `Eq`
+
+```
+use fbheap::heap::BareQueue;
+
+struct Task {
+    name: &'static str,
+    deadline: u32,
+}
+
+let mut tasks = BareQueue::by_key(|task: &Task| task.deadline);
+tasks.push(Task { name: "report", deadline: 30 })?;
+let review = tasks.push_with_handle(Task { name: "review", deadline: 50 })?;
+// the review was moved up, so its priority follows
+tasks.update_at(&review, |task| task.deadline = 10)?;
+assert_eq!(tasks.pop().map(|task| task.name), Ok("review"));
+assert_eq!(tasks.pop().map(|task| task.name), Ok("report"));
+assert!(tasks.is_empty());
+# Ok::<(), fbheap::error::Error>(())
+```
+*/
+pub struct KeyedQueue<T, Key, F>
+where
+    Key: Ord,
+    F: Fn(&T) -> Key,
+{
+    /// underlying queue, holding every value with its derived key
+    queue: BareQueue<T, Key>,
+    /// function deriving the key of a value
+    key: F,
+}
+
+impl<T, Key> BareQueue<T, Key>
+where
+    Key: Ord,
+{
+    /// construct empty queue deriving the priority of every value with the given function
+    #[must_use]
+    pub const fn by_key<F>(key: F) -> KeyedQueue<T, Key, F>
+    where
+        F: Fn(&T) -> Key,
+    {
+        KeyedQueue::new(key)
+    }
+}
+
+impl<T, Key, F> KeyedQueue<T, Key, F>
+where
+    Key: Ord,
+    F: Fn(&T) -> Key,
+{
+    /// construct empty queue deriving the priority of every value with the given function
+    #[must_use]
+    pub const fn new(key: F) -> Self {
+        Self {
+            queue: BareQueue::new(),
+            key,
+        }
+    }
+
+    /// returns true if the queue is empty
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// returns the number of elements in the queue
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /**
+    push a value onto the queue, with the priority derived from it
+
+    # Errors
+    ReachedCapacity => the queue is already at capacity
+    */
+    pub fn push(&mut self, t: T) -> Result<(), Error> {
+        let key = (self.key)(&t);
+        self.queue.try_push(t, key)
+    }
+
+    /**
+    push a value onto the queue, with the priority derived from it, returning a handle to it
+
+    # Errors
+    ReachedCapacity => the queue is already at capacity
+    */
+    pub fn push_with_handle(&mut self, t: T) -> Result<Handle<T, Key>, Error> {
+        let key = (self.key)(&t);
+        self.queue.try_push_with_handle(t, key)
+    }
+
+    /**
+    return the value with the lowest priority
+
+    # Errors
+    Empty => cannot return element from empty queue\n
+    NotFound => internal indexing error
+    */
+    pub fn pop(&mut self) -> Result<T, Error> {
+        self.queue.pop().map(|(t, _)| t)
+    }
+
+    /**
+    change the value the handle points to, and move it to the priority derived from its new state,
+    in whichever direction
+
+    # Errors
+    StaleHandle => the element the handle points to is no longer in any queue\n
+    NotFound => the handle points into another queue
+    */
+    pub fn update_at(
+        &mut self,
+        handle: &Handle<T, Key>,
+        change: impl FnOnce(&mut T),
+    ) -> Result<(), Error> {
+        let node = self.queue.get_handle_node(handle)?;
+        change(&mut node.value_mut());
+        let key = (self.key)(&node.value_ref());
+        self.queue.update_node_priority(node, key)
+    }
+}
@@ -0,0 +1,328 @@
+use crate::{error::Error, heap::BareQueue, queue::PriorityQueue};
+use alloc::vec::Vec;
+use core::mem::take;
+
+/* # lazy queue */
+
+/// number of tombstones a queue built by `new` holds before removing them from its trees
+const DEFAULT_THRESHOLD: usize = 64;
+
+/// account for one deleted copy of the value at the given position leaving the trees
+fn bury<T>(tombstones: &mut Vec<(T, usize)>, position: usize) {
+    let (_, count) = &mut tombstones[position];
+    *count -= 1;
+    if *count == 0 {
+        tombstones.swap_remove(position);
+    }
+}
+
+/**
+fibonacci queue which deletes elements lazily, for workloads where deletions are rare but pops are hot
+
+a deleted value is only recorded as a tombstone, without searching for its node or cutting it
+out of its tree, and is discarded once it reaches the front of the queue, so that pops never
+hand it out, while all tombstones are removed from the trees at once when there are more than
+the threshold of them
+
+a value pushed more than once needs as many deletions to leave the queue, and every tombstone
+takes out the copy with the lowest priority, which is the one that would reach the front first,
+so the outcome does not depend on when the queue compacts
+
+deleting through `PriorityQueue`, which hands back the deleted element, finds and removes it
+right away instead
+
+deleting a value which is not in the queue is a logic error,
+and leaves the length of the queue off until the next compaction
+
+```
+use fbheap::error::Error::Empty;
+use fbheap::lazy::LazyQueue;
+
+let mut queue = LazyQueue::with_threshold(1);
+queue.push("i was first", 3);
+queue.push("i am important", 1);
+queue.push("i was cancelled", 2);
+queue.push("i was cancelled too", 4);
+queue.delete(&"i was cancelled");
+assert_eq!(queue.len(), 3);
+assert_eq!(queue.tombstones(), 1);
+assert_eq!(queue.pop(), Ok(("i am important", 1)));
+// the cancelled element is discarded as soon as it reaches the front
+assert_eq!(queue.tombstones(), 0);
+queue.delete(&"i was cancelled too");
+assert_eq!(queue.pop(), Ok(("i was first", 3)));
+assert!(queue.is_empty());
+assert_eq!(queue.pop(), Err(Empty));
+```
+*/
+pub struct LazyQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /// underlying queue, whose first element is never a tombstone
+    queue: BareQueue<T, Priority>,
+    /// values deleted from the queue but still held by its trees, with the number of their deleted copies
+    tombstones: Vec<(T, usize)>,
+    /// number of tombstones above which they are removed from the trees
+    threshold: usize,
+}
+
+impl<T, Priority> Default for LazyQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, Priority> LazyQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /* # helper functions */
+
+    fn tombstone(&self, value: &T) -> Option<usize> {
+        self.tombstones.iter().position(|(t, _)| t == value)
+    }
+
+    /// pop tombstones off the front, so that the first element of the queue is a live one
+    fn discard_front(&mut self) -> Result<(), Error> {
+        while let Some(front) = self.queue.peek_mut() {
+            let Some(position) = self.tombstones.iter().position(|(t, _)| *t == *front.value())
+            else {
+                break;
+            };
+            bury(&mut self.tombstones, position);
+            front.pop()?;
+        }
+        Ok(())
+    }
+
+    /* # heap functionality */
+
+    /// construct empty queue
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::with_threshold(DEFAULT_THRESHOLD)
+    }
+
+    /// construct empty queue which compacts once it holds more than the given number of tombstones
+    #[must_use]
+    pub const fn with_threshold(threshold: usize) -> Self {
+        Self {
+            queue: BareQueue::new(),
+            tombstones: Vec::new(),
+            threshold,
+        }
+    }
+
+    /// returns true if the queue is empty
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// returns the number of elements in the queue, not counting tombstones
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.queue.len().saturating_sub(self.tombstones())
+    }
+
+    /// returns the number of deleted elements still held by the trees
+    #[must_use]
+    pub fn tombstones(&self) -> usize {
+        self.tombstones.iter().map(|(_, count)| count).sum()
+    }
+
+    /**
+    push a value onto the queue with given priority,
+    compacting first if the same value was deleted but not yet removed
+
+    # Errors
+    ReachedCapacity => the queue is already at capacity\n
+    NotFound => internal indexing error
+    */
+    pub fn push(&mut self, t: T, priority: Priority) -> Result<(), Error> {
+        if self.tombstone(&t).is_some() {
+            self.compact()?;
+        }
+        self.queue.try_push(t, priority)?;
+        self.discard_front()
+    }
+
+    /**
+    return the element with the lowest priority
+
+    # Errors
+    Empty => cannot return element from empty queue\n
+    NotFound => internal indexing error
+    */
+    pub fn pop(&mut self) -> Result<(T, Priority), Error> {
+        let element = self.queue.pop()?;
+        self.discard_front()?;
+        Ok(element)
+    }
+
+    /**
+    decreases the priority of the item with given value,
+    compacting first if the same value was deleted but not yet removed,
+    so that only a live copy is decreased
+
+    ```
+    use fbheap::error::Error::NotFound;
+    use fbheap::lazy::LazyQueue;
+
+    let mut queue = LazyQueue::new();
+    queue.push("retry", 5)?;
+    queue.push("retry", 6)?;
+    queue.delete(&"retry")?;
+    queue.decrease_priority(&"retry", 1)?;
+    assert_eq!(queue.pop(), Ok(("retry", 1)));
+    assert_eq!(queue.decrease_priority(&"retry", 0), Err(NotFound("value")));
+    # Ok::<(), fbheap::error::Error>(())
+    ```
+
+    # Errors
+    NotFound => index with given value was not found in the queue\n
+    CannotIncreasePriority => the give prioprity is higher than the current one for the index of that value
+    */
+    pub fn decrease_priority(&mut self, value: &T, new_priority: Priority) -> Result<(), Error> {
+        if self.tombstone(value).is_some() {
+            self.compact()?;
+        }
+        self.queue.decrease_priority(value, new_priority)
+    }
+
+    /**
+    delete one copy of the item with given value from the queue by recording a tombstone for it
+
+    ```
+    use fbheap::lazy::LazyQueue;
+
+    let mut queue = LazyQueue::new();
+    queue.push("a", 5)?;
+    queue.push("a", 6)?;
+    queue.push("z", 1)?;
+    queue.delete(&"a")?;
+    assert_eq!(queue.len(), 2);
+    assert_eq!(queue.pop(), Ok(("z", 1)));
+    // the tombstone took out the copy with the lowest priority
+    assert_eq!(queue.pop(), Ok(("a", 6)));
+    # Ok::<(), fbheap::error::Error>(())
+    ```
+
+    # Errors
+    NotFound => internal indexing error
+    */
+    pub fn delete(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: Clone,
+    {
+        match self.tombstone(value) {
+            Some(position) => self.tombstones[position].1 += 1,
+            None => self.tombstones.push((value.clone(), 1)),
+        }
+        self.discard_front()?;
+        if self.tombstones() > self.threshold {
+            self.compact()?;
+        }
+        Ok(())
+    }
+
+    /**
+    remove all tombstones from the trees of the queue,
+    taking out as many of the lowest copies of every deleted value as it has tombstones
+
+    # Errors
+    ImpossibleRcRelease => internal reference counting error\n
+    ReachedCapacity => the queue is already at capacity
+    */
+    pub fn compact(&mut self) -> Result<(), Error> {
+        if self.tombstones.is_empty() {
+            return Ok(());
+        }
+        // going up in priority, the first copies of a deleted value are the ones to take out
+        for (t, priority) in take(&mut self.queue).into_sorted_vec()? {
+            match self.tombstone(&t) {
+                Some(position) => bury(&mut self.tombstones, position),
+                None => self.queue.try_push(t, priority)?,
+            }
+        }
+        self.tombstones.clear();
+        Ok(())
+    }
+
+    /**
+    move all elements of another queue into this one, together with its tombstones
+
+    # Errors
+    will error if the combined queue would exceed capacity
+    */
+    pub fn meld(&mut self, other: Self) -> Result<(), Error> {
+        self.queue.meld(other.queue)?;
+        for (t, count) in other.tombstones {
+            match self.tombstone(&t) {
+                Some(position) => self.tombstones[position].1 += count,
+                None => self.tombstones.push((t, count)),
+            }
+        }
+        self.discard_front()?;
+        if self.tombstones() > self.threshold {
+            self.compact()?;
+        }
+        Ok(())
+    }
+}
+
+/* # priority queue */
+
+impl<T, Priority> PriorityQueue<T, Priority> for LazyQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    fn push(&mut self, t: T, priority: Priority) -> Result<(), Error> {
+        Self::push(self, t, priority)
+    }
+
+    fn pop(&mut self) -> Result<(T, Priority), Error> {
+        Self::pop(self)
+    }
+
+    fn peek(&self) -> Option<(T, Priority)>
+    where
+        T: Clone,
+        Priority: Clone,
+    {
+        self.queue.peek_cloned()
+    }
+
+    fn decrease_priority(&mut self, value: &T, new_priority: Priority) -> Result<(), Error> {
+        Self::decrease_priority(self, value, new_priority)
+    }
+
+    fn delete(&mut self, value: &T) -> Result<(T, Priority), Error> {
+        if self.tombstone(value).is_some() {
+            self.compact()?;
+        }
+        let element = self.queue.delete(value)?;
+        self.discard_front()?;
+        Ok(element)
+    }
+
+    fn is_empty(&self) -> bool {
+        Self::is_empty(self)
+    }
+
+    fn len(&self) -> usize {
+        Self::len(self)
+    }
+
+    fn meld(&mut self, other: Self) -> Result<(), Error> {
+        Self::meld(self, other)
+    }
+}
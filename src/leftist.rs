@@ -0,0 +1,355 @@
+use crate::{
+    error::Error,
+    queue::PriorityQueue,
+    slab::{Slab, SlabNode},
+};
+use core::mem::swap;
+
+/* # leftist queue */
+
+/// node stored in a slot of the arena, linked to other nodes by index
+struct Slot<T, Priority> {
+    /// held value
+    t: T,
+    /// priority of the held value
+    priority: Priority,
+    /// index of the parent node
+    parent: Option<u32>,
+    /// index of the left child, whose rank is not lower than that of the right one
+    left: Option<u32>,
+    /// index of the right child
+    right: Option<u32>,
+    /// length of the rightmost path down to a missing child
+    rank: u32,
+}
+
+impl<T, Priority> Slot<T, Priority> {
+    /// create ampty node
+    const fn new(t: T, priority: Priority) -> Self {
+        Self {
+            t,
+            priority,
+            parent: None,
+            left: None,
+            right: None,
+            rank: 1,
+        }
+    }
+}
+
+impl<T, Priority> SlabNode for Slot<T, Priority>
+where
+    Priority: Ord,
+{
+    type Priority = Priority;
+
+    fn priority(&self) -> &Priority {
+        &self.priority
+    }
+}
+
+/**
+leftist queue storing its nodes in a single vector, linked by indices instead of pointers
+
+it keeps a single binary tree in which the rightmost path from every node is no longer than any
+other path down to a missing child, so that two trees are merged along their rightmost paths
+in logarithmic time in the worst case, which is all that pushing, popping and decreasing take
+
+```
+use fbheap::error::Error::Empty;
+use fbheap::leftist::LeftistQueue;
+
+let mut queue = LeftistQueue::new();
+queue.push("i was first", 3);
+queue.push("i am important", 1);
+queue.push("i was not important at first", 4);
+assert_eq!(queue.pop(), Ok(("i am important", 1)));
+queue.decrease_priority(&"i was not important at first", 2);
+assert_eq!(queue.pop(), Ok(("i was not important at first", 2)));
+assert_eq!(queue.pop(), Ok(("i was first", 3)));
+assert!(queue.is_empty());
+assert_eq!(queue.pop(), Err(Empty));
+```
+*/
+pub struct LeftistQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /// storage for nodes, vacant slots are reused by later pushes
+    slots: Slab<Slot<T, Priority>>,
+    /// index of the root, which holds the lowest priority, it such exists
+    root: Option<u32>,
+    /// number of nodes in the queue
+    node_count: usize,
+}
+
+impl<T, Priority> Default for LeftistQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, Priority> LeftistQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /* # helper functions */
+
+    /* ## slot functions */
+
+    fn rank(&self, index: Option<u32>) -> Result<u32, Error> {
+        index.map_or(Ok(0), |index| Ok(self.slots.get(index)?.rank))
+    }
+
+    /* ## structural functions */
+
+    /// swap the children of a node if the right one has the higher rank, and recompute its rank,
+    /// returning whether the rank changed
+    fn restore(&mut self, index: u32) -> Result<bool, Error> {
+        let slot = self.slots.get(index)?;
+        let (left, right) = (self.rank(slot.left)?, self.rank(slot.right)?);
+        let slot = self.slots.get_mut(index)?;
+        if left < right {
+            swap(&mut slot.left, &mut slot.right);
+        }
+        let rank = left.min(right) + 1;
+        let changed = slot.rank != rank;
+        slot.rank = rank;
+        Ok(changed)
+    }
+
+    /// merge two detached trees along their rightmost paths, returning the root of the merged tree
+    fn merge(&mut self, one: Option<u32>, other: Option<u32>) -> Result<Option<u32>, Error> {
+        let (parent, child) = match (one, other) {
+            (None, tree) | (tree, None) => return Ok(tree),
+            (Some(one), Some(other)) if self.slots.is_lower(other, one)? => (other, one),
+            (Some(one), Some(other)) => (one, other),
+        };
+        // the rightmost paths are logarithmic, so the recursion is shallow
+        let right = self.slots.get_mut(parent)?.right.take();
+        let merged = self.merge(right, Some(child))?;
+        if let Some(merged) = merged {
+            self.slots.get_mut(merged)?.parent = Some(parent);
+        }
+        self.slots.get_mut(parent)?.right = merged;
+        self.restore(parent)?;
+        Ok(Some(parent))
+    }
+
+    /// merge a detached tree into the queue
+    fn meld_root(&mut self, index: u32) -> Result<(), Error> {
+        self.root = self.merge(self.root, Some(index))?;
+        if let Some(root) = self.root {
+            self.slots.get_mut(root)?.parent = None;
+        }
+        Ok(())
+    }
+
+    /// separate the tree rooted at a node from its parent,
+    /// restoring the ranks above it for as long as they change
+    fn detach(&mut self, index: u32) -> Result<(), Error> {
+        let Some(parent) = self.slots.get_mut(index)?.parent.take() else {
+            self.root = None;
+            return Ok(());
+        };
+        let slot = self.slots.get_mut(parent)?;
+        if slot.left == Some(index) {
+            slot.left = None;
+        } else {
+            slot.right = None;
+        }
+        let mut next = Some(parent);
+        while let Some(node) = next {
+            if !self.restore(node)? {
+                break;
+            }
+            next = self.slots.get(node)?.parent;
+        }
+        Ok(())
+    }
+
+    /// merge the children of a detached node into a single tree, returning its root
+    fn merge_children(&mut self, index: u32) -> Result<Option<u32>, Error> {
+        let slot = self.slots.get_mut(index)?;
+        let (left, right) = (slot.left.take(), slot.right.take());
+        for child in [left, right].into_iter().flatten() {
+            self.slots.get_mut(child)?.parent = None;
+        }
+        self.merge(left, right)
+    }
+
+    fn get_node(&self, t: &T) -> Option<u32> {
+        self.slots.position(|slot| slot.t == *t)
+    }
+
+    /* # heap functionality */
+
+    /// construct empty queue
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            slots: Slab::new(),
+            root: None,
+            node_count: 0,
+        }
+    }
+
+    /// returns true if the queue is empty
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.node_count == 0
+    }
+
+    /// returns the number of elements in the queue
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.node_count
+    }
+
+    /**
+    push a value onto the queue with given priority
+
+    # Errors
+    will error if the queue is already at capacity
+    */
+    pub fn push(&mut self, t: T, priority: Priority) -> Result<(), Error> {
+        let index = self.slots.allocate(Slot::new(t, priority))?;
+        self.node_count = self
+            .node_count
+            .checked_add(1)
+            .ok_or(Error::ReachedCapacity)?;
+        self.meld_root(index)
+    }
+
+    /**
+    return the element with the lowest priority
+
+    # Errors
+    Empty => cannot return element from empty queue\n
+    NotFound => internal indexing error
+    */
+    pub fn pop(&mut self) -> Result<(T, Priority), Error> {
+        let root = self.root.ok_or(Error::Empty)?;
+        self.node_count = self.node_count.checked_sub(1).ok_or(Error::Empty)?;
+        self.root = self.merge_children(root)?;
+
+        let slot = self.slots.release(root)?;
+        Ok((slot.t, slot.priority))
+    }
+
+    /**
+    decreases the priority of the item with given value
+
+    # Errors
+    NotFound => index with given value was not found in the queue\n
+    CannotIncreasePriority => the give prioprity is higher than the current one for the index of that value
+    */
+    pub fn decrease_priority(&mut self, value: &T, new_priority: Priority) -> Result<(), Error> {
+        let index = self.get_node(value).ok_or(Error::NotFound("value"))?;
+        if self.slots.get(index)?.priority <= new_priority {
+            return Err(Error::CannotIncreasePriority {
+                current_was_lower: self.slots.get(index)?.priority < new_priority,
+            });
+        }
+        self.slots.get_mut(index)?.priority = new_priority;
+        if self.root != Some(index) {
+            self.detach(index)?;
+            self.meld_root(index)?;
+        }
+        Ok(())
+    }
+
+    /**
+    remove the item with given value from the queue, regardless of its priority
+
+    # Errors
+    NotFound => index with given value was not found in the queue
+    */
+    pub fn delete(&mut self, value: &T) -> Result<(T, Priority), Error> {
+        let index = self.get_node(value).ok_or(Error::NotFound("value"))?;
+        self.detach(index)?;
+        self.node_count = self.node_count.checked_sub(1).ok_or(Error::Empty)?;
+        if let Some(children) = self.merge_children(index)? {
+            self.meld_root(children)?;
+        }
+
+        let slot = self.slots.release(index)?;
+        Ok((slot.t, slot.priority))
+    }
+
+    /**
+    move all elements of another queue into this one,
+    which takes time proportional to the size of the other queue, since its indices are shifted,
+    while the trees themselves are merged in logarithmic time
+
+    # Errors
+    will error if the combined queue would exceed capacity
+    */
+    pub fn meld(&mut self, other: Self) -> Result<(), Error> {
+        let node_count = self
+            .node_count
+            .checked_add(other.node_count)
+            .ok_or(Error::ReachedCapacity)?;
+        let offset = self.slots.append(other.slots, |slot, offset| {
+            slot.parent = slot.parent.map(|index| index + offset);
+            slot.left = slot.left.map(|index| index + offset);
+            slot.right = slot.right.map(|index| index + offset);
+        })?;
+        self.node_count = node_count;
+        if let Some(root) = other.root {
+            self.meld_root(root + offset)?;
+        }
+        Ok(())
+    }
+}
+
+/* # priority queue */
+
+impl<T, Priority> PriorityQueue<T, Priority> for LeftistQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    fn push(&mut self, t: T, priority: Priority) -> Result<(), Error> {
+        Self::push(self, t, priority)
+    }
+
+    fn pop(&mut self) -> Result<(T, Priority), Error> {
+        Self::pop(self)
+    }
+
+    fn peek(&self) -> Option<(T, Priority)>
+    where
+        T: Clone,
+        Priority: Clone,
+    {
+        let slot = self.slots.get(self.root?).ok()?;
+        Some((slot.t.clone(), slot.priority.clone()))
+    }
+
+    fn decrease_priority(&mut self, value: &T, new_priority: Priority) -> Result<(), Error> {
+        Self::decrease_priority(self, value, new_priority)
+    }
+
+    fn delete(&mut self, value: &T) -> Result<(T, Priority), Error> {
+        Self::delete(self, value)
+    }
+
+    fn is_empty(&self) -> bool {
+        Self::is_empty(self)
+    }
+
+    fn len(&self) -> usize {
+        Self::len(self)
+    }
+
+    fn meld(&mut self, other: Self) -> Result<(), Error> {
+        Self::meld(self, other)
+    }
+}
@@ -1,7 +1,55 @@
-#![feature(let_chains)]
+#![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
+pub mod arena;
+pub mod binomial;
+pub mod bounded;
+pub mod boxed;
+#[cfg(feature = "async")]
+pub mod channel;
+#[cfg(feature = "std")]
+pub mod dary;
 pub mod error;
+pub mod event;
+pub mod explain;
+#[cfg(feature = "std")]
+pub mod frontier;
+pub mod graph;
+pub mod handle;
+#[cfg(feature = "std")]
+pub mod hash;
 pub mod heap;
+pub mod huffman;
+pub mod inspect;
+pub mod keyed;
+pub mod lazy;
+pub mod leftist;
+pub mod lookup;
+pub mod max;
+pub mod median;
+pub mod merge;
+pub mod minmax;
+pub mod model;
+pub mod monotone;
+pub mod pairing;
+pub mod persistent;
+pub mod policy;
+pub mod priority;
+pub mod queue;
+pub mod radix;
+#[cfg(feature = "raw")]
+pub mod raw;
+pub mod soft;
+pub mod stable;
+pub mod stats;
+#[cfg(feature = "std")]
+pub mod sync;
+pub mod topk;
+pub mod unique;
+
+/// vector of nodes linked by index, shared by the queues which do not count references
+mod slab;
 
 /// container for data with priority in the tree strucutre of the heap
 mod node;
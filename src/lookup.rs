@@ -0,0 +1,298 @@
+use crate::handle::Handle;
+use alloc::{boxed::Box, collections::BTreeMap, vec::Vec};
+
+/* # lookup strategies */
+
+/// outcome of looking a value up in an index
+pub enum Lookup<T, Priority>
+where
+    Priority: Eq,
+{
+    /// an element holding the value
+    Found(Handle<T, Priority>),
+    /// no element holds the value
+    Missing,
+    /// the index keeps no records, so the queue has to search through its trees
+    Search,
+}
+
+/**
+strategy by which a queue finds the element holding a given value,
+as used by `decrease_priority`, `delete` and the other operations taking a value
+
+the queue reports every element entering and leaving it, with a handle identifying the element,
+so that an index can answer lookups without searching through the trees,
+and values are never changed while they are recorded
+
+```
+use fbheap::heap::BareQueue;
+use fbheap::lookup::{NoLookup, OrdIndex};
+
+// ordered values are found in logarithmic time
+let mut queue = BareQueue::new();
+queue.set_lookup(OrdIndex::new());
+queue.push("a", 3);
+queue.push("b", 5);
+queue.decrease_priority(&"b", 1)?;
+assert_eq!(queue.pop(), Ok(("b", 1)));
+
+// opaque values are never searched for
+let mut jobs = BareQueue::new();
+jobs.set_lookup(NoLookup);
+jobs.push("c", 2);
+assert!(!jobs.contains(&"c"));
+# Ok::<(), fbheap::error::Error>(())
+```
+*/
+pub trait LookupIndex<T, Priority>
+where
+    Priority: Eq,
+{
+    /// returns false if the index keeps no records, sparing the queue from reporting elements
+    fn is_tracking(&self) -> bool {
+        true
+    }
+
+    /// record an element holding the value entering the queue
+    fn insert(&mut self, value: &T, handle: Handle<T, Priority>);
+
+    /// forget an element holding the value leaving the queue
+    fn remove(&mut self, value: &T, handle: &Handle<T, Priority>);
+
+    /// forget all elements
+    fn clear(&mut self);
+
+    /// look up an element holding the value
+    fn find(&self, value: &T) -> Lookup<T, Priority>;
+
+    /// construct empty index of the same kind, for queues cloned or split off from this one
+    fn new_empty(&self) -> Box<dyn LookupIndex<T, Priority>>;
+}
+
+/* ## search */
+
+/// searching through the trees on every lookup, in time linear in the size of the queue,
+/// which is what queues do without any strategy set
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Search;
+
+impl<T, Priority> LookupIndex<T, Priority> for Search
+where
+    Priority: Eq,
+{
+    fn is_tracking(&self) -> bool {
+        false
+    }
+
+    fn insert(&mut self, _: &T, _: Handle<T, Priority>) {}
+
+    fn remove(&mut self, _: &T, _: &Handle<T, Priority>) {}
+
+    fn clear(&mut self) {}
+
+    fn find(&self, _: &T) -> Lookup<T, Priority> {
+        Lookup::Search
+    }
+
+    fn new_empty(&self) -> Box<dyn LookupIndex<T, Priority>> {
+        Box::new(Self)
+    }
+}
+
+/* ## no lookup */
+
+/// never finding any value, for opaque values which are only ever reached through handles,
+/// so that a mistaken lookup fails right away instead of searching the whole queue
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoLookup;
+
+impl<T, Priority> LookupIndex<T, Priority> for NoLookup
+where
+    Priority: Eq,
+{
+    fn is_tracking(&self) -> bool {
+        false
+    }
+
+    fn insert(&mut self, _: &T, _: Handle<T, Priority>) {}
+
+    fn remove(&mut self, _: &T, _: &Handle<T, Priority>) {}
+
+    fn clear(&mut self) {}
+
+    fn find(&self, _: &T) -> Lookup<T, Priority> {
+        Lookup::Missing
+    }
+
+    fn new_empty(&self) -> Box<dyn LookupIndex<T, Priority>> {
+        Box::new(Self)
+    }
+}
+
+/* ## indices */
+
+/// take the handle out of the ones recorded for a value, returning whether none are left
+fn forget<T, Priority>(handles: &mut Vec<Handle<T, Priority>>, handle: &Handle<T, Priority>) -> bool
+where
+    Priority: Eq,
+{
+    if let Some(position) = handles.iter().position(|other| other == handle) {
+        handles.swap_remove(position);
+    }
+    handles.is_empty()
+}
+
+/// ordered map from values to their elements, for values which are `Ord` and `Clone`,
+/// finding them in logarithmic time
+pub struct OrdIndex<T, Priority>
+where
+    Priority: Eq,
+{
+    /// elements by value, several of them when a value is queued more than once
+    elements: BTreeMap<T, Vec<Handle<T, Priority>>>,
+}
+
+impl<T, Priority> OrdIndex<T, Priority>
+where
+    Priority: Eq,
+{
+    /// construct empty index
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            elements: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T, Priority> Default for OrdIndex<T, Priority>
+where
+    Priority: Eq,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, Priority> LookupIndex<T, Priority> for OrdIndex<T, Priority>
+where
+    T: Ord + Clone + 'static,
+    Priority: Eq + 'static,
+{
+    fn insert(&mut self, value: &T, handle: Handle<T, Priority>) {
+        self.elements.entry(value.clone()).or_default().push(handle);
+    }
+
+    fn remove(&mut self, value: &T, handle: &Handle<T, Priority>) {
+        if self
+            .elements
+            .get_mut(value)
+            .is_some_and(|handles| forget(handles, handle))
+        {
+            self.elements.remove(value);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.elements.clear();
+    }
+
+    fn find(&self, value: &T) -> Lookup<T, Priority> {
+        match self.elements.get(value).and_then(|handles| handles.first()) {
+            Some(handle) => Lookup::Found(handle.clone()),
+            None => Lookup::Missing,
+        }
+    }
+
+    fn new_empty(&self) -> Box<dyn LookupIndex<T, Priority>> {
+        Box::new(Self::new())
+    }
+}
+
+/**
+hash map from values to their elements, for values which are `Hash` and `Clone`,
+finding them in constant time
+
+```
+use fbheap::heap::BareQueue;
+use fbheap::lookup::HashIndex;
+
+let mut queue = BareQueue::new();
+queue.push("a", 3);
+queue.push("a", 4);
+queue.set_lookup(HashIndex::new());
+queue.delete(&"a")?;
+assert!(queue.contains(&"a"));
+queue.delete(&"a")?;
+assert!(!queue.contains(&"a"));
+# Ok::<(), fbheap::error::Error>(())
+```
+*/
+#[cfg(feature = "std")]
+pub struct HashIndex<T, Priority>
+where
+    Priority: Eq,
+{
+    /// elements by value, several of them when a value is queued more than once
+    elements: std::collections::HashMap<T, Vec<Handle<T, Priority>>>,
+}
+
+#[cfg(feature = "std")]
+impl<T, Priority> HashIndex<T, Priority>
+where
+    Priority: Eq,
+{
+    /// construct empty index
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            elements: std::collections::HashMap::new(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, Priority> Default for HashIndex<T, Priority>
+where
+    Priority: Eq,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, Priority> LookupIndex<T, Priority> for HashIndex<T, Priority>
+where
+    T: Eq + core::hash::Hash + Clone + 'static,
+    Priority: Eq + 'static,
+{
+    fn insert(&mut self, value: &T, handle: Handle<T, Priority>) {
+        self.elements.entry(value.clone()).or_default().push(handle);
+    }
+
+    fn remove(&mut self, value: &T, handle: &Handle<T, Priority>) {
+        if self
+            .elements
+            .get_mut(value)
+            .is_some_and(|handles| forget(handles, handle))
+        {
+            self.elements.remove(value);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.elements.clear();
+    }
+
+    fn find(&self, value: &T) -> Lookup<T, Priority> {
+        match self.elements.get(value).and_then(|handles| handles.first()) {
+            Some(handle) => Lookup::Found(handle.clone()),
+            None => Lookup::Missing,
+        }
+    }
+
+    fn new_empty(&self) -> Box<dyn LookupIndex<T, Priority>> {
+        Box::new(Self::new())
+    }
+}
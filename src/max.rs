@@ -0,0 +1,176 @@
+use crate::{error::Error, heap::BareQueue};
+use core::cmp::Reverse;
+
+/* # max queue */
+
+/**
+fibonacci queue which hands out the element with the highest priority first
+
+```
+use fbheap::error::Error::Empty;
+use fbheap::max::MaxQueue;
+
+let mut queue = MaxQueue::new();
+queue.push("i was first", 3);
+queue.push("i am important", 5);
+queue.push("i was not important at first", 1);
+assert_eq!(queue.pop(), Ok(("i am important", 5)));
+queue.increase_priority(&"i was not important at first", 4);
+assert_eq!(queue.pop(), Ok(("i was not important at first", 4)));
+assert_eq!(queue.pop(), Ok(("i was first", 3)));
+assert!(queue.is_empty());
+assert_eq!(queue.pop(), Err(Empty));
+```
+*/
+pub struct MaxQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /// underlying queue ordered by reversed priorities
+    queue: BareQueue<T, Reverse<Priority>>,
+}
+
+impl<T, Priority> Default for MaxQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, Priority> MaxQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /* # helper functions */
+
+    /// translate errors of the underlying queue, where the direction of priorities is reversed
+    const fn flip(error: Error) -> Error {
+        match error {
+            Error::CannotIncreasePriority { current_was_lower } => Error::CannotDecreasePriority {
+                current_was_higher: current_was_lower,
+            },
+            Error::CannotDecreasePriority { current_was_higher } => Error::CannotIncreasePriority {
+                current_was_lower: current_was_higher,
+            },
+            error => error,
+        }
+    }
+
+    /* # heap functionality */
+
+    /// construct empty queue
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            queue: BareQueue::new(),
+        }
+    }
+
+    /// returns true if the queue is empty
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// returns the number of elements in the queue
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /**
+    push a value onto the queue with given priority
+
+    # Errors
+    will error if the queue is already at capacity
+    */
+    pub fn push(&mut self, t: T, priority: Priority) -> Result<(), Error> {
+        self.queue.try_push(t, Reverse(priority))
+    }
+
+    /**
+    return the element with the highest priority
+
+    # Errors
+    Empty => cannot return element from empty queue\n
+    NotFound => internal indexing error
+    */
+    pub fn pop(&mut self) -> Result<(T, Priority), Error> {
+        self.queue.pop().map(|(t, Reverse(priority))| (t, priority))
+    }
+
+    /// returns a copy of the element with the highest priority, if there is any
+    #[must_use]
+    pub fn peek(&self) -> Option<(T, Priority)>
+    where
+        T: Clone,
+        Priority: Clone,
+    {
+        self.queue
+            .peek_cloned()
+            .map(|(t, Reverse(priority))| (t, priority))
+    }
+
+    /**
+    increases the priority of the item with given value
+
+    # Errors
+    NotFound => index with given value was not found in the queue\n
+    CannotDecreasePriority => the given priority is lower than the current one for the index of that value
+    */
+    pub fn increase_priority(&mut self, value: &T, new_priority: Priority) -> Result<(), Error> {
+        self.queue
+            .decrease_priority(value, Reverse(new_priority))
+            .map_err(Self::flip)
+    }
+
+    /**
+    decreases the priority of the item with given value
+
+    # Errors
+    NotFound => index with given value was not found in the queue\n
+    CannotIncreasePriority => the give prioprity is higher than the current one for the index of that value
+    */
+    pub fn decrease_priority(&mut self, value: &T, new_priority: Priority) -> Result<(), Error> {
+        self.queue
+            .increase_priority(value, Reverse(new_priority))
+            .map_err(Self::flip)
+    }
+
+    /**
+    changes the priority of the item with given value, in whichever direction
+
+    # Errors
+    NotFound => index with given value was not found in the queue
+    */
+    pub fn update_priority(&mut self, value: &T, new_priority: Priority) -> Result<(), Error> {
+        self.queue.update_priority(value, Reverse(new_priority))
+    }
+
+    /**
+    remove the item with given value from the queue, regardless of its priority
+
+    # Errors
+    NotFound => index with given value was not found in the queue
+    */
+    pub fn delete(&mut self, value: &T) -> Result<(T, Priority), Error> {
+        self.queue
+            .delete(value)
+            .map(|(t, Reverse(priority))| (t, priority))
+    }
+
+    /**
+    move all elements of another queue into this one
+
+    # Errors
+    will error if the combined queue would exceed capacity
+    */
+    pub fn meld(&mut self, other: Self) -> Result<(), Error> {
+        self.queue.meld(other.queue)
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{error::Error, heap::BareQueue, max::MaxQueue};
+
+/* # running median */
+
+/**
+median of a stream of priorities, kept up to date as priorities are inserted
+
+the lower half of the priorities is kept in a queue handing out its highest one first,
+and the upper half in a queue handing out its lowest one first,
+so that the median is found at the front of the lower half,
+which holds one priority more than the upper half when their number is odd
+
+```
+use fbheap::median::RunningMedian;
+
+let mut latencies = RunningMedian::new();
+assert_eq!(latencies.median(), None);
+for latency in [12, 3, 40] {
+    latencies.insert(latency)?;
+}
+assert_eq!(latencies.median(), Some(12));
+latencies.insert(7)?;
+assert_eq!(latencies.median(), Some(7));
+assert_eq!(latencies.upper_median(), Some(12));
+# Ok::<(), fbheap::error::Error>(())
+```
+*/
+pub struct RunningMedian<Priority>
+where
+    Priority: Ord,
+{
+    /// lower half of the priorities, including the median
+    lower: MaxQueue<(), Priority>,
+    /// upper half of the priorities
+    upper: BareQueue<(), Priority>,
+}
+
+impl<Priority> Default for RunningMedian<Priority>
+where
+    Priority: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Priority> RunningMedian<Priority>
+where
+    Priority: Ord,
+{
+    /// construct empty median
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            lower: MaxQueue::new(),
+            upper: BareQueue::new(),
+        }
+    }
+
+    /// returns true if no priorities were inserted
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.lower.is_empty()
+    }
+
+    /// returns the number of inserted priorities
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.lower.len() + self.upper.len()
+    }
+
+    /**
+    insert a priority, passing the highest priority of the lower half on to the upper half,
+    and the lowest one of the upper half back if it grew larger than the lower half
+
+    # Errors
+    ReachedCapacity => the queue is already at capacity\n
+    NotFound => internal indexing error
+    */
+    pub fn insert(&mut self, priority: Priority) -> Result<(), Error> {
+        self.lower.push((), priority)?;
+        let (_, highest) = self.lower.pop()?;
+        self.upper.try_push((), highest)?;
+        if self.upper.len() > self.lower.len() {
+            let (_, lowest) = self.upper.pop()?;
+            self.lower.push((), lowest)?;
+        }
+        Ok(())
+    }
+
+    /// returns the median, or the lower of the two middle priorities when their number is even
+    #[must_use]
+    pub fn median(&self) -> Option<Priority>
+    where
+        Priority: Clone,
+    {
+        self.lower.peek().map(|(_, priority)| priority)
+    }
+
+    /// returns the median, or the upper of the two middle priorities when their number is even
+    #[must_use]
+    pub fn upper_median(&self) -> Option<Priority>
+    where
+        Priority: Clone,
+    {
+        if self.upper.len() < self.lower.len() {
+            self.median()
+        } else {
+            self.upper.peek_cloned().map(|(_, priority)| priority)
+        }
+    }
+}
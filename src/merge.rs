@@ -0,0 +1,78 @@
+use crate::heap::BareQueue;
+use alloc::vec::Vec;
+use core::mem::replace;
+
+/* # k-way merge */
+
+/**
+merge iterators which are each sorted into a single sorted iterator,
+pulling the next item of an input only once its previous item was handed out,
+which then takes the place of the previous item at the front of the queue
+
+the queue holds the next item of every input that is not exhausted,
+so that items which compare equal are handed out in the order of their inputs
+
+```
+use fbheap::merge::kmerge;
+
+let runs = vec![vec![1, 4, 9], vec![2, 3, 10], vec![], vec![4, 5]];
+let merged: Vec<_> = kmerge(runs.into_iter().map(Vec::into_iter)).collect();
+assert_eq!(merged, vec![1, 2, 3, 4, 4, 5, 9, 10]);
+```
+*/
+pub fn kmerge<I>(iterators: impl IntoIterator<Item = I>) -> KMerge<I>
+where
+    I: Iterator,
+    I::Item: Ord,
+{
+    let mut inputs: Vec<I> = iterators.into_iter().collect();
+    let mut queue = BareQueue::new();
+    for (index, input) in inputs.iter_mut().enumerate() {
+        if let Some(item) = input.next() {
+            queue.push(index, (item, index));
+        }
+    }
+    KMerge { inputs, queue }
+}
+
+/// iterator merging sorted inputs, built by [`kmerge`]
+pub struct KMerge<I>
+where
+    I: Iterator,
+    I::Item: Ord,
+{
+    /// inputs, in the order ties between them are broken in
+    inputs: Vec<I>,
+    /// next item of every input that is not exhausted, queued under the index of its input
+    queue: BareQueue<usize, (I::Item, usize)>,
+}
+
+impl<I> Iterator for KMerge<I>
+where
+    I: Iterator,
+    I::Item: Ord,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut front = self.queue.peek_mut()?;
+        let index = *front.value();
+        match self.inputs[index].next() {
+            // the next item of the same input replaces the front in place, and the guard restores the order
+            Some(next) => Some(replace(&mut front.priority_mut().0, next)),
+            None => front.pop().ok().map(|(_, (item, _))| item),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inputs.iter().map(Iterator::size_hint).fold(
+            (self.queue.len(), Some(self.queue.len())),
+            |(low, high), (l, h)| {
+                (
+                    low.saturating_add(l),
+                    high.zip(h).and_then(|(high, h)| high.checked_add(h)),
+                )
+            },
+        )
+    }
+}
@@ -0,0 +1,202 @@
+use crate::{
+    arena::{ArenaHandle, ArenaQueue},
+    error::Error,
+    queue::PriorityQueue,
+};
+use alloc::vec::Vec;
+use core::cmp::Reverse;
+
+/* # min max queue */
+
+/// element of the queue, linked to its nodes in both underlying queues
+struct Slot<T> {
+    value: T,
+    min: ArenaHandle,
+    max: ArenaHandle,
+}
+
+/**
+double ended fibonacci queue, handing out the element with either the lowest or the highest priority
+
+every element is queued in a queue ordered by lowest priorities and in one ordered by highest
+priorities, each holding a handle to it in the other, so that the element popped from one queue
+is deleted from the other one right away
+
+both queues keep their nodes in arenas, so the queue can be sent across threads whenever its contents can
+
+```
+use fbheap::error::Error::Empty;
+use fbheap::minmax::MinMaxQueue;
+
+let mut jobs = MinMaxQueue::new();
+jobs.push("backup", 3)?;
+jobs.push("page", 1)?;
+jobs.push("reindex", 7)?;
+jobs.push("report", 5)?;
+// serve the most urgent job first
+assert_eq!(jobs.pop_min(), Ok(("page", 1)));
+// shed the least urgent job when overloaded
+assert_eq!(jobs.pop_max(), Ok(("reindex", 7)));
+assert_eq!(jobs.peek_max(), Some(("report", 5)));
+assert_eq!(jobs.pop_min(), Ok(("backup", 3)));
+assert_eq!(jobs.pop_min(), Ok(("report", 5)));
+assert!(jobs.is_empty());
+assert_eq!(jobs.pop_max(), Err(Empty));
+# Ok::<(), fbheap::error::Error>(())
+```
+*/
+pub struct MinMaxQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /// slots of the elements, ordered by lowest priorities
+    min: ArenaQueue<usize, Priority>,
+    /// slots of the elements, ordered by highest priorities
+    max: ArenaQueue<usize, Reverse<Priority>>,
+    /// elements by their slot, empty for slots that are free
+    slots: Vec<Option<Slot<T>>>,
+    /// slots that can be reused
+    free: Vec<usize>,
+}
+
+impl<T, Priority> Default for MinMaxQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, Priority> MinMaxQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /* # helper functions */
+
+    /// empty a slot, returning the element it held
+    fn release(&mut self, slot: usize) -> Result<Slot<T>, Error> {
+        let released = self
+            .slots
+            .get_mut(slot)
+            .and_then(Option::take)
+            .ok_or(Error::NotFound("node"))?;
+        self.free.push(slot);
+        Ok(released)
+    }
+
+    /* # heap functionality */
+
+    /// construct empty queue
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            min: ArenaQueue::new(),
+            max: ArenaQueue::new(),
+            slots: Vec::new(),
+            free: Vec::new(),
+        }
+    }
+
+    /// returns true if the queue is empty
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.min.is_empty()
+    }
+
+    /// returns the number of elements in the queue
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.min.len()
+    }
+
+    /**
+    push a value onto the queue with given priority
+
+    # Errors
+    ReachedCapacity => the queue is already at capacity
+    */
+    pub fn push(&mut self, t: T, priority: Priority) -> Result<(), Error>
+    where
+        Priority: Clone,
+    {
+        let slot = self.free.pop().unwrap_or(self.slots.len());
+        let max = match self.max.push_with_handle(slot, Reverse(priority.clone())) {
+            Ok(max) => max,
+            Err(error) => {
+                self.free.push(slot);
+                return Err(error);
+            }
+        };
+        let min = match self.min.push_with_handle(slot, priority) {
+            Ok(min) => min,
+            Err(error) => {
+                self.max.delete_at(max)?;
+                self.free.push(slot);
+                return Err(error);
+            }
+        };
+        let filled = Some(Slot { value: t, min, max });
+        if slot == self.slots.len() {
+            self.slots.push(filled);
+        } else {
+            self.slots[slot] = filled;
+        }
+        Ok(())
+    }
+
+    /**
+    return the element with the lowest priority
+
+    # Errors
+    Empty => cannot return element from empty queue\n
+    NotFound => internal indexing error
+    */
+    pub fn pop_min(&mut self) -> Result<(T, Priority), Error> {
+        let (slot, priority) = self.min.pop()?;
+        let released = self.release(slot)?;
+        self.max.delete_at(released.max)?;
+        Ok((released.value, priority))
+    }
+
+    /**
+    return the element with the highest priority
+
+    # Errors
+    Empty => cannot return element from empty queue\n
+    NotFound => internal indexing error
+    */
+    pub fn pop_max(&mut self) -> Result<(T, Priority), Error> {
+        let (slot, Reverse(priority)) = self.max.pop()?;
+        let released = self.release(slot)?;
+        self.min.delete_at(released.min)?;
+        Ok((released.value, priority))
+    }
+
+    /// returns a copy of the element with the lowest priority, if there is any
+    #[must_use]
+    pub fn peek_min(&self) -> Option<(T, Priority)>
+    where
+        T: Clone,
+        Priority: Clone,
+    {
+        let (slot, priority) = PriorityQueue::peek(&self.min)?;
+        let value = self.slots.get(slot)?.as_ref()?.value.clone();
+        Some((value, priority))
+    }
+
+    /// returns a copy of the element with the highest priority, if there is any
+    #[must_use]
+    pub fn peek_max(&self) -> Option<(T, Priority)>
+    where
+        T: Clone,
+        Priority: Clone,
+    {
+        let (slot, Reverse(priority)) = PriorityQueue::peek(&self.max)?;
+        let value = self.slots.get(slot)?.as_ref()?.value.clone();
+        Some((value, priority))
+    }
+}
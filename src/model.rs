@@ -0,0 +1,283 @@
+use crate::{error::Error, queue::PriorityQueue};
+use alloc::vec::Vec;
+use core::fmt::Debug;
+
+/* # reference model */
+
+/// single operation on a priority queue, as replayed by `Model::apply`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Op<T, Priority> {
+    Push(T, Priority),
+    Pop,
+    Peek,
+    DecreasePriority(T, Priority),
+    Delete(T),
+}
+
+#[cfg(feature = "fuzzing")]
+impl Op<u8, u8> {
+    /**
+    decode arbitrary bytes into operations, so that a fuzzer can explore sequences of them
+
+    every operation takes one byte for its kind, followed by a value and a priority for pushes
+    and decreases, or just a value for deletes, and an incomplete operation at the end is dropped
+
+    ```
+    use fbheap::model::Op;
+
+    let ops = Op::decode(&[0, 7, 3, 4, 6, 7, 1, 7, 3, 7, 2]);
+    assert_eq!(
+        ops,
+        vec![Op::Push(7, 3), Op::Pop, Op::DecreasePriority(7, 1), Op::Peek, Op::Delete(7)]
+    );
+    ```
+    */
+    #[must_use]
+    pub fn decode(bytes: &[u8]) -> Vec<Self> {
+        let mut bytes = bytes.iter().copied();
+        let mut ops = Vec::new();
+        while let Some(kind) = bytes.next() {
+            let op = match kind % 8 {
+                4 | 5 => Self::Pop,
+                7 => Self::Peek,
+                3 => {
+                    let Some(t) = bytes.next() else {
+                        break;
+                    };
+                    Self::Delete(t)
+                }
+                kind => {
+                    let (Some(t), Some(priority)) = (bytes.next(), bytes.next()) else {
+                        break;
+                    };
+                    if kind == 6 {
+                        Self::DecreasePriority(t, priority)
+                    } else {
+                        Self::Push(t, priority)
+                    }
+                }
+            };
+            ops.push(op);
+        }
+        ops
+    }
+}
+
+/**
+reference queue keeping its elements in a vector sorted by priority,
+too slow for real use but simple enough to be obviously correct
+
+it checks any backend implementing `PriorityQueue` by replaying the same operations on both and
+asserting that they agree, where elements of equal priority may come out in any order,
+and pushes of values which are already queued are skipped, since queues look elements up by value
+
+```
+use fbheap::model::{Model, Op};
+use fbheap::pairing::PairingQueue;
+
+let mut queue = PairingQueue::new();
+Model::check(
+    &mut queue,
+    [
+        Op::Push("a", 3),
+        Op::Push("b", 1),
+        Op::DecreasePriority("a", 0),
+        Op::DecreasePriority("c", 0),
+        Op::Delete("b"),
+        Op::Delete("b"),
+        Op::Pop,
+        Op::Peek,
+        Op::Pop,
+        Op::Pop,
+    ],
+);
+assert!(queue.is_empty());
+```
+*/
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Model<T, Priority> {
+    /// elements by descending priority, so that the lowest one is popped off the end
+    elements: Vec<(T, Priority)>,
+}
+
+impl<T, Priority> Default for Model<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, Priority> Model<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /* # helper functions */
+
+    fn position(&self, value: &T) -> Option<usize> {
+        self.elements.iter().position(|(t, _)| t == value)
+    }
+
+    fn lowest_priority(&self) -> Option<&Priority> {
+        self.elements.last().map(|(_, priority)| priority)
+    }
+
+    /* # heap functionality */
+
+    /// construct empty model
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            elements: Vec::new(),
+        }
+    }
+
+    /// returns true if the model is empty
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// returns the number of elements in the model
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// returns true if the model holds the given value
+    #[must_use]
+    pub fn contains(&self, value: &T) -> bool {
+        self.position(value).is_some()
+    }
+
+    /// push a value with given priority
+    pub fn push(&mut self, t: T, priority: Priority) {
+        let index = self
+            .elements
+            .partition_point(|(_, other)| *other > priority);
+        self.elements.insert(index, (t, priority));
+    }
+
+    /**
+    return the element with the lowest priority
+
+    # Errors
+    Empty => cannot return element from empty model
+    */
+    pub fn pop(&mut self) -> Result<(T, Priority), Error> {
+        self.elements.pop().ok_or(Error::Empty)
+    }
+
+    /**
+    decreases the priority of the item with given value
+
+    # Errors
+    NotFound => the value is not in the model\n
+    CannotIncreasePriority => the given priority is not lower than the current one
+    */
+    pub fn decrease_priority(&mut self, value: &T, new_priority: Priority) -> Result<(), Error> {
+        let index = self.position(value).ok_or(Error::NotFound("value"))?;
+        if self.elements[index].1 <= new_priority {
+            return Err(Error::CannotIncreasePriority {
+                current_was_lower: self.elements[index].1 < new_priority,
+            });
+        }
+        let (t, _) = self.elements.remove(index);
+        self.push(t, new_priority);
+        Ok(())
+    }
+
+    /**
+    remove the item with given value, regardless of its priority
+
+    # Errors
+    NotFound => the value is not in the model
+    */
+    pub fn delete(&mut self, value: &T) -> Result<(T, Priority), Error> {
+        let index = self.position(value).ok_or(Error::NotFound("value"))?;
+        Ok(self.elements.remove(index))
+    }
+
+    /* # checking */
+
+    /**
+    apply an operation to both the model and a queue
+
+    # Panics
+    panics if the queue disagrees with the model about the outcome or about its length
+    */
+    pub fn apply<Q>(&mut self, queue: &mut Q, op: Op<T, Priority>)
+    where
+        Q: PriorityQueue<T, Priority>,
+        T: Clone + Debug,
+        Priority: Clone + Debug,
+    {
+        match op {
+            Op::Push(t, priority) => {
+                if !self.contains(&t) {
+                    assert_eq!(queue.push(t.clone(), priority.clone()), Ok(()));
+                    self.push(t, priority);
+                }
+            }
+            Op::Pop => match queue.pop() {
+                Ok((t, priority)) => {
+                    assert_eq!(Some(&priority), self.lowest_priority(), "popped {t:?}");
+                    assert_eq!(self.delete(&t).ok(), Some((t, priority)));
+                }
+                Err(error) => {
+                    assert_eq!(error, Error::Empty);
+                    assert!(self.is_empty(), "failed to pop from non empty queue");
+                }
+            },
+            Op::Peek => match queue.peek() {
+                Some((t, priority)) => {
+                    assert_eq!(Some(&priority), self.lowest_priority(), "peeked {t:?}");
+                    let index = self.position(&t);
+                    assert!(
+                        index.is_some_and(|index| self.elements[index].1 == priority),
+                        "peeked {t:?} which is not in the model"
+                    );
+                }
+                None => assert!(self.is_empty(), "failed to peek into non empty queue"),
+            },
+            Op::DecreasePriority(t, priority) => {
+                assert_eq!(
+                    queue.decrease_priority(&t, priority.clone()),
+                    self.decrease_priority(&t, priority),
+                    "decreasing {t:?}"
+                );
+            }
+            Op::Delete(t) => {
+                assert_eq!(queue.delete(&t), self.delete(&t), "deleting {t:?}");
+            }
+        }
+        assert_eq!(queue.len(), self.len());
+        assert_eq!(queue.is_empty(), self.is_empty());
+    }
+
+    /**
+    apply a sequence of operations to a queue, checking every step against a fresh model,
+    and then pop all remaining elements
+
+    # Panics
+    panics if the queue disagrees with the model at any step
+    */
+    pub fn check<Q>(queue: &mut Q, ops: impl IntoIterator<Item = Op<T, Priority>>)
+    where
+        Q: PriorityQueue<T, Priority>,
+        T: Clone + Debug,
+        Priority: Clone + Debug,
+    {
+        let mut model = Self::new();
+        for op in ops {
+            model.apply(queue, op);
+        }
+        while !model.is_empty() {
+            model.apply(queue, Op::Pop);
+        }
+        model.apply(queue, Op::Pop);
+    }
+}
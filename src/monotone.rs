@@ -0,0 +1,232 @@
+use crate::{error::Error, heap::BareQueue, queue::PriorityQueue};
+
+/* # monotone queue */
+
+/**
+fibonacci queue which rejects priorities lower than that of the last popped element
+
+in dijkstra-like searches priorities never drop below the last popped one,
+so a push or decrease violating this points to a bug, such as a negative edge
+or an inconsistent heuristic, which is reported instead of silently reordering the queue
+
+```
+use fbheap::error::Error::NonMonotonePush;
+use fbheap::monotone::MonotoneQueue;
+
+let mut queue = MonotoneQueue::new();
+queue.push("a", 2)?;
+queue.push("b", 5)?;
+assert_eq!(queue.pop(), Ok(("a", 2)));
+assert_eq!(queue.last_popped(), Some(&2));
+// reached through a negative edge
+assert_eq!(queue.push("c", 1), Err(NonMonotonePush));
+assert_eq!(queue.decrease_priority(&"b", 1), Err(NonMonotonePush));
+queue.decrease_priority(&"b", 2)?;
+assert_eq!(queue.pop(), Ok(("b", 2)));
+# Ok::<(), fbheap::error::Error>(())
+```
+*/
+pub struct MonotoneQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /// underlying queue
+    queue: BareQueue<T, Priority>,
+    /// priority of the last popped element, which no queued priority may be lower than
+    last: Option<Priority>,
+}
+
+impl<T, Priority> Default for MonotoneQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, Priority> MonotoneQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /* # helper functions */
+
+    /// check that the priority is not lower than that of the last popped element
+    fn check(&self, priority: &Priority) -> Result<(), Error> {
+        match &self.last {
+            Some(last) if priority < last => Err(Error::NonMonotonePush),
+            _ => Ok(()),
+        }
+    }
+
+    /* # heap functionality */
+
+    /// construct empty queue
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            queue: BareQueue::new(),
+            last: None,
+        }
+    }
+
+    /// returns true if the queue is empty
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// returns the number of elements in the queue
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// returns the priority of the last popped element, if any element was popped
+    #[must_use]
+    pub const fn last_popped(&self) -> Option<&Priority> {
+        self.last.as_ref()
+    }
+
+    /**
+    push a value onto the queue with given priority
+
+    # Errors
+    NonMonotonePush => the priority is lower than that of the last popped element\n
+    ReachedCapacity => the queue is already at capacity
+    */
+    pub fn push(&mut self, t: T, priority: Priority) -> Result<(), Error> {
+        self.check(&priority)?;
+        self.queue.try_push(t, priority)
+    }
+
+    /**
+    return the element with the lowest priority, remembering its priority
+
+    # Errors
+    Empty => cannot return element from empty queue\n
+    NotFound => internal indexing error
+    */
+    pub fn pop(&mut self) -> Result<(T, Priority), Error>
+    where
+        Priority: Clone,
+    {
+        let (t, priority) = self.queue.pop()?;
+        self.last = Some(priority.clone());
+        Ok((t, priority))
+    }
+
+    /**
+    decreases the priority of the item with given value
+
+    # Errors
+    NonMonotonePush => the priority is lower than that of the last popped element\n
+    NotFound => index with given value was not found in the queue\n
+    CannotIncreasePriority => the give prioprity is higher than the current one for the index of that value
+    */
+    pub fn decrease_priority(&mut self, value: &T, new_priority: Priority) -> Result<(), Error> {
+        self.check(&new_priority)?;
+        self.queue.decrease_priority(value, new_priority)
+    }
+
+    /**
+    increases the priority of the item with given value
+
+    # Errors
+    NotFound => index with given value was not found in the queue\n
+    CannotDecreasePriority => the given priority is lower than the current one for the index of that value
+    */
+    pub fn increase_priority(&mut self, value: &T, new_priority: Priority) -> Result<(), Error> {
+        self.queue.increase_priority(value, new_priority)
+    }
+
+    /**
+    changes the priority of the item with given value, in whichever direction
+
+    # Errors
+    NonMonotonePush => the priority is lower than that of the last popped element\n
+    NotFound => index with given value was not found in the queue
+    */
+    pub fn update_priority(&mut self, value: &T, new_priority: Priority) -> Result<(), Error> {
+        self.check(&new_priority)?;
+        self.queue.update_priority(value, new_priority)
+    }
+
+    /**
+    remove the item with given value from the queue, regardless of its priority
+
+    # Errors
+    NotFound => index with given value was not found in the queue
+    */
+    pub fn delete(&mut self, value: &T) -> Result<(T, Priority), Error> {
+        self.queue.delete(value)
+    }
+
+    /**
+    move all elements of another queue into this one,
+    keeping the higher of the two last popped priorities
+
+    # Errors
+    NonMonotonePush => the other queue holds a priority lower than that of the last popped element,
+    in which case this queue is left unchanged and the other one is dropped\n
+    ReachedCapacity => the combined queue would exceed capacity
+    */
+    pub fn meld(&mut self, mut other: Self) -> Result<(), Error> {
+        if let Some(front) = other.queue.peek_mut() {
+            self.check(&front.priority())?;
+        }
+        if let Some(front) = self.queue.peek_mut() {
+            other.check(&front.priority())?;
+        }
+        self.queue.meld(other.queue)?;
+        self.last = self.last.take().max(other.last);
+        Ok(())
+    }
+}
+
+/* # priority queue */
+
+impl<T, Priority> PriorityQueue<T, Priority> for MonotoneQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord + Clone,
+{
+    fn push(&mut self, t: T, priority: Priority) -> Result<(), Error> {
+        Self::push(self, t, priority)
+    }
+
+    fn pop(&mut self) -> Result<(T, Priority), Error> {
+        Self::pop(self)
+    }
+
+    fn peek(&self) -> Option<(T, Priority)>
+    where
+        T: Clone,
+        Priority: Clone,
+    {
+        self.queue.peek_cloned()
+    }
+
+    fn decrease_priority(&mut self, value: &T, new_priority: Priority) -> Result<(), Error> {
+        Self::decrease_priority(self, value, new_priority)
+    }
+
+    fn delete(&mut self, value: &T) -> Result<(T, Priority), Error> {
+        Self::delete(self, value)
+    }
+
+    fn is_empty(&self) -> bool {
+        Self::is_empty(self)
+    }
+
+    fn len(&self) -> usize {
+        Self::len(self)
+    }
+
+    fn meld(&mut self, other: Self) -> Result<(), Error> {
+        Self::meld(self, other)
+    }
+}
@@ -1,22 +1,51 @@
-use crate::error::Error;
-use core::{cell::RefCell, cmp::Ordering};
-use std::rc::Rc;
+use crate::{error::Error, handle::Owner};
+use alloc::{
+    rc::{Rc, Weak},
+    vec::Vec,
+};
+use core::{
+    cell::{Ref, RefCell, RefMut},
+    cmp::Ordering,
+    mem::swap,
+};
 
 pub type NRef<T, Priority> = Rc<RefCell<NCore<T, Priority>>>;
+pub type NWeak<T, Priority> = Weak<RefCell<NCore<T, Priority>>>;
 
-pub trait NPrpt<T, Priority>: Clone + Ord {
+/**
+structural operations on nodes, private to the crate as they can break the heap if misused,
+read-only access from outside goes through `inspect::NodeView`
+*/
+pub(crate) trait NPrpt<T, Priority>: Clone + Ord {
     fn new_node(t: T, priority: Priority) -> Self;
 
     /** # Errors
     will error if the reference count on self exceeds one
     */
     fn pair(self) -> Result<(T, Priority), Error>;
+    fn pair_cloned(&self) -> (T, Priority)
+    where
+        T: Clone,
+        Priority: Clone;
+    /// exchange the value and priority of the node with the given ones, in place
+    fn swap_pair(&self, pair: &mut (T, Priority));
     // fn pair_ref(&self) -> (&T, &Priority);
 
     /* # values */
     fn has_higher_priority(&self, priority: &Priority) -> bool;
+    fn has_lower_priority(&self, priority: &Priority) -> bool;
     fn set_priority(&self, priority: Priority);
-    fn has_value(&self, t: &T) -> bool;
+    fn value_ref(&self) -> Ref<'_, T>;
+    fn value_mut(&self) -> RefMut<'_, T>;
+    fn priority_ref(&self) -> Ref<'_, Priority>;
+    fn priority_mut(&self) -> RefMut<'_, Priority>;
+    fn has_value(&self, t: &T) -> bool
+    where
+        T: Eq;
+    fn sequence(&self) -> u64;
+    fn set_sequence(&self, sequence: u64);
+    fn owner(&self) -> Option<Rc<Owner>>;
+    fn set_owner(&self, owner: &Rc<Owner>);
 
     /* # mark */
     fn mark(&self);
@@ -25,7 +54,7 @@ pub trait NPrpt<T, Priority>: Clone + Ord {
 
     /* # parents */
     fn get_parent(&self) -> Option<Self>;
-    fn set_parent(&self, parent: Self);
+    fn set_parent(&self, parent: &Self);
     fn remove_parent(&self);
 
     /* # children */
@@ -36,34 +65,41 @@ pub trait NPrpt<T, Priority>: Clone + Ord {
     will error if the child is not found
     */
     fn remove_child(&self, child: &Self) -> Result<(), Error>;
-    fn get_children(&self) -> Vec<Self>;
-    fn drain_children(&self) -> Vec<Self>;
+    /// iterate over the children, without holding on to a borrow of the node in between
+    fn get_children(&self) -> impl Iterator<Item = Self>;
+    /// detach the children one at a time, as the iterator advances
+    fn drain_children(&self) -> impl Iterator<Item = Self>;
 
     /* # ops */
     fn link(&mut self, other: &mut Self);
 }
 
-#[derive(PartialEq, Eq)]
 pub struct NCore<T, Priority>
 where
-    T: Eq,
     Priority: Eq,
 {
     /// held value
     t: T,
     /// priority of the held value
     priority: Priority,
-    /// parent node in the tree structure
-    parent: Option<NRef<T, Priority>>,
+    /// parent node in the tree structure, weak so that parents and children do not keep each other alive
+    parent: Option<NWeak<T, Priority>>,
+    /// previous sibling, weak so that siblings do not keep each other alive in both directions
+    prev: Option<NWeak<T, Priority>>,
+    /// next sibling
+    next: Option<NRef<T, Priority>>,
     /// children in the tree structure
-    children: Vec<NRef<T, Priority>>,
+    children: NList<T, Priority>,
     /// flag for whether this node has lost any children already
     marked: bool,
+    /// insertion sequence number breaking ties between equal priorities, zero unless deterministic
+    sequence: u64,
+    /// identity of the queue holding the node, for checking handles against it
+    owner: Option<Rc<Owner>>,
 }
 
 impl<T, Priority> NCore<T, Priority>
 where
-    T: Eq,
     Priority: Eq,
 {
     /// create ampty node
@@ -72,8 +108,12 @@ where
             t,
             priority,
             parent: None,
-            children: Vec::new(),
+            prev: None,
+            next: None,
+            children: NList::new(),
             marked: false,
+            sequence: 0,
+            owner: None,
         }
     }
 
@@ -91,30 +131,207 @@ where
     */
 }
 
+/* # sibling lists */
+
+/**
+doubly linked list of sibling nodes, linked through the nodes themselves,
+so that inserting, removing and appending take constant time
+
+the list is not circular, so that strong references between siblings only ever point forward
+and never form a cycle
+*/
+pub struct NList<T, Priority>
+where
+    Priority: Eq,
+{
+    /// first node of the list
+    head: Option<NRef<T, Priority>>,
+    /// last node of the list
+    tail: Option<NWeak<T, Priority>>,
+    /// number of nodes in the list
+    len: usize,
+}
+
+impl<T, Priority> NList<T, Priority>
+where
+    Priority: Eq,
+{
+    /// create empty list
+    pub const fn new() -> Self {
+        Self {
+            head: None,
+            tail: None,
+            len: 0,
+        }
+    }
+
+    /// returns the number of nodes in the list
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// add a detached node at the end of the list
+    pub fn push(&mut self, node: NRef<T, Priority>) {
+        let tail = self.tail.replace(Rc::downgrade(&node));
+        match tail.as_ref().and_then(NWeak::upgrade) {
+            Some(tail) => {
+                node.borrow_mut().prev = Some(Rc::downgrade(&tail));
+                tail.borrow_mut().next = Some(node);
+            }
+            None => self.head = Some(node),
+        }
+        self.len += 1;
+    }
+
+    /** # Errors
+    will error if the node is not found at the start of the list while lacking a previous sibling
+    */
+    pub fn remove(&mut self, node: &NRef<T, Priority>) -> Result<(), Error> {
+        let prev = node.borrow().prev.as_ref().and_then(NWeak::upgrade);
+        if prev.is_none()
+            && !self
+                .head
+                .as_ref()
+                .is_some_and(|head| Rc::ptr_eq(head, node))
+        {
+            return Err(Error::NotFound("node"));
+        }
+        let next = {
+            let mut core = node.borrow_mut();
+            core.prev = None;
+            core.next.take()
+        };
+        match &next {
+            Some(next) => next.borrow_mut().prev = prev.as_ref().map(Rc::downgrade),
+            None => self.tail = prev.as_ref().map(Rc::downgrade),
+        }
+        match &prev {
+            Some(prev) => prev.borrow_mut().next = next,
+            None => self.head = next,
+        }
+        self.len -= 1;
+        Ok(())
+    }
+
+    /// move all nodes of the other list to the end of this one
+    pub fn append(&mut self, other: &mut Self) {
+        let Some(head) = other.head.take() else {
+            return;
+        };
+        match self.tail.as_ref().and_then(NWeak::upgrade) {
+            Some(tail) => {
+                head.borrow_mut().prev = Some(Rc::downgrade(&tail));
+                tail.borrow_mut().next = Some(head);
+            }
+            None => self.head = Some(head),
+        }
+        self.tail = other.tail.take();
+        self.len += other.len;
+        other.len = 0;
+    }
+
+    /// detach the first node from the list
+    pub fn pop_front(&mut self) -> Option<NRef<T, Priority>> {
+        let head = self.head.take()?;
+        self.head = head.borrow_mut().next.take();
+        match &self.head {
+            Some(next) => next.borrow_mut().prev = None,
+            None => self.tail = None,
+        }
+        self.len -= 1;
+        Some(head)
+    }
+
+    /// iterate over the nodes in order
+    pub fn iter(&self) -> impl Iterator<Item = NRef<T, Priority>> {
+        core::iter::successors(self.head.clone(), |node| node.borrow().next.clone())
+    }
+
+    /// detach all nodes from the list, in order
+    pub fn drain(&mut self) -> Vec<NRef<T, Priority>> {
+        let mut nodes = Vec::with_capacity(self.len);
+        let mut next = self.head.take();
+        while let Some(node) = next {
+            next = {
+                let mut core = node.borrow_mut();
+                core.prev = None;
+                core.next.take()
+            };
+            nodes.push(node);
+        }
+        self.tail = None;
+        self.len = 0;
+        nodes
+    }
+}
+
+impl<T, Priority> Drop for NList<T, Priority>
+where
+    Priority: Eq,
+{
+    /// unlink the nodes one at a time, as dropping a long list recursively would overflow the stack
+    fn drop(&mut self) {
+        let mut next = self.head.take();
+        while let Some(node) = next {
+            next = node.borrow_mut().next.take();
+        }
+    }
+}
+
+impl<T, Priority> core::fmt::Debug for NCore<T, Priority>
+where
+    T: core::fmt::Debug,
+    Priority: Eq + core::fmt::Debug,
+{
+    /// describes the node itself, without following links to other nodes
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("NCore")
+            .field("t", &self.t)
+            .field("priority", &self.priority)
+            .field("rank", &self.children.len())
+            .field("marked", &self.marked)
+            .finish()
+    }
+}
+
+impl<T, Priority> PartialEq for NCore<T, Priority>
+where
+    Priority: Eq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority && self.sequence == other.sequence
+    }
+}
+
+impl<T, Priority> Eq for NCore<T, Priority> where Priority: Eq {}
+
 impl<T, Priority> PartialOrd for NCore<T, Priority>
 where
-    T: Eq,
     Priority: Eq + PartialOrd,
 {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.priority.partial_cmp(&other.priority)
+        match self.priority.partial_cmp(&other.priority) {
+            Some(Ordering::Equal) => Some(self.sequence.cmp(&other.sequence)),
+            ordering => ordering,
+        }
     }
 }
 
 impl<T, Priority> Ord for NCore<T, Priority>
 where
-    T: Eq,
     Priority: Eq + Ord,
 {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.priority.cmp(&other.priority)
+        self.priority
+            .cmp(&other.priority)
+            .then(self.sequence.cmp(&other.sequence))
     }
 }
 
 /*
 impl<T, Priority> Hash for NCore<T, Priority>
 where
-    T: Eq + Hash,
+    T: Hash,
     Priority: Eq + Ord + Hash,
 {
     fn hash<H: Hasher>(&self, state: &mut H) {
@@ -126,7 +343,6 @@ where
 
 impl<T, Priority> NPrpt<T, Priority> for NRef<T, Priority>
 where
-    T: Eq,
     Priority: Eq + Ord,
 {
     fn new_node(t: T, priority: Priority) -> Self {
@@ -144,6 +360,21 @@ where
             .pair())
     }
 
+    fn pair_cloned(&self) -> (T, Priority)
+    where
+        T: Clone,
+        Priority: Clone,
+    {
+        let core = self.borrow();
+        (core.t.clone(), core.priority.clone())
+    }
+
+    fn swap_pair(&self, pair: &mut (T, Priority)) {
+        let mut core = self.borrow_mut();
+        swap(&mut core.t, &mut pair.0);
+        swap(&mut core.priority, &mut pair.1);
+    }
+
     /*
     fn pair_ref(&self) -> (&T, &Priority) {
         self.borrow().pair_ref()
@@ -154,14 +385,53 @@ where
         self.borrow().priority > *priority
     }
 
+    fn has_lower_priority(&self, priority: &Priority) -> bool {
+        self.borrow().priority < *priority
+    }
+
     fn set_priority(&self, priority: Priority) {
         self.borrow_mut().priority = priority;
     }
 
-    fn has_value(&self, t: &T) -> bool {
+    fn value_ref(&self) -> Ref<'_, T> {
+        Ref::map(self.borrow(), |core| &core.t)
+    }
+
+    fn value_mut(&self) -> RefMut<'_, T> {
+        RefMut::map(self.borrow_mut(), |core| &mut core.t)
+    }
+
+    fn priority_ref(&self) -> Ref<'_, Priority> {
+        Ref::map(self.borrow(), |core| &core.priority)
+    }
+
+    fn priority_mut(&self) -> RefMut<'_, Priority> {
+        RefMut::map(self.borrow_mut(), |core| &mut core.priority)
+    }
+
+    fn has_value(&self, t: &T) -> bool
+    where
+        T: Eq,
+    {
         self.borrow().t == *t
     }
 
+    fn sequence(&self) -> u64 {
+        self.borrow().sequence
+    }
+
+    fn set_sequence(&self, sequence: u64) {
+        self.borrow_mut().sequence = sequence;
+    }
+
+    fn owner(&self) -> Option<Rc<Owner>> {
+        self.borrow().owner.clone()
+    }
+
+    fn set_owner(&self, owner: &Rc<Owner>) {
+        self.borrow_mut().owner = Some(Rc::clone(owner));
+    }
+
     fn mark(&self) {
         self.borrow_mut().marked = true;
     }
@@ -175,11 +445,11 @@ where
     }
 
     fn get_parent(&self) -> Option<Self> {
-        self.borrow().parent.clone()
+        self.borrow().parent.as_ref().and_then(NWeak::upgrade)
     }
 
-    fn set_parent(&self, parent: Self) {
-        self.borrow_mut().parent = Some(parent);
+    fn set_parent(&self, parent: &Self) {
+        self.borrow_mut().parent = Some(Rc::downgrade(parent));
     }
 
     fn remove_parent(&self) {
@@ -191,31 +461,24 @@ where
     }
 
     fn remove_child(&self, child: &Self) -> Result<(), Error> {
-        let index = self
-            .borrow()
-            .children
-            .iter()
-            .position(|x| x == child)
-            .ok_or(Error::InvalidIndex)?;
-        self.borrow_mut().children.swap_remove(index);
-        Ok(())
+        self.borrow_mut().children.remove(child)
     }
 
-    fn get_children(&self) -> Vec<Self> {
-        self.borrow_mut().children.clone()
+    fn get_children(&self) -> impl Iterator<Item = Self> {
+        self.borrow().children.iter()
     }
 
-    fn drain_children(&self) -> Vec<Self> {
-        self.borrow_mut().children.drain(..).collect()
+    fn drain_children(&self) -> impl Iterator<Item = Self> {
+        core::iter::from_fn(|| self.borrow_mut().children.pop_front())
     }
 
     fn link(&mut self, other: &mut Self) {
         let (smaller, bigger) = match self.cmp(&other) {
-            Ordering::Greater => (self, other),
-            _ => (other, self),
+            Ordering::Greater => (other, self),
+            _ => (self, other),
         };
 
-        bigger.set_parent(smaller.clone());
+        bigger.set_parent(smaller);
         smaller.insert_child(bigger.clone());
         smaller.unmark();
     }
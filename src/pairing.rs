@@ -0,0 +1,349 @@
+use crate::{
+    error::Error,
+    queue::PriorityQueue,
+    slab::{Slab, SlabNode},
+};
+use alloc::vec::Vec;
+
+/* # pairing queue */
+
+/// node stored in a slot of the arena, linked to other nodes by index
+struct Slot<T, Priority> {
+    /// held value
+    t: T,
+    /// priority of the held value
+    priority: Priority,
+    /// index of the leftmost child
+    child: Option<u32>,
+    /// index of the next sibling to the right
+    sibling: Option<u32>,
+    /// index of the previous sibling, or of the parent for the leftmost child
+    prev: Option<u32>,
+}
+
+impl<T, Priority> Slot<T, Priority> {
+    /// create ampty node
+    const fn new(t: T, priority: Priority) -> Self {
+        Self {
+            t,
+            priority,
+            child: None,
+            sibling: None,
+            prev: None,
+        }
+    }
+}
+
+impl<T, Priority> SlabNode for Slot<T, Priority>
+where
+    Priority: Ord,
+{
+    type Priority = Priority;
+
+    fn priority(&self) -> &Priority {
+        &self.priority
+    }
+}
+
+/**
+pairing queue storing its nodes in a single vector, linked by indices instead of pointers
+
+it offers the same operations as the `BareQueue` with a single tree, which is merged with every
+pushed element and restructured in two passes on every pop,
+and in practice is often faster than a fibonacci queue despite weaker amortized bounds
+
+```
+use fbheap::error::Error::Empty;
+use fbheap::pairing::PairingQueue;
+
+let mut queue = PairingQueue::new();
+queue.push("i was first", 3);
+queue.push("i am important", 1);
+queue.push("i was not important at first", 4);
+assert_eq!(queue.pop(), Ok(("i am important", 1)));
+queue.decrease_priority(&"i was not important at first", 2);
+assert_eq!(queue.pop(), Ok(("i was not important at first", 2)));
+assert_eq!(queue.pop(), Ok(("i was first", 3)));
+assert!(queue.is_empty());
+assert_eq!(queue.pop(), Err(Empty));
+```
+*/
+pub struct PairingQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /// storage for nodes, vacant slots are reused by later pushes
+    slots: Slab<Slot<T, Priority>>,
+    /// index of the root, which holds the lowest priority, it such exists
+    root: Option<u32>,
+    /// number of nodes in the queue
+    node_count: usize,
+}
+
+impl<T, Priority> Default for PairingQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, Priority> PairingQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /* # helper functions */
+
+    /* ## structural functions */
+
+    /// merge two detached trees, returning the root of the merged tree
+    fn link(&mut self, one: u32, other: u32) -> Result<u32, Error> {
+        let (parent, child) = if self.slots.is_lower(other, one)? {
+            (other, one)
+        } else {
+            (one, other)
+        };
+        let first_child = self.slots.get(parent)?.child;
+        if let Some(first_child) = first_child {
+            self.slots.get_mut(first_child)?.prev = Some(child);
+        }
+        let slot = self.slots.get_mut(child)?;
+        slot.sibling = first_child;
+        slot.prev = Some(parent);
+        self.slots.get_mut(parent)?.child = Some(child);
+        Ok(parent)
+    }
+
+    /// merge a detached tree into the queue
+    fn meld_root(&mut self, index: u32) -> Result<(), Error> {
+        self.root = Some(match self.root {
+            Some(root) => self.link(root, index)?,
+            None => index,
+        });
+        Ok(())
+    }
+
+    /// separate the tree rooted at a node from its parent and siblings
+    fn detach(&mut self, index: u32) -> Result<(), Error> {
+        let slot = self.slots.get_mut(index)?;
+        let (prev, sibling) = (slot.prev.take(), slot.sibling.take());
+        if let Some(sibling) = sibling {
+            self.slots.get_mut(sibling)?.prev = prev;
+        }
+        if let Some(prev) = prev {
+            let prev = self.slots.get_mut(prev)?;
+            if prev.child == Some(index) {
+                prev.child = sibling;
+            } else {
+                prev.sibling = sibling;
+            }
+        }
+        Ok(())
+    }
+
+    /// merge the children of a node into a single tree in two passes, returning its root
+    fn merge_children(&mut self, index: u32) -> Result<Option<u32>, Error> {
+        let mut children = Vec::new();
+        let mut next = self.slots.get_mut(index)?.child.take();
+        while let Some(child) = next {
+            let slot = self.slots.get_mut(child)?;
+            next = slot.sibling.take();
+            slot.prev = None;
+            children.push(child);
+        }
+
+        // merge pairs from left to right, then the pairs from right to left
+        let mut pairs = Vec::with_capacity(children.len().div_ceil(2));
+        for pair in children.chunks(2) {
+            pairs.push(match *pair {
+                [one, other] => self.link(one, other)?,
+                [one] => one,
+                _ => unreachable!("chunks hold one or two children"),
+            });
+        }
+        let mut root = None;
+        for tree in pairs.into_iter().rev() {
+            root = Some(match root {
+                Some(root) => self.link(root, tree)?,
+                None => tree,
+            });
+        }
+        Ok(root)
+    }
+
+    fn get_node(&self, t: &T) -> Option<u32> {
+        self.slots.position(|slot| slot.t == *t)
+    }
+
+    /* # heap functionality */
+
+    /// construct empty queue
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            slots: Slab::new(),
+            root: None,
+            node_count: 0,
+        }
+    }
+
+    /// returns true if the queue is empty
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.node_count == 0
+    }
+
+    /// returns the number of elements in the queue
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.node_count
+    }
+
+    /**
+    push a value onto the queue with given priority
+
+    # Errors
+    will error if the queue is already at capacity
+    */
+    pub fn push(&mut self, t: T, priority: Priority) -> Result<(), Error> {
+        let index = self.slots.allocate(Slot::new(t, priority))?;
+        self.node_count = self
+            .node_count
+            .checked_add(1)
+            .ok_or(Error::ReachedCapacity)?;
+        self.meld_root(index)
+    }
+
+    /**
+    return the element with the lowest priority
+
+    # Errors
+    Empty => cannot return element from empty queue\n
+    NotFound => internal indexing error
+    */
+    pub fn pop(&mut self) -> Result<(T, Priority), Error> {
+        let root = self.root.ok_or(Error::Empty)?;
+        self.node_count = self.node_count.checked_sub(1).ok_or(Error::Empty)?;
+        self.root = self.merge_children(root)?;
+
+        let slot = self.slots.release(root)?;
+        Ok((slot.t, slot.priority))
+    }
+
+    /**
+    decreases the priority of the item with given value
+
+    # Errors
+    NotFound => index with given value was not found in the queue\n
+    CannotIncreasePriority => the give prioprity is higher than the current one for the index of that value
+    */
+    pub fn decrease_priority(&mut self, value: &T, new_priority: Priority) -> Result<(), Error> {
+        let index = self.get_node(value).ok_or(Error::NotFound("value"))?;
+        if self.slots.get(index)?.priority <= new_priority {
+            return Err(Error::CannotIncreasePriority {
+                current_was_lower: self.slots.get(index)?.priority < new_priority,
+            });
+        }
+        self.slots.get_mut(index)?.priority = new_priority;
+        if self.root != Some(index) {
+            self.detach(index)?;
+            self.meld_root(index)?;
+        }
+        Ok(())
+    }
+
+    /**
+    remove the item with given value from the queue, regardless of its priority
+
+    # Errors
+    NotFound => index with given value was not found in the queue
+    */
+    pub fn delete(&mut self, value: &T) -> Result<(T, Priority), Error> {
+        let index = self.get_node(value).ok_or(Error::NotFound("value"))?;
+        self.node_count = self.node_count.checked_sub(1).ok_or(Error::Empty)?;
+        if self.root == Some(index) {
+            self.root = self.merge_children(index)?;
+        } else {
+            self.detach(index)?;
+            if let Some(children) = self.merge_children(index)? {
+                self.meld_root(children)?;
+            }
+        }
+
+        let slot = self.slots.release(index)?;
+        Ok((slot.t, slot.priority))
+    }
+
+    /**
+    move all elements of another queue into this one,
+    which takes time proportional to the size of the other queue, since its indices are shifted
+
+    # Errors
+    will error if the combined queue would exceed capacity
+    */
+    pub fn meld(&mut self, other: Self) -> Result<(), Error> {
+        let node_count = self
+            .node_count
+            .checked_add(other.node_count)
+            .ok_or(Error::ReachedCapacity)?;
+        let offset = self.slots.append(other.slots, |slot, offset| {
+            slot.child = slot.child.map(|index| index + offset);
+            slot.sibling = slot.sibling.map(|index| index + offset);
+            slot.prev = slot.prev.map(|index| index + offset);
+        })?;
+        self.node_count = node_count;
+        if let Some(root) = other.root {
+            self.meld_root(root + offset)?;
+        }
+        Ok(())
+    }
+}
+
+/* # priority queue */
+
+impl<T, Priority> PriorityQueue<T, Priority> for PairingQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    fn push(&mut self, t: T, priority: Priority) -> Result<(), Error> {
+        Self::push(self, t, priority)
+    }
+
+    fn pop(&mut self) -> Result<(T, Priority), Error> {
+        Self::pop(self)
+    }
+
+    fn peek(&self) -> Option<(T, Priority)>
+    where
+        T: Clone,
+        Priority: Clone,
+    {
+        let slot = self.slots.get(self.root?).ok()?;
+        Some((slot.t.clone(), slot.priority.clone()))
+    }
+
+    fn decrease_priority(&mut self, value: &T, new_priority: Priority) -> Result<(), Error> {
+        Self::decrease_priority(self, value, new_priority)
+    }
+
+    fn delete(&mut self, value: &T) -> Result<(T, Priority), Error> {
+        Self::delete(self, value)
+    }
+
+    fn is_empty(&self) -> bool {
+        Self::is_empty(self)
+    }
+
+    fn len(&self) -> usize {
+        Self::len(self)
+    }
+
+    fn meld(&mut self, other: Self) -> Result<(), Error> {
+        Self::meld(self, other)
+    }
+}
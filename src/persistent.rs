@@ -0,0 +1,229 @@
+use crate::error::Error;
+use alloc::{rc::Rc, vec::Vec};
+
+/* # persistent queue */
+
+/// node of a leftist tree, shared between all versions of the queue which contain it
+struct PNode<T, Priority> {
+    /// held value
+    t: T,
+    /// priority of the held value
+    priority: Priority,
+    /// length of the rightmost path down to a missing child
+    rank: u32,
+    /// left child, whose rank is not lower than that of the right one
+    left: Option<Rc<PNode<T, Priority>>>,
+    /// right child
+    right: Option<Rc<PNode<T, Priority>>>,
+}
+
+type PRef<T, Priority> = Option<Rc<PNode<T, Priority>>>;
+
+fn rank<T, Priority>(tree: &PRef<T, Priority>) -> u32 {
+    tree.as_ref().map_or(0, |node| node.rank)
+}
+
+/// build a node over two subtrees, putting the one with the higher rank to the left
+fn join<T, Priority>(
+    t: T,
+    priority: Priority,
+    one: PRef<T, Priority>,
+    other: PRef<T, Priority>,
+) -> Rc<PNode<T, Priority>> {
+    let (left, right) = if rank(&one) < rank(&other) {
+        (other, one)
+    } else {
+        (one, other)
+    };
+    Rc::new(PNode {
+        t,
+        priority,
+        rank: rank(&right) + 1,
+        left,
+        right,
+    })
+}
+
+/// merge two trees, copying the nodes along their rightmost paths and sharing everything else
+fn merge<T, Priority>(one: &PRef<T, Priority>, other: &PRef<T, Priority>) -> PRef<T, Priority>
+where
+    T: Clone,
+    Priority: Ord + Clone,
+{
+    let (parent, child) = match (one, other) {
+        (None, tree) | (tree, None) => return tree.clone(),
+        (Some(one), Some(other)) if other.priority < one.priority => (other, one),
+        (Some(one), Some(other)) => (one, other),
+    };
+    // the rightmost paths are logarithmic, so the recursion is shallow
+    let right = merge(&parent.right, &Some(Rc::clone(child)));
+    Some(join(
+        parent.t.clone(),
+        parent.priority.clone(),
+        parent.left.clone(),
+        right,
+    ))
+}
+
+/**
+persistent queue, whose operations leave it untouched and return a new version instead
+
+versions share all the nodes they have in common, every operation copies only a logarithmic
+number of them, so a backtracking search can keep many snapshots of its frontier alive at once
+at little cost, and cloning a version is constant time
+
+values and priorities on the copied paths are cloned, so both should be cheap to clone,
+wrapping them in `Rc` otherwise
+
+```
+use fbheap::error::Error::Empty;
+use fbheap::persistent::PersistentQueue;
+
+let empty = PersistentQueue::new();
+let frontier = empty.push("i was first", 3)?.push("i am important", 1)?;
+let (popped, rest) = frontier.pop()?;
+assert_eq!(popped, ("i am important", 1));
+// the earlier version is still there to backtrack to
+assert_eq!(frontier.len(), 2);
+assert_eq!(frontier.peek(), Some((&"i am important", &1)));
+let melded = rest.meld(&frontier)?;
+assert_eq!(melded.len(), 3);
+assert_eq!(melded.pop()?.0, ("i am important", 1));
+assert_eq!(empty.pop().err(), Some(Empty));
+# Ok::<(), fbheap::error::Error>(())
+```
+*/
+pub struct PersistentQueue<T, Priority>
+where
+    Priority: Ord,
+{
+    /// root of the tree, which holds the lowest priority, if such exists
+    root: PRef<T, Priority>,
+    /// number of elements in this version
+    node_count: usize,
+}
+
+impl<T, Priority> Clone for PersistentQueue<T, Priority>
+where
+    Priority: Ord,
+{
+    fn clone(&self) -> Self {
+        Self {
+            root: self.root.clone(),
+            node_count: self.node_count,
+        }
+    }
+}
+
+impl<T, Priority> Default for PersistentQueue<T, Priority>
+where
+    Priority: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, Priority> Drop for PersistentQueue<T, Priority>
+where
+    Priority: Ord,
+{
+    /// release nodes no other version shares one at a time,
+    /// since left paths can be as long as the queue and would overflow the stack
+    fn drop(&mut self) {
+        let mut pending: Vec<_> = self.root.take().into_iter().collect();
+        while let Some(node) = pending.pop() {
+            if let Ok(mut node) = Rc::try_unwrap(node) {
+                pending.extend(node.left.take());
+                pending.extend(node.right.take());
+            }
+        }
+    }
+}
+
+impl<T, Priority> PersistentQueue<T, Priority>
+where
+    Priority: Ord,
+{
+    /// construct empty queue
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            root: None,
+            node_count: 0,
+        }
+    }
+
+    /// returns true if the queue is empty
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.node_count == 0
+    }
+
+    /// returns the number of elements in the queue
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.node_count
+    }
+
+    /// returns the element with the lowest priority, if there is any
+    #[must_use]
+    pub fn peek(&self) -> Option<(&T, &Priority)> {
+        self.root.as_ref().map(|node| (&node.t, &node.priority))
+    }
+}
+
+impl<T, Priority> PersistentQueue<T, Priority>
+where
+    T: Clone,
+    Priority: Ord + Clone,
+{
+    /**
+    returns a version of the queue with the value pushed with given priority
+
+    # Errors
+    ReachedCapacity => the queue is already at capacity
+    */
+    pub fn push(&self, t: T, priority: Priority) -> Result<Self, Error> {
+        let node_count = self
+            .node_count
+            .checked_add(1)
+            .ok_or(Error::ReachedCapacity)?;
+        Ok(Self {
+            root: merge(&self.root, &Some(join(t, priority, None, None))),
+            node_count,
+        })
+    }
+
+    /**
+    return the element with the lowest priority, together with the version of the queue without it
+
+    # Errors
+    Empty => cannot return element from empty queue
+    */
+    pub fn pop(&self) -> Result<((T, Priority), Self), Error> {
+        let root = self.root.as_ref().ok_or(Error::Empty)?;
+        let rest = Self {
+            root: merge(&root.left, &root.right),
+            node_count: self.node_count.checked_sub(1).ok_or(Error::Empty)?,
+        };
+        Ok(((root.t.clone(), root.priority.clone()), rest))
+    }
+
+    /**
+    returns a version of the queue holding the elements of both versions
+
+    # Errors
+    ReachedCapacity => the combined queue would exceed capacity
+    */
+    pub fn meld(&self, other: &Self) -> Result<Self, Error> {
+        let node_count = self
+            .node_count
+            .checked_add(other.node_count)
+            .ok_or(Error::ReachedCapacity)?;
+        Ok(Self {
+            root: merge(&self.root, &other.root),
+            node_count,
+        })
+    }
+}
@@ -0,0 +1,119 @@
+/// operations observed by a queue, as seen when deciding whether to consolidate
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Workload {
+    /// number of values pushed so far
+    pub pushes: usize,
+    /// number of values popped so far
+    pub pops: usize,
+    /// number of successful priority decreases so far
+    pub decreases: usize,
+    /// current number of roots
+    pub roots: usize,
+    /// current number of nodes
+    pub nodes: usize,
+}
+
+impl Workload {
+    /// construct empty workload
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            pushes: 0,
+            pops: 0,
+            decreases: 0,
+            roots: 0,
+            nodes: 0,
+        }
+    }
+
+    /// upper estimate of the rank of any tree, roughly log_2 of the node count
+    const fn rank_estimate(&self) -> usize {
+        (usize::BITS - self.nodes.leading_zeros()) as usize
+    }
+}
+
+/**
+strategy deciding whether `pop` should consolidate the list of roots
+
+consolidating eagerly keeps the number of roots logarithmic, which makes finding the new minimum
+cheap, while consolidating lazily leaves roots alone until they pile up, which saves linking work
+when pops are rare compared to pushes and decreases
+
+```
+use fbheap::policy::{Consolidation, Workload};
+
+let mut workload = Workload::new();
+workload.nodes = 1000;
+workload.roots = 20;
+assert!(Consolidation::Eager.should_consolidate(&workload));
+assert!(!Consolidation::Lazy(4).should_consolidate(&workload));
+
+// mostly pushes, so consolidation is deferred
+workload.pushes = 1000;
+workload.pops = 10;
+assert!(!Consolidation::Adaptive.should_consolidate(&workload));
+
+// mostly pops, so every pop consolidates
+workload.pops = 900;
+assert!(Consolidation::Adaptive.should_consolidate(&workload));
+
+let custom = Consolidation::Custom(|workload| workload.roots > 10);
+assert!(custom.should_consolidate(&workload));
+```
+*/
+#[derive(Clone, Copy, Debug, Default)]
+pub enum Consolidation {
+    /// consolidate on every pop
+    #[default]
+    Eager,
+    /// consolidate only once the number of roots exceeds the given multiple of the maximal rank
+    Lazy(usize),
+    /// choose between eager and lazy consolidation based on the observed mix of operations
+    Adaptive,
+    /// defer the decision to a user provided function
+    Custom(fn(&Workload) -> bool),
+}
+
+impl Consolidation {
+    /// factor used by the adaptive strategy while it behaves lazily
+    const ADAPTIVE_LAZY_FACTOR: usize = 4;
+
+    /// returns true if the queue should consolidate under the given workload
+    #[must_use]
+    pub fn should_consolidate(&self, workload: &Workload) -> bool {
+        match *self {
+            Self::Eager => true,
+            Self::Lazy(factor) => workload.roots > factor.saturating_mul(workload.rank_estimate()),
+            Self::Adaptive => {
+                // pops pay for consolidation, so as long as they make up a fair share
+                // of the operations it is cheapest to keep the roots tidy at all times
+                let others = workload.pushes.saturating_add(workload.decreases);
+                workload.pops.saturating_mul(2) >= others
+                    || Self::Lazy(Self::ADAPTIVE_LAZY_FACTOR).should_consolidate(workload)
+            }
+            Self::Custom(decide) => decide(workload),
+        }
+    }
+}
+
+/// strategy deciding which element a full bounded queue gives up when another one is pushed
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum EvictionPolicy {
+    /// keep the queued elements and hand the pushed one back
+    #[default]
+    RejectNew,
+    /// evict the element with the highest priority, which may be the pushed one
+    EvictLargest,
+    /// evict the element with the lowest priority, which may be the pushed one
+    EvictSmallest,
+}
+
+/// strategy deciding what a queue of unique values does when a queued value is pushed again
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DuplicatePolicy {
+    /// keep the queued element and refuse the pushed one
+    #[default]
+    Reject,
+    /// remove the queued element and push the new one in its place
+    Replace,
+}
@@ -0,0 +1,155 @@
+/**
+priority built from several components, compared lexicographically in the order they were added
+
+```
+use fbheap::heap::BareQueue;
+use fbheap::priority::Priority;
+
+let mut queue = BareQueue::new();
+// earlier deadline first, heavier weight breaks ties
+queue.push("late", Priority::by(20_u64).then(1));
+queue.push("light", Priority::by(10_u64).then(2));
+queue.push("heavy", Priority::by(10_u64).then(1));
+assert_eq!(queue.pop().map(|(task, _)| task), Ok("heavy"));
+assert_eq!(queue.pop().map(|(task, _)| task), Ok("light"));
+
+// waiting long enough makes up for a later deadline
+let mut queue = BareQueue::new();
+queue.push("old", Priority::by(30_u64).then(0).aged_by(2, 0));
+queue.push("new", Priority::by(20_u64).then(0).aged_by(2, 10));
+assert_eq!(queue.pop().map(|(task, _)| task), Ok("old"));
+```
+*/
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Priority<K>(K);
+
+impl<K> Priority<K> {
+    /// start a priority with its most significant component
+    #[must_use]
+    pub const fn by(key: K) -> Self {
+        Self(key)
+    }
+
+    /// add a less significant component, which only matters when all previous ones are equal
+    #[must_use]
+    pub fn then<L>(self, key: L) -> Priority<(K, L)> {
+        Priority((self.0, key))
+    }
+
+    /// take the components out of the priority
+    pub fn into_inner(self) -> K {
+        self.0
+    }
+}
+
+impl<K> Priority<K>
+where
+    K: Aging,
+{
+    /**
+    age the priority at the given rate, as of the tick at which it was enqueued
+
+    the most significant component is shifted by the rate times the tick,
+    so of two waiting items the older one catches up by the rate for every tick it has waited,
+    while the relative order of queued items never changes as time passes
+    */
+    #[must_use]
+    pub fn aged_by(mut self, rate: u64, enqueued_at: u64) -> Self {
+        let primary = self.0.primary();
+        *primary = primary.saturating_add(rate.saturating_mul(enqueued_at));
+        self
+    }
+}
+
+/// priority components whose most significant part is a number of ticks, which can be aged
+pub trait Aging {
+    /// the most significant component
+    fn primary(&mut self) -> &mut u64;
+}
+
+impl Aging for u64 {
+    fn primary(&mut self) -> &mut u64 {
+        self
+    }
+}
+
+impl<K, L> Aging for (K, L)
+where
+    K: Aging,
+{
+    fn primary(&mut self) -> &mut u64 {
+        self.0.primary()
+    }
+}
+
+/* # float priorities */
+
+/**
+floating point priority, totally ordered as by `total_cmp`
+
+negative numbers come before positive ones, with `-0.0` just before `0.0`,
+and nan is not rejected but sorted by its sign, so a positive nan comes after infinity
+and is handed out last, while a negative nan comes before negative infinity
+
+```
+use fbheap::heap::BareQueue;
+use fbheap::priority::TotalOrd;
+
+let mut queue = BareQueue::new();
+queue.push("far", TotalOrd(2.5));
+queue.push("unknown", TotalOrd(f64::NAN));
+queue.push("near", TotalOrd(0.75));
+queue.push("unreachable", TotalOrd(f64::INFINITY));
+let order: Vec<_> = queue.into_iter().map(|(place, _)| place).collect();
+assert_eq!(order, vec!["near", "far", "unreachable", "unknown"]);
+```
+*/
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TotalOrd<F>(pub F);
+
+impl<F> TotalOrd<F> {
+    /// take the number out of the priority
+    pub fn into_inner(self) -> F {
+        self.0
+    }
+}
+
+/// implement the total order and a hash consistent with it for a floating point type
+macro_rules! total_ord {
+    ($float:ty) => {
+        impl PartialEq for TotalOrd<$float> {
+            fn eq(&self, other: &Self) -> bool {
+                self.cmp(other).is_eq()
+            }
+        }
+
+        impl Eq for TotalOrd<$float> {}
+
+        impl PartialOrd for TotalOrd<$float> {
+            fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for TotalOrd<$float> {
+            fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+                self.0.total_cmp(&other.0)
+            }
+        }
+
+        impl core::hash::Hash for TotalOrd<$float> {
+            fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+                self.0.to_bits().hash(state);
+            }
+        }
+
+        impl From<$float> for TotalOrd<$float> {
+            fn from(number: $float) -> Self {
+                Self(number)
+            }
+        }
+    };
+}
+
+total_ord!(f32);
+total_ord!(f64);
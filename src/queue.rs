@@ -0,0 +1,80 @@
+use crate::error::Error;
+
+/**
+operations shared by all queue backends, so that code can be written once for any of them
+
+```
+use fbheap::binomial::BinomialQueue;
+use fbheap::heap::BareQueue;
+use fbheap::pairing::PairingQueue;
+use fbheap::queue::PriorityQueue;
+
+fn schedule(queue: &mut impl PriorityQueue<&'static str, u32>) -> Vec<&'static str> {
+    queue.push("write", 2).unwrap();
+    queue.push("read", 3).unwrap();
+    queue.push("wake", 1).unwrap();
+    queue.push("sleep", 4).unwrap();
+    queue.decrease_priority(&"read", 0).unwrap();
+    assert_eq!(queue.delete(&"sleep"), Ok(("sleep", 4)));
+    assert_eq!(queue.peek(), Some(("read", 0)));
+    core::iter::from_fn(|| queue.pop().ok()).map(|(task, _)| task).collect()
+}
+
+let order = vec!["read", "wake", "write"];
+assert_eq!(schedule(&mut BareQueue::new()), order);
+assert_eq!(schedule(&mut PairingQueue::new()), order);
+assert_eq!(schedule(&mut BinomialQueue::new()), order);
+```
+*/
+pub trait PriorityQueue<T, Priority> {
+    /** # Errors
+    will error if the queue is already at capacity
+    */
+    fn push(&mut self, t: T, priority: Priority) -> Result<(), Error>;
+
+    /** # Errors
+    Empty => cannot return element from empty queue
+    */
+    fn pop(&mut self) -> Result<(T, Priority), Error>;
+
+    /** # Errors
+    Empty => cannot return element from empty queue
+    */
+    fn pop_value(&mut self) -> Result<T, Error> {
+        self.pop().map(|(t, _)| t)
+    }
+
+    /** # Errors
+    Empty => cannot return element from empty queue
+    */
+    fn pop_priority(&mut self) -> Result<Priority, Error> {
+        self.pop().map(|(_, priority)| priority)
+    }
+
+    /// returns a copy of the element with the lowest priority, if there is any
+    fn peek(&self) -> Option<(T, Priority)>
+    where
+        T: Clone,
+        Priority: Clone;
+
+    /** # Errors
+    NotFound => index with given value was not found in the queue\n
+    CannotIncreasePriority => the given priority is not lower than the current one
+    */
+    fn decrease_priority(&mut self, value: &T, new_priority: Priority) -> Result<(), Error>;
+
+    /** # Errors
+    NotFound => index with given value was not found in the queue
+    */
+    fn delete(&mut self, value: &T) -> Result<(T, Priority), Error>;
+
+    fn is_empty(&self) -> bool;
+    fn len(&self) -> usize;
+
+    /** # Errors
+    will error if the combined queue would exceed capacity
+    */
+    fn meld(&mut self, other: Self) -> Result<(), Error>
+    where
+        Self: Sized;
+}
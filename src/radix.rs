@@ -0,0 +1,272 @@
+use crate::{error::Error, queue::PriorityQueue};
+use alloc::vec::Vec;
+use core::mem::take;
+
+/* # radix queue */
+
+/// number of buckets, one for every possible highest bit in which a priority differs from the last
+/// popped one, and one for priorities equal to it
+const BUCKETS: usize = u64::BITS as usize + 1;
+
+/**
+radix queue for integer priorities which never drop below the last popped one,
+such as distances in dijkstra's algorithm
+
+elements are kept in buckets by the highest bit in which their priority differs from that of the
+last popped element, and a bucket is only ever split into lower ones, so every element moves
+at most once per bit and operations take amortized time logarithmic in the range of priorities,
+not in the number of elements
+
+```
+use fbheap::error::Error::{NonMonotonePush, Empty};
+use fbheap::radix::RadixQueue;
+
+let mut queue = RadixQueue::new();
+queue.push("i was first", 3);
+queue.push("i am important", 1);
+queue.push("i was not important at first", 4);
+assert_eq!(queue.pop(), Ok(("i am important", 1)));
+queue.decrease_priority(&"i was not important at first", 2);
+assert_eq!(queue.pop(), Ok(("i was not important at first", 2)));
+// priorities cannot go back below the last popped one
+assert_eq!(queue.push("i am too late", 1), Err(NonMonotonePush));
+assert_eq!(queue.pop(), Ok(("i was first", 3)));
+assert!(queue.is_empty());
+assert_eq!(queue.pop(), Err(Empty));
+```
+*/
+pub struct RadixQueue<T>
+where
+    T: Eq,
+{
+    /// elements by the highest bit in which their priority differs from the last popped one
+    buckets: [Vec<(T, u64)>; BUCKETS],
+    /// priority of the last popped element, which no queued priority may be lower than
+    last: u64,
+    /// number of elements in the queue
+    node_count: usize,
+}
+
+impl<T> Default for RadixQueue<T>
+where
+    T: Eq,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> RadixQueue<T>
+where
+    T: Eq,
+{
+    /* # helper functions */
+
+    /// bucket holding the given priority
+    const fn bucket(&self, priority: u64) -> usize {
+        (u64::BITS - (priority ^ self.last).leading_zeros()) as usize
+    }
+
+    /// bucket and position within it of the given value
+    fn get_node(&self, t: &T) -> Option<(usize, usize)> {
+        self.buckets
+            .iter()
+            .enumerate()
+            .find_map(|(bucket, elements)| {
+                elements
+                    .iter()
+                    .position(|(value, _)| value == t)
+                    .map(|position| (bucket, position))
+            })
+    }
+
+    /// split the lowest non empty bucket, so that the first bucket holds the lowest priority
+    fn redistribute(&mut self) {
+        if !self.buckets[0].is_empty() {
+            return;
+        }
+        let Some(bucket) = self
+            .buckets
+            .iter()
+            .position(|elements| !elements.is_empty())
+        else {
+            return;
+        };
+        let elements = take(&mut self.buckets[bucket]);
+        if let Some(lowest) = elements.iter().map(|&(_, priority)| priority).min() {
+            self.last = lowest;
+        }
+        for (t, priority) in elements {
+            let bucket = self.bucket(priority);
+            self.buckets[bucket].push((t, priority));
+        }
+    }
+
+    /* # heap functionality */
+
+    /// construct empty queue
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            buckets: [const { Vec::new() }; BUCKETS],
+            last: 0,
+            node_count: 0,
+        }
+    }
+
+    /// returns true if the queue is empty
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.node_count == 0
+    }
+
+    /// returns the number of elements in the queue
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.node_count
+    }
+
+    /**
+    push a value onto the queue with given priority
+
+    # Errors
+    NonMonotonePush => the priority is lower than that of the last popped element\n
+    ReachedCapacity => the queue is already at capacity
+    */
+    pub fn push(&mut self, t: T, priority: u64) -> Result<(), Error> {
+        if priority < self.last {
+            return Err(Error::NonMonotonePush);
+        }
+        self.node_count = self
+            .node_count
+            .checked_add(1)
+            .ok_or(Error::ReachedCapacity)?;
+        let bucket = self.bucket(priority);
+        self.buckets[bucket].push((t, priority));
+        Ok(())
+    }
+
+    /**
+    return the element with the lowest priority
+
+    # Errors
+    Empty => cannot return element from empty queue
+    */
+    pub fn pop(&mut self) -> Result<(T, u64), Error> {
+        self.redistribute();
+        let element = self.buckets[0].pop().ok_or(Error::Empty)?;
+        self.node_count -= 1;
+        Ok(element)
+    }
+
+    /**
+    decreases the priority of the item with given value
+
+    # Errors
+    NotFound => index with given value was not found in the queue\n
+    CannotIncreasePriority => the give prioprity is higher than the current one for the index of that value\n
+    NonMonotonePush => the priority is lower than that of the last popped element
+    */
+    pub fn decrease_priority(&mut self, value: &T, new_priority: u64) -> Result<(), Error> {
+        let (bucket, position) = self.get_node(value).ok_or(Error::NotFound("value"))?;
+        if self.buckets[bucket][position].1 <= new_priority {
+            return Err(Error::CannotIncreasePriority {
+                current_was_lower: self.buckets[bucket][position].1 < new_priority,
+            });
+        }
+        if new_priority < self.last {
+            return Err(Error::NonMonotonePush);
+        }
+        let (t, _) = self.buckets[bucket].swap_remove(position);
+        let bucket = self.bucket(new_priority);
+        self.buckets[bucket].push((t, new_priority));
+        Ok(())
+    }
+
+    /**
+    remove the item with given value from the queue, regardless of its priority
+
+    # Errors
+    NotFound => index with given value was not found in the queue
+    */
+    pub fn delete(&mut self, value: &T) -> Result<(T, u64), Error> {
+        let (bucket, position) = self.get_node(value).ok_or(Error::NotFound("value"))?;
+        self.node_count -= 1;
+        Ok(self.buckets[bucket].swap_remove(position))
+    }
+
+    /**
+    move all elements of another queue into this one,
+    which takes time proportional to the size of the other queue
+
+    # Errors
+    NonMonotonePush => the other queue holds a priority lower than that of the last popped element,
+    in which case this queue is left unchanged and the other one is dropped\n
+    ReachedCapacity => the combined queue would exceed capacity
+    */
+    pub fn meld(&mut self, other: Self) -> Result<(), Error> {
+        if other
+            .buckets
+            .iter()
+            .flatten()
+            .any(|&(_, priority)| priority < self.last)
+        {
+            return Err(Error::NonMonotonePush);
+        }
+        self.node_count = self
+            .node_count
+            .checked_add(other.node_count)
+            .ok_or(Error::ReachedCapacity)?;
+        for (t, priority) in other.buckets.into_iter().flatten() {
+            let bucket = self.bucket(priority);
+            self.buckets[bucket].push((t, priority));
+        }
+        Ok(())
+    }
+}
+
+/* # priority queue */
+
+impl<T> PriorityQueue<T, u64> for RadixQueue<T>
+where
+    T: Eq,
+{
+    fn push(&mut self, t: T, priority: u64) -> Result<(), Error> {
+        Self::push(self, t, priority)
+    }
+
+    fn pop(&mut self) -> Result<(T, u64), Error> {
+        Self::pop(self)
+    }
+
+    fn peek(&self) -> Option<(T, u64)>
+    where
+        T: Clone,
+    {
+        let bucket = self.buckets.iter().find(|elements| !elements.is_empty())?;
+        bucket
+            .iter()
+            .min_by_key(|&&(_, priority)| priority)
+            .cloned()
+    }
+
+    fn decrease_priority(&mut self, value: &T, new_priority: u64) -> Result<(), Error> {
+        Self::decrease_priority(self, value, new_priority)
+    }
+
+    fn delete(&mut self, value: &T) -> Result<(T, u64), Error> {
+        Self::delete(self, value)
+    }
+
+    fn is_empty(&self) -> bool {
+        Self::is_empty(self)
+    }
+
+    fn len(&self) -> usize {
+        Self::len(self)
+    }
+
+    fn meld(&mut self, other: Self) -> Result<(), Error> {
+        Self::meld(self, other)
+    }
+}
@@ -0,0 +1,541 @@
+use crate::{error::Error, heap::max_rank, queue::PriorityQueue};
+use alloc::{boxed::Box, vec::Vec};
+use core::{
+    marker::PhantomData,
+    mem::{swap, take},
+    ptr::NonNull,
+};
+
+/* # raw queue */
+
+/*
+# safety
+
+the queue upholds the following invariants, which every unsafe block below relies on:
+- every node is allocated by `allocate` and owned by exactly one queue, which frees it exactly once,
+  either when handing out its element or when the queue is dropped,
+  so every pointer reachable from a queue points to a live node
+- siblings form a circular list through `left` and `right`, a node alone in its list points to itself
+- `parent` is set for exactly those nodes which are not roots,
+  `child` points into the list of children, and `rank` is the length of that list
+- `first` points into the list of roots and holds the lowest priority among them
+- pointers never leave the queue, and nodes are only accessed while the queue is borrowed,
+  through places of the pointers themselves, so no reference to a node outlives a single expression
+*/
+
+/// pointer to a node owned by a `RawQueue`
+type Link<T, Priority> = NonNull<RNode<T, Priority>>;
+
+/// container for data with priority in the tree structure of the queue, linked by raw pointers
+struct RNode<T, Priority> {
+    /// held value
+    t: T,
+    /// priority of the held value
+    priority: Priority,
+    /// parent node in the tree structure
+    parent: Option<Link<T, Priority>>,
+    /// any of the children in the tree structure
+    child: Option<Link<T, Priority>>,
+    /// previous sibling in the circular list
+    left: Link<T, Priority>,
+    /// next sibling in the circular list
+    right: Link<T, Priority>,
+    /// number of children
+    rank: usize,
+    /// flag for whether this node has lost any children already
+    marked: bool,
+}
+
+/// allocate a node, alone in a list of its own
+fn allocate<T, Priority>(t: T, priority: Priority) -> Link<T, Priority> {
+    let node = NonNull::from(Box::leak(Box::new(RNode {
+        t,
+        priority,
+        parent: None,
+        child: None,
+        left: NonNull::dangling(),
+        right: NonNull::dangling(),
+        rank: 0,
+        marked: false,
+    })));
+    // SAFETY: the node was just allocated, and nothing else points to it yet
+    unsafe {
+        (*node.as_ptr()).left = node;
+        (*node.as_ptr()).right = node;
+    }
+    node
+}
+
+/**
+join the circular lists holding the two nodes into one
+
+# Safety
+both nodes are live and in different lists
+*/
+unsafe fn splice<T, Priority>(a: Link<T, Priority>, b: Link<T, Priority>) {
+    let a_right = (*a.as_ptr()).right;
+    let b_left = (*b.as_ptr()).left;
+    (*a.as_ptr()).right = b;
+    (*b.as_ptr()).left = a;
+    (*b_left.as_ptr()).right = a_right;
+    (*a_right.as_ptr()).left = b_left;
+}
+
+/**
+take a node out of its circular list, leaving it alone in a list of its own
+
+# Safety
+the node and its siblings are live
+*/
+unsafe fn unlink<T, Priority>(node: Link<T, Priority>) {
+    let left = (*node.as_ptr()).left;
+    let right = (*node.as_ptr()).right;
+    (*left.as_ptr()).right = right;
+    (*right.as_ptr()).left = left;
+    (*node.as_ptr()).left = node;
+    (*node.as_ptr()).right = node;
+}
+
+/**
+iterate over the circular list holding the given node, starting with it
+
+# Safety
+the nodes of the list stay live and linked as they are until the iterator is dropped
+*/
+unsafe fn siblings<T, Priority>(
+    start: Link<T, Priority>,
+) -> impl Iterator<Item = Link<T, Priority>> {
+    core::iter::successors(Some(start), move |&node| {
+        // SAFETY: the caller keeps the list alive and unchanged
+        let right = unsafe { (*node.as_ptr()).right };
+        (right != start).then_some(right)
+    })
+}
+
+/**
+make a root the child of another root
+
+# Safety
+both nodes are live roots
+*/
+unsafe fn link<T, Priority>(parent: Link<T, Priority>, child: Link<T, Priority>) {
+    unlink(child);
+    (*child.as_ptr()).parent = Some(parent);
+    (*child.as_ptr()).marked = false;
+    match (*parent.as_ptr()).child {
+        Some(sibling) => splice(sibling, child),
+        None => (*parent.as_ptr()).child = Some(child),
+    }
+    (*parent.as_ptr()).rank += 1;
+}
+
+/**
+fibonacci queue whose nodes are linked by raw pointers instead of `Rc<RefCell>`,
+which saves reference counting and dynamic borrow checks on every step through the trees
+
+the unsafe code relies on the invariants listed at the top of its module,
+and its tests are run under miri to catch violations
+
+```
+use fbheap::error::Error::Empty;
+use fbheap::raw::RawQueue;
+
+let mut queue = RawQueue::new();
+queue.push("i was first", 3);
+queue.push("i am important", 1);
+queue.push("i was not important at first", 4);
+assert_eq!(queue.pop(), Ok(("i am important", 1)));
+queue.decrease_priority(&"i was not important at first", 2);
+assert_eq!(queue.pop(), Ok(("i was not important at first", 2)));
+assert_eq!(queue.pop(), Ok(("i was first", 3)));
+assert!(queue.is_empty());
+assert_eq!(queue.pop(), Err(Empty));
+```
+*/
+pub struct RawQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /// root with the lowest priority, through which the list of roots is reached, it such exists
+    first: Option<Link<T, Priority>>,
+    /// number of nodes in the queue
+    node_count: usize,
+    /// roots by rank while consolidating, kept empty in between so that its allocation is reused
+    ranks: Vec<Option<Link<T, Priority>>>,
+    /// the queue owns its nodes
+    marker: PhantomData<Box<RNode<T, Priority>>>,
+}
+
+impl<T, Priority> Default for RawQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, Priority> RawQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /* # helper functions */
+
+    /**
+    add a list of detached nodes to the roots, making the first of them first if its priority is lower
+
+    # Safety
+    the nodes are live and not in the list of roots
+    */
+    unsafe fn insert_roots(&mut self, node: Link<T, Priority>) {
+        match self.first {
+            Some(first) => {
+                splice(first, node);
+                if (*node.as_ptr()).priority < (*first.as_ptr()).priority {
+                    self.first = Some(node);
+                }
+            }
+            None => self.first = Some(node),
+        }
+    }
+
+    /**
+    link roots of equal rank until all ranks differ, and find the root with the lowest priority
+
+    # Safety
+    the invariants of the queue hold, except that the first node may be any root
+    */
+    unsafe fn consolidate(&mut self) {
+        let Some(start) = self.first else {
+            return;
+        };
+        let mut ranks = take(&mut self.ranks);
+        ranks.resize_with(max_rank(self.node_count), || None);
+
+        // the roots are counted first, since linking takes them out of the list being walked,
+        // but never the ones not yet visited, so the next root is read before each is linked
+        let count = siblings(start).count();
+        let mut next = start;
+        for _ in 0..count {
+            let mut root = next;
+            next = (*root.as_ptr()).right;
+            let mut rank = (*root.as_ptr()).rank;
+            // indexing is safe, since structural guarantees
+            while let Some(mut other) = ranks[rank].take() {
+                // keep the smaller of the two as the root of the linked tree
+                if (*other.as_ptr()).priority < (*root.as_ptr()).priority {
+                    swap(&mut root, &mut other);
+                }
+                link(root, other);
+                rank = (*root.as_ptr()).rank;
+            }
+            ranks[rank] = Some(root);
+        }
+
+        self.first = None;
+        for root in ranks.drain(..).flatten() {
+            match self.first {
+                Some(first) if (*first.as_ptr()).priority <= (*root.as_ptr()).priority => {}
+                _ => self.first = Some(root),
+            }
+        }
+        self.ranks = ranks;
+    }
+
+    /**
+    separate node from its parent and add it to the list of roots,
+    continuing with every ancestor which has already lost a child
+
+    # Safety
+    the node is live and not a root
+    */
+    unsafe fn cut_node(&mut self, node: Link<T, Priority>) {
+        let mut node = node;
+        while let Some(parent) = (*node.as_ptr()).parent {
+            if (*parent.as_ptr()).child == Some(node) {
+                let right = (*node.as_ptr()).right;
+                (*parent.as_ptr()).child = (right != node).then_some(right);
+            }
+            unlink(node);
+            (*parent.as_ptr()).rank -= 1;
+            (*node.as_ptr()).parent = None;
+            (*node.as_ptr()).marked = false;
+            self.insert_roots(node);
+            if (*parent.as_ptr()).parent.is_none() {
+                break;
+            }
+            if !(*parent.as_ptr()).marked {
+                (*parent.as_ptr()).marked = true;
+                break;
+            }
+            node = parent;
+        }
+    }
+
+    /**
+    take a root out of the list of roots, handing its children over to that list, and free it
+
+    # Safety
+    the node is a live root, which the node count no longer accounts for
+    */
+    unsafe fn remove_root(&mut self, root: Link<T, Priority>) -> (T, Priority) {
+        if let Some(child) = (*root.as_ptr()).child.take() {
+            for node in siblings(child) {
+                (*node.as_ptr()).parent = None;
+                (*node.as_ptr()).marked = false;
+            }
+            splice(root, child);
+        }
+        let next = (*root.as_ptr()).right;
+        unlink(root);
+        self.first = (next != root).then_some(next);
+        self.consolidate();
+
+        let RNode { t, priority, .. } = *Box::from_raw(root.as_ptr());
+        (t, priority)
+    }
+
+    /// find the node holding the given value
+    fn get_node(&self, t: &T) -> Option<Link<T, Priority>> {
+        let mut lists: Vec<_> = self.first.into_iter().collect();
+        while let Some(start) = lists.pop() {
+            // SAFETY: the queue is borrowed, so its nodes stay live and linked as they are
+            for node in unsafe { siblings(start) } {
+                // SAFETY: as above
+                unsafe {
+                    if (*node.as_ptr()).t == *t {
+                        return Some(node);
+                    }
+                    lists.extend((*node.as_ptr()).child);
+                }
+            }
+        }
+        None
+    }
+
+    /* # heap functionality */
+
+    /// construct empty queue
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            first: None,
+            node_count: 0,
+            ranks: Vec::new(),
+            marker: PhantomData,
+        }
+    }
+
+    /// returns true if the queue is empty
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.first.is_none()
+    }
+
+    /// returns the number of elements in the queue
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.node_count
+    }
+
+    /// returns true if the queue holds the given value
+    #[must_use]
+    pub fn contains(&self, value: &T) -> bool {
+        self.get_node(value).is_some()
+    }
+
+    /**
+    push a value onto the queue with given priority
+
+    # Errors
+    will error if the queue is already at capacity
+    */
+    pub fn push(&mut self, t: T, priority: Priority) -> Result<(), Error> {
+        self.node_count = self
+            .node_count
+            .checked_add(1)
+            .ok_or(Error::ReachedCapacity)?;
+        // SAFETY: the node was just allocated, so it is not a root yet
+        unsafe { self.insert_roots(allocate(t, priority)) };
+        Ok(())
+    }
+
+    /**
+    return the element with the lowest priority
+
+    # Errors
+    Empty => cannot return element from empty queue
+    */
+    pub fn pop(&mut self) -> Result<(T, Priority), Error> {
+        let first = self.first.ok_or(Error::Empty)?;
+        self.node_count = self.node_count.checked_sub(1).ok_or(Error::Empty)?;
+        // SAFETY: the first node is a live root
+        Ok(unsafe { self.remove_root(first) })
+    }
+
+    /// returns a copy of the element with the lowest priority, if there is any
+    #[must_use]
+    pub fn peek(&self) -> Option<(T, Priority)>
+    where
+        T: Clone,
+        Priority: Clone,
+    {
+        // SAFETY: the first node is live while the queue is borrowed
+        self.first.map(|first| unsafe {
+            (
+                (*first.as_ptr()).t.clone(),
+                (*first.as_ptr()).priority.clone(),
+            )
+        })
+    }
+
+    /**
+    decreases the priority of the item with given value
+
+    # Errors
+    NotFound => index with given value was not found in the queue\n
+    CannotIncreasePriority => the give prioprity is higher than the current one for the index of that value
+    */
+    pub fn decrease_priority(&mut self, value: &T, new_priority: Priority) -> Result<(), Error> {
+        let node = self.get_node(value).ok_or(Error::NotFound("value"))?;
+        // SAFETY: the node was just found in the queue, so it is live
+        unsafe {
+            {
+                let current = &(*node.as_ptr()).priority;
+                if *current <= new_priority {
+                    return Err(Error::CannotIncreasePriority {
+                        current_was_lower: *current < new_priority,
+                    });
+                }
+            }
+            (*node.as_ptr()).priority = new_priority;
+            match (*node.as_ptr()).parent {
+                Some(parent) if (*node.as_ptr()).priority < (*parent.as_ptr()).priority => {
+                    self.cut_node(node);
+                }
+                Some(_) => {}
+                None => {
+                    if self
+                        .first
+                        .is_some_and(|first| (*node.as_ptr()).priority < (*first.as_ptr()).priority)
+                    {
+                        self.first = Some(node);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /**
+    remove the item with given value from the queue, regardless of its priority
+
+    # Errors
+    NotFound => index with given value was not found in the queue
+    */
+    pub fn delete(&mut self, value: &T) -> Result<(T, Priority), Error> {
+        let node = self.get_node(value).ok_or(Error::NotFound("value"))?;
+        self.node_count = self.node_count.checked_sub(1).ok_or(Error::Empty)?;
+        // SAFETY: the node was just found in the queue, so it is live, and cutting it makes it a root
+        unsafe {
+            if (*node.as_ptr()).parent.is_some() {
+                self.cut_node(node);
+            }
+            Ok(self.remove_root(node))
+        }
+    }
+
+    /**
+    move all elements of another queue into this one
+
+    # Errors
+    will error if the combined queue would exceed capacity
+    */
+    pub fn meld(&mut self, mut other: Self) -> Result<(), Error> {
+        self.node_count = self
+            .node_count
+            .checked_add(other.node_count)
+            .ok_or(Error::ReachedCapacity)?;
+        if let Some(other_first) = other.first.take() {
+            other.node_count = 0;
+            // SAFETY: the nodes were taken over from the other queue, which no longer reaches them
+            unsafe { self.insert_roots(other_first) };
+        }
+        Ok(())
+    }
+}
+
+impl<T, Priority> Drop for RawQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /// free the nodes one list at a time, reading the links of each node before freeing it
+    fn drop(&mut self) {
+        let mut lists: Vec<_> = self.first.take().into_iter().collect();
+        while let Some(start) = lists.pop() {
+            let mut node = start;
+            loop {
+                // SAFETY: every node is reached exactly once, through the list holding it,
+                // and its links are read before it is freed
+                let next = unsafe {
+                    let next = (*node.as_ptr()).right;
+                    lists.extend((*node.as_ptr()).child);
+                    drop(Box::from_raw(node.as_ptr()));
+                    next
+                };
+                if next == start {
+                    break;
+                }
+                node = next;
+            }
+        }
+    }
+}
+
+/* # priority queue */
+
+impl<T, Priority> PriorityQueue<T, Priority> for RawQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    fn push(&mut self, t: T, priority: Priority) -> Result<(), Error> {
+        Self::push(self, t, priority)
+    }
+
+    fn pop(&mut self) -> Result<(T, Priority), Error> {
+        Self::pop(self)
+    }
+
+    fn peek(&self) -> Option<(T, Priority)>
+    where
+        T: Clone,
+        Priority: Clone,
+    {
+        Self::peek(self)
+    }
+
+    fn decrease_priority(&mut self, value: &T, new_priority: Priority) -> Result<(), Error> {
+        Self::decrease_priority(self, value, new_priority)
+    }
+
+    fn delete(&mut self, value: &T) -> Result<(T, Priority), Error> {
+        Self::delete(self, value)
+    }
+
+    fn is_empty(&self) -> bool {
+        Self::is_empty(self)
+    }
+
+    fn len(&self) -> usize {
+        Self::len(self)
+    }
+
+    fn meld(&mut self, other: Self) -> Result<(), Error> {
+        Self::meld(self, other)
+    }
+}
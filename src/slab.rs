@@ -0,0 +1,141 @@
+use crate::error::Error;
+use alloc::vec::Vec;
+
+/* # slab */
+
+/// node held in a slab, which orders the nodes by their priority
+pub(crate) trait SlabNode {
+    type Priority: Ord;
+
+    fn priority(&self) -> &Self::Priority;
+}
+
+/**
+storage for the nodes of the index linked queues, kept in a single vector,
+where the slots left vacant by released nodes are reused by later ones
+*/
+pub(crate) struct Slab<N> {
+    /// storage for nodes, vacant slots are reused by later allocations
+    slots: Vec<Option<N>>,
+    /// indices of vacant slots
+    vacant: Vec<u32>,
+}
+
+impl<N> Slab<N> {
+    /// construct empty slab
+    pub(crate) const fn new() -> Self {
+        Self {
+            slots: Vec::new(),
+            vacant: Vec::new(),
+        }
+    }
+
+    /// construct slab from its slots, none of which are reused until vacated
+    #[cfg(feature = "serde")]
+    pub(crate) const fn from_slots(slots: Vec<Option<N>>) -> Self {
+        Self {
+            slots,
+            vacant: Vec::new(),
+        }
+    }
+
+    /// all slots, vacant or not
+    #[cfg(feature = "serde")]
+    pub(crate) fn as_slice(&self) -> &[Option<N>] {
+        &self.slots
+    }
+
+    /// number of slots, vacant or not
+    #[cfg(feature = "serde")]
+    pub(crate) fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub(crate) fn get(&self, index: u32) -> Result<&N, Error> {
+        self.slots
+            .get(index as usize)
+            .and_then(Option::as_ref)
+            .ok_or(Error::NotFound("node"))
+    }
+
+    pub(crate) fn get_mut(&mut self, index: u32) -> Result<&mut N, Error> {
+        self.slots
+            .get_mut(index as usize)
+            .and_then(Option::as_mut)
+            .ok_or(Error::NotFound("node"))
+    }
+
+    /// index of the first node satisfying the predicate
+    pub(crate) fn position(&self, mut predicate: impl FnMut(&N) -> bool) -> Option<u32> {
+        self.slots
+            .iter()
+            .position(|slot| slot.as_ref().is_some_and(&mut predicate))
+            .and_then(|index| u32::try_from(index).ok())
+    }
+
+    /// store a node in a vacant slot, or in a new one at the end if there is none
+    pub(crate) fn allocate(&mut self, node: N) -> Result<u32, Error> {
+        if let Some(index) = self.vacant.pop() {
+            self.slots[index as usize] = Some(node);
+            Ok(index)
+        } else {
+            let index = u32::try_from(self.slots.len()).map_err(|_| Error::ReachedCapacity)?;
+            self.slots.push(Some(node));
+            Ok(index)
+        }
+    }
+
+    /// take a node out of its slot, which is left empty but not reused until vacated
+    pub(crate) fn take(&mut self, index: u32) -> Result<N, Error> {
+        self.slots
+            .get_mut(index as usize)
+            .and_then(Option::take)
+            .ok_or(Error::NotFound("node"))
+    }
+
+    /// let later allocations reuse an empty slot
+    pub(crate) fn vacate(&mut self, index: u32) {
+        self.vacant.push(index);
+    }
+
+    /// take a node out of its slot, which is reused by later allocations
+    pub(crate) fn release(&mut self, index: u32) -> Result<N, Error> {
+        let node = self.take(index)?;
+        self.vacate(index);
+        Ok(node)
+    }
+
+    /**
+    move all slots of another slab behind the ones of this one,
+    letting the given function shift the links of every moved node by the returned offset
+
+    # Errors
+    will error if the combined slab would exceed capacity, in which case this one is left unchanged
+    */
+    pub(crate) fn append(
+        &mut self,
+        other: Self,
+        mut relink: impl FnMut(&mut N, u32),
+    ) -> Result<u32, Error> {
+        let offset = u32::try_from(self.slots.len()).map_err(|_| Error::ReachedCapacity)?;
+        u32::try_from(self.slots.len() + other.slots.len()).map_err(|_| Error::ReachedCapacity)?;
+        self.slots.extend(other.slots.into_iter().map(|slot| {
+            slot.map(|mut node| {
+                relink(&mut node, offset);
+                node
+            })
+        }));
+        self.vacant
+            .extend(other.vacant.into_iter().map(|index| index + offset));
+        Ok(offset)
+    }
+}
+
+impl<N> Slab<N>
+where
+    N: SlabNode,
+{
+    pub(crate) fn is_lower(&self, index: u32, other: u32) -> Result<bool, Error> {
+        Ok(self.get(index)?.priority() < self.get(other)?.priority())
+    }
+}
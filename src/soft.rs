@@ -0,0 +1,252 @@
+use crate::error::Error;
+use alloc::{boxed::Box, vec::Vec};
+use core::mem::swap;
+
+/* # soft queue */
+
+/// node of a tree in the queue, holding a list of elements which all share its corrupted priority
+struct SNode<T, Priority> {
+    /// held elements, with their original priorities
+    items: Vec<(T, Priority)>,
+    /// corrupted priority, not lower than any priority held by this node
+    ckey: Priority,
+    /// rank of the tree rooted at this node
+    rank: u32,
+    /// number of elements this node aims to hold
+    size: usize,
+    /// children in the tree structure, with corrupted priorities not lower than this one
+    left: Option<Box<SNode<T, Priority>>>,
+    right: Option<Box<SNode<T, Priority>>>,
+}
+
+impl<T, Priority> SNode<T, Priority>
+where
+    Priority: Ord + Clone,
+{
+    /// create a node holding a single element
+    fn new(t: T, priority: Priority) -> Self {
+        Self {
+            ckey: priority.clone(),
+            items: alloc::vec![(t, priority)],
+            rank: 0,
+            size: 1,
+            left: None,
+            right: None,
+        }
+    }
+
+    const fn is_leaf(&self) -> bool {
+        self.left.is_none() && self.right.is_none()
+    }
+
+    /// refill the list of elements from the children until it holds enough of them,
+    /// taking the whole list of the child with the lower corrupted priority at every step
+    fn sift(&mut self) {
+        while self.items.len() < self.size && !self.is_leaf() {
+            if self.left.as_ref().is_none_or(|left| {
+                self.right
+                    .as_ref()
+                    .is_some_and(|right| left.ckey > right.ckey)
+            }) {
+                swap(&mut self.left, &mut self.right);
+            }
+            let Some(left) = self.left.as_mut() else {
+                break;
+            };
+            self.items.append(&mut left.items);
+            self.ckey = left.ckey.clone();
+            if left.is_leaf() {
+                self.left = None;
+            } else {
+                left.sift();
+            }
+        }
+    }
+
+    /// link two trees of equal rank below a new root, which takes elements from them
+    fn combine(left: Box<Self>, right: Box<Self>, threshold: u32) -> Box<Self> {
+        let rank = left.rank + 1;
+        // above the threshold nodes hold growing lists, which is where priorities get corrupted
+        let size = if rank <= threshold {
+            1
+        } else {
+            left.size.saturating_mul(3).saturating_add(1) / 2
+        };
+        let mut node = Box::new(Self {
+            items: Vec::new(),
+            ckey: left.ckey.clone(),
+            rank,
+            size,
+            left: Some(left),
+            right: Some(right),
+        });
+        node.sift();
+        node
+    }
+}
+
+/**
+soft heap, which trades exactness for speed by corrupting priorities,
+raising those of some elements so that they are handed out together with others
+
+at any time at most one in every `one_in` pushed elements is corrupted,
+and every element is handed out no later than its corrupted priority allows,
+which makes pushing constant time and popping amortized logarithmic in `one_in` only,
+enough for selection and minimum spanning trees in linear time
+
+elements are kept in binary trees whose nodes pool lists of elements under a shared corrupted
+priority, after the simplified soft heap of kaplan and zwick
+
+```
+use fbheap::soft::SoftQueue;
+
+let mut queue = SoftQueue::new(8);
+for x in 0..1000_u32 {
+    queue.push(x, (x * 7919) % 1000)?;
+}
+// at most one in eight elements is corrupted
+assert!(queue.corrupted() <= 1000 / 8);
+let mut popped: Vec<_> = core::iter::from_fn(|| queue.pop().ok()).collect();
+popped.sort();
+assert_eq!(popped, (0..1000).map(|x| (x, (x * 7919) % 1000)).collect::<Vec<_>>());
+
+// with a low enough error rate nothing is corrupted, so elements come out in order
+let mut exact = SoftQueue::new(1 << 20);
+for x in [3, 1, 4, 1, 5, 9, 2, 6] {
+    exact.push(x, x)?;
+}
+assert_eq!(exact.corrupted(), 0);
+let sorted: Vec<_> = core::iter::from_fn(|| exact.pop().ok()).map(|(x, _)| x).collect();
+assert_eq!(sorted, vec![1, 1, 2, 3, 4, 5, 6, 9]);
+# Ok::<(), fbheap::error::Error>(())
+```
+*/
+pub struct SoftQueue<T, Priority>
+where
+    Priority: Ord,
+{
+    /// trees by rank, at most one of each
+    roots: Vec<Option<Box<SNode<T, Priority>>>>,
+    /// rank above which nodes pool elements
+    threshold: u32,
+    /// number of elements in the queue
+    node_count: usize,
+}
+
+impl<T, Priority> SoftQueue<T, Priority>
+where
+    Priority: Ord + Clone,
+{
+    /* # helper functions */
+
+    /// rank of the tree whose root has the lowest corrupted priority
+    fn min_rank(&self) -> Option<usize> {
+        let mut min: Option<(usize, &Priority)> = None;
+        for (rank, root) in self.roots.iter().enumerate() {
+            if let Some(root) = root {
+                if min.is_none_or(|(_, ckey)| root.ckey < *ckey) {
+                    min = Some((rank, &root.ckey));
+                }
+            }
+        }
+        min.map(|(rank, _)| rank)
+    }
+
+    /* # heap functionality */
+
+    /**
+    construct empty queue, corrupting at most one in every `one_in` pushed elements,
+    where zero is taken as one
+    */
+    #[must_use]
+    pub fn new(one_in: u64) -> Self {
+        // nodes pool elements only above rank log_2(3 / ε), which bounds the corrupted ones by εn
+        let scaled = one_in.max(1).saturating_mul(3);
+        Self {
+            roots: Vec::new(),
+            threshold: u64::BITS - (scaled - 1).leading_zeros(),
+            node_count: 0,
+        }
+    }
+
+    /// returns true if the queue is empty
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.node_count == 0
+    }
+
+    /// returns the number of elements in the queue
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.node_count
+    }
+
+    /// returns the number of elements whose priority is currently corrupted,
+    /// counted by walking through the whole queue
+    #[must_use]
+    pub fn corrupted(&self) -> usize {
+        let mut nodes: Vec<_> = self.roots.iter().flatten().collect();
+        let mut corrupted = 0;
+        while let Some(node) = nodes.pop() {
+            corrupted += node
+                .items
+                .iter()
+                .filter(|(_, priority)| *priority < node.ckey)
+                .count();
+            nodes.extend(node.left.iter().chain(node.right.iter()));
+        }
+        corrupted
+    }
+
+    /**
+    push a value onto the queue with given priority
+
+    # Errors
+    ReachedCapacity => the queue is already at capacity
+    */
+    pub fn push(&mut self, t: T, priority: Priority) -> Result<(), Error> {
+        self.node_count = self
+            .node_count
+            .checked_add(1)
+            .ok_or(Error::ReachedCapacity)?;
+        // trees of equal rank are combined like carries in binary addition
+        let mut carry = Box::new(SNode::new(t, priority));
+        for slot in &mut self.roots {
+            match slot.take() {
+                Some(root) => carry = SNode::combine(root, carry, self.threshold),
+                None => {
+                    *slot = Some(carry);
+                    return Ok(());
+                }
+            }
+        }
+        self.roots.push(Some(carry));
+        Ok(())
+    }
+
+    /**
+    return an element with the lowest corrupted priority, together with its original priority,
+    so that corrupted elements may come out later than their priority alone would have them
+
+    # Errors
+    Empty => cannot return element from empty queue
+    */
+    pub fn pop(&mut self) -> Result<(T, Priority), Error> {
+        let rank = self.min_rank().ok_or(Error::Empty)?;
+        let slot = &mut self.roots[rank];
+        let root = slot.as_mut().ok_or(Error::NotFound("node"))?;
+        let popped = root.items.pop().ok_or(Error::NotFound("node"))?;
+        if root.items.len() * 2 <= root.size {
+            if !root.is_leaf() {
+                root.sift();
+            } else if root.items.is_empty() {
+                *slot = None;
+                while self.roots.last().is_some_and(Option::is_none) {
+                    self.roots.pop();
+                }
+            }
+        }
+        self.node_count -= 1;
+        Ok(popped)
+    }
+}
@@ -0,0 +1,229 @@
+use crate::{error::Error, heap::BareQueue, node::NPrpt, queue::PriorityQueue};
+
+/* # stable queue */
+
+/**
+fibonacci queue which hands out elements of equal priority in the order they were pushed
+
+every element is queued with a sequence number breaking ties, so that the order of equal
+priorities does not depend on how the trees of the queue happen to be linked,
+and an element whose priority changes is queued behind the ones already holding its new priority
+
+```
+use fbheap::error::Error::Empty;
+use fbheap::stable::StableQueue;
+
+let mut queue = StableQueue::new();
+queue.push("first come", 1);
+queue.push("urgent", 0);
+queue.push("second come", 1);
+queue.push("third come", 2);
+assert_eq!(queue.pop(), Ok(("urgent", 0)));
+queue.decrease_priority(&"third come", 1);
+assert_eq!(queue.pop(), Ok(("first come", 1)));
+assert_eq!(queue.pop(), Ok(("second come", 1)));
+assert_eq!(queue.pop(), Ok(("third come", 1)));
+assert!(queue.is_empty());
+assert_eq!(queue.pop(), Err(Empty));
+```
+*/
+pub struct StableQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /// underlying queue ordered by priorities, then by sequence numbers
+    queue: BareQueue<T, (Priority, u64)>,
+    /// sequence number given to the next element
+    sequence: u64,
+}
+
+impl<T, Priority> Default for StableQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, Priority> StableQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /* # helper functions */
+
+    /// attach the next sequence number to a priority
+    fn sequenced(&mut self, priority: Priority) -> Result<(Priority, u64), Error> {
+        let sequence = self.sequence;
+        self.sequence = sequence.checked_add(1).ok_or(Error::Numerical)?;
+        Ok((priority, sequence))
+    }
+
+    /* # heap functionality */
+
+    /// construct empty queue
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            queue: BareQueue::new(),
+            sequence: 0,
+        }
+    }
+
+    /// returns true if the queue is empty
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// returns the number of elements in the queue
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /**
+    push a value onto the queue with given priority
+
+    # Errors
+    ReachedCapacity => the queue is already at capacity\n
+    Numerical => the queue ran out of sequence numbers
+    */
+    pub fn push(&mut self, t: T, priority: Priority) -> Result<(), Error> {
+        let priority = self.sequenced(priority)?;
+        self.queue.try_push(t, priority)
+    }
+
+    /**
+    return the element with the lowest priority, pushed earliest among those of equal priority
+
+    # Errors
+    Empty => cannot return element from empty queue\n
+    NotFound => internal indexing error
+    */
+    pub fn pop(&mut self) -> Result<(T, Priority), Error> {
+        self.queue.pop().map(|(t, (priority, _))| (t, priority))
+    }
+
+    /**
+    decreases the priority of the item with given value
+
+    # Errors
+    NotFound => index with given value was not found in the queue\n
+    CannotIncreasePriority => the give prioprity is not lower than the current one for the index of that value\n
+    Numerical => the queue ran out of sequence numbers
+    */
+    pub fn decrease_priority(&mut self, value: &T, new_priority: Priority) -> Result<(), Error> {
+        let node = self.queue.get_node(value).ok_or(Error::NotFound("value"))?;
+        // compared without sequence numbers, which would make an unchanged priority look higher
+        {
+            let current = &node.priority_ref().0;
+            if *current <= new_priority {
+                return Err(Error::CannotIncreasePriority {
+                    current_was_lower: *current < new_priority,
+                });
+            }
+        }
+        let priority = self.sequenced(new_priority)?;
+        self.queue.decrease_node_priority(node, priority)
+    }
+
+    /**
+    increases the priority of the item with given value,
+    an unchanged priority still moves the item behind the others of equal priority
+
+    # Errors
+    NotFound => index with given value was not found in the queue\n
+    CannotDecreasePriority => the given priority is lower than the current one for the index of that value\n
+    Numerical => the queue ran out of sequence numbers
+    */
+    pub fn increase_priority(&mut self, value: &T, new_priority: Priority) -> Result<(), Error> {
+        let priority = self.sequenced(new_priority)?;
+        self.queue.increase_priority(value, priority)
+    }
+
+    /**
+    changes the priority of the item with given value, in whichever direction
+
+    # Errors
+    NotFound => index with given value was not found in the queue\n
+    Numerical => the queue ran out of sequence numbers
+    */
+    pub fn update_priority(&mut self, value: &T, new_priority: Priority) -> Result<(), Error> {
+        let priority = self.sequenced(new_priority)?;
+        self.queue.update_priority(value, priority)
+    }
+
+    /**
+    remove the item with given value from the queue, regardless of its priority
+
+    # Errors
+    NotFound => index with given value was not found in the queue
+    */
+    pub fn delete(&mut self, value: &T) -> Result<(T, Priority), Error> {
+        self.queue
+            .delete(value)
+            .map(|(t, (priority, _))| (t, priority))
+    }
+
+    /**
+    move all elements of another queue into this one,
+    ties between elements of the two queues are broken by their positions within their own queue
+
+    # Errors
+    will error if the combined queue would exceed capacity
+    */
+    pub fn meld(&mut self, other: Self) -> Result<(), Error> {
+        self.sequence = self.sequence.max(other.sequence);
+        self.queue.meld(other.queue)
+    }
+}
+
+/* # priority queue */
+
+impl<T, Priority> PriorityQueue<T, Priority> for StableQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    fn push(&mut self, t: T, priority: Priority) -> Result<(), Error> {
+        Self::push(self, t, priority)
+    }
+
+    fn pop(&mut self) -> Result<(T, Priority), Error> {
+        Self::pop(self)
+    }
+
+    fn peek(&self) -> Option<(T, Priority)>
+    where
+        T: Clone,
+        Priority: Clone,
+    {
+        self.queue
+            .peek_cloned()
+            .map(|(t, (priority, _))| (t, priority))
+    }
+
+    fn decrease_priority(&mut self, value: &T, new_priority: Priority) -> Result<(), Error> {
+        Self::decrease_priority(self, value, new_priority)
+    }
+
+    fn delete(&mut self, value: &T) -> Result<(T, Priority), Error> {
+        Self::delete(self, value)
+    }
+
+    fn is_empty(&self) -> bool {
+        Self::is_empty(self)
+    }
+
+    fn len(&self) -> usize {
+        Self::len(self)
+    }
+
+    fn meld(&mut self, other: Self) -> Result<(), Error> {
+        Self::meld(self, other)
+    }
+}
@@ -0,0 +1,102 @@
+use alloc::vec::Vec;
+
+/* # statistics */
+
+/**
+snapshot of the structure of a queue, for watching how its trees evolve
+
+```
+use fbheap::heap::BareQueue;
+
+let mut queue: BareQueue<_, _> = (0..8).map(|value| (value, value)).collect();
+let stats = queue.stats();
+assert_eq!(stats.roots, 8);
+assert_eq!(stats.depths, vec![8]);
+
+// popping links the remaining seven nodes into trees of ranks 0, 1 and 2
+queue.pop().unwrap();
+let stats = queue.stats();
+assert_eq!(stats.nodes, 7);
+assert_eq!(stats.roots, 3);
+assert_eq!(stats.max_rank, 2);
+assert_eq!(stats.depths, vec![3, 3, 1]);
+
+queue.decrease_priority(&7, 0).unwrap();
+queue.decrease_priority(&6, 0).unwrap();
+assert_eq!(queue.stats().marked, 1);
+```
+*/
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct QueueStats {
+    /// total number of nodes
+    pub nodes: usize,
+    /// number of roots
+    pub roots: usize,
+    /// highest rank of any node, which is its number of children
+    pub max_rank: usize,
+    /// number of nodes which have lost a child since they last became a child themselves
+    pub marked: usize,
+    /// number of nodes at every depth, roots being at depth zero
+    pub depths: Vec<usize>,
+}
+
+/**
+counters of the elementary steps a queue performed, for checking its amortized analysis
+against actual runs, which are only kept with the `instrument` feature
+
+cuts include cascading ones, which are also counted on their own, and comparisons cover those
+between priorities made while restructuring the queue and keeping track of its minimum,
+but not those made while looking values up, which count as searches whenever they walk the trees
+
+```
+# #[cfg(feature = "instrument")] {
+use fbheap::heap::BareQueue;
+
+let mut queue: BareQueue<_, _> = (0..8).map(|value| (value, value)).collect();
+queue.pop().unwrap();
+let metrics = queue.metrics();
+assert_eq!(metrics.consolidations, 1);
+// seven roots are linked into trees of ranks 0, 1 and 2
+assert_eq!(metrics.links, 4);
+
+// both nodes are cut from parents which had not lost a child before
+queue.decrease_priority(&6, 0).unwrap();
+queue.decrease_priority(&4, 0).unwrap();
+assert_eq!(queue.metrics().cuts, 2);
+assert_eq!(queue.metrics().cascading_cuts, 0);
+assert_eq!(queue.metrics().searches, 2);
+queue.reset_metrics();
+assert_eq!(queue.metrics(), Default::default());
+# }
+```
+*/
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Metrics {
+    /// number of times one tree was linked below another
+    pub links: usize,
+    /// number of nodes cut from their parents
+    pub cuts: usize,
+    /// number of cuts of parents which had already lost a child
+    pub cascading_cuts: usize,
+    /// number of passes consolidating the roots
+    pub consolidations: usize,
+    /// number of comparisons between priorities
+    pub comparisons: usize,
+    /// number of walks through the trees looking for values
+    pub searches: usize,
+}
+
+impl Metrics {
+    /// construct zeroed counters
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            links: 0,
+            cuts: 0,
+            cascading_cuts: 0,
+            consolidations: 0,
+            comparisons: 0,
+            searches: 0,
+        }
+    }
+}
@@ -0,0 +1,267 @@
+use crate::{arena::ArenaQueue, error::Error};
+use std::{
+    sync::{Condvar, Mutex, MutexGuard},
+    time::Duration,
+};
+
+/* # sync queue */
+
+/**
+fibonacci queue which can be shared between threads, guarded by a single lock
+
+```
+use fbheap::sync::SyncQueue;
+
+let queue = SyncQueue::new();
+std::thread::scope(|scope| {
+    for worker in 0..4 {
+        let queue = &queue;
+        scope.spawn(move || {
+            for task in 0..10 {
+                queue.push((worker, task), task).unwrap();
+            }
+        });
+    }
+});
+assert_eq!(queue.len(), Ok(40));
+assert_eq!(queue.pop().map(|(_, priority)| priority), Ok(0));
+```
+
+workers can also wait for jobs instead of polling the queue
+
+```
+use fbheap::error::Error::Empty;
+use fbheap::sync::SyncQueue;
+use std::time::Duration;
+
+let jobs = SyncQueue::new();
+let done: Vec<_> = std::thread::scope(|scope| {
+    let workers: Vec<_> = (0..2)
+        .map(|_| {
+            scope.spawn(|| {
+                let mut done = Vec::new();
+                // a worker retires at the first sentinel, which sorts after every job
+                while let Ok((Some(job), _)) = jobs.pop_wait() {
+                    done.push(job);
+                }
+                done
+            })
+        })
+        .collect();
+    for job in 0..10 {
+        jobs.push(Some(job), job).unwrap();
+    }
+    for sentinel in 0..2 {
+        jobs.push(None, u32::MAX - sentinel).unwrap();
+    }
+    workers
+        .into_iter()
+        .flat_map(|worker| worker.join().unwrap())
+        .collect()
+});
+assert_eq!(done.len(), 10);
+// with nothing left to do, waiting gives up once the timeout passes
+assert_eq!(jobs.pop_timeout(Duration::from_millis(10)), Err(Empty));
+```
+*/
+pub struct SyncQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /// underlying queue, locked for the duration of every operation
+    queue: Mutex<ArenaQueue<T, Priority>>,
+    /// signalled whenever elements are added, waking threads waiting to pop
+    pushed: Condvar,
+}
+
+impl<T, Priority> Default for SyncQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, Priority> From<ArenaQueue<T, Priority>> for SyncQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    fn from(queue: ArenaQueue<T, Priority>) -> Self {
+        Self {
+            queue: Mutex::new(queue),
+            pushed: Condvar::new(),
+        }
+    }
+}
+
+impl<T, Priority> SyncQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /* # helper functions */
+
+    fn lock(&self) -> Result<MutexGuard<'_, ArenaQueue<T, Priority>>, Error> {
+        self.queue.lock().map_err(|_| Error::Poisoned)
+    }
+
+    /* # heap functionality */
+
+    /// construct empty queue
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            queue: Mutex::new(ArenaQueue::new()),
+            pushed: Condvar::new(),
+        }
+    }
+
+    /**
+    take the underlying queue out of the lock
+
+    # Errors
+    will error if the lock was poisoned
+    */
+    pub fn into_inner(self) -> Result<ArenaQueue<T, Priority>, Error> {
+        self.queue.into_inner().map_err(|_| Error::Poisoned)
+    }
+
+    /**
+    returns true if the queue is empty
+
+    # Errors
+    will error if the lock was poisoned
+    */
+    pub fn is_empty(&self) -> Result<bool, Error> {
+        Ok(self.lock()?.is_empty())
+    }
+
+    /**
+    returns the number of elements in the queue
+
+    # Errors
+    will error if the lock was poisoned
+    */
+    pub fn len(&self) -> Result<usize, Error> {
+        Ok(self.lock()?.len())
+    }
+
+    /**
+    push a value onto the queue with given priority
+
+    # Errors
+    ReachedCapacity => the queue is already at capacity\n
+    Poisoned => the lock was poisoned
+    */
+    pub fn push(&self, t: T, priority: Priority) -> Result<(), Error> {
+        self.lock()?.push(t, priority)?;
+        self.pushed.notify_one();
+        Ok(())
+    }
+
+    /**
+    return the element with the lowest priority
+
+    # Errors
+    Empty => cannot return element from empty queue\n
+    NotFound => internal indexing error\n
+    Poisoned => the lock was poisoned
+    */
+    pub fn pop(&self) -> Result<(T, Priority), Error> {
+        self.lock()?.pop()
+    }
+
+    /**
+    return the element with the lowest priority, waiting for one to be pushed if the queue is empty
+
+    # Errors
+    NotFound => internal indexing error\n
+    Poisoned => the lock was poisoned
+    */
+    pub fn pop_wait(&self) -> Result<(T, Priority), Error> {
+        self.pushed
+            .wait_while(self.lock()?, |queue| queue.is_empty())
+            .map_err(|_| Error::Poisoned)?
+            .pop()
+    }
+
+    /**
+    return the element with the lowest priority,
+    waiting at most for the given duration for one to be pushed if the queue is empty
+
+    # Errors
+    Empty => no element was pushed before the duration passed\n
+    NotFound => internal indexing error\n
+    Poisoned => the lock was poisoned
+    */
+    pub fn pop_timeout(&self, timeout: Duration) -> Result<(T, Priority), Error> {
+        let (mut queue, _) = self
+            .pushed
+            .wait_timeout_while(self.lock()?, timeout, |queue| queue.is_empty())
+            .map_err(|_| Error::Poisoned)?;
+        queue.pop()
+    }
+
+    /**
+    decreases the priority of the item with given value
+
+    # Errors
+    NotFound => index with given value was not found in the queue\n
+    CannotIncreasePriority => the give prioprity is higher than the current one for the index of that value\n
+    Poisoned => the lock was poisoned
+    */
+    pub fn decrease_priority(&self, value: &T, new_priority: Priority) -> Result<(), Error> {
+        self.lock()?.decrease_priority(value, new_priority)
+    }
+
+    /**
+    increases the priority of the item with given value
+
+    # Errors
+    NotFound => index with given value was not found in the queue\n
+    CannotDecreasePriority => the given priority is lower than the current one for the index of that value\n
+    Poisoned => the lock was poisoned
+    */
+    pub fn increase_priority(&self, value: &T, new_priority: Priority) -> Result<(), Error> {
+        self.lock()?.increase_priority(value, new_priority)
+    }
+
+    /**
+    changes the priority of the item with given value, in whichever direction
+
+    # Errors
+    NotFound => index with given value was not found in the queue\n
+    Poisoned => the lock was poisoned
+    */
+    pub fn update_priority(&self, value: &T, new_priority: Priority) -> Result<(), Error> {
+        self.lock()?.update_priority(value, new_priority)
+    }
+
+    /**
+    remove the item with given value from the queue, regardless of its priority
+
+    # Errors
+    NotFound => index with given value was not found in the queue\n
+    Poisoned => the lock was poisoned
+    */
+    pub fn delete(&self, value: &T) -> Result<(T, Priority), Error> {
+        self.lock()?.delete(value)
+    }
+
+    /**
+    move all elements of another queue into this one
+
+    # Errors
+    ReachedCapacity => the combined queue would exceed capacity\n
+    Poisoned => the lock was poisoned
+    */
+    pub fn meld(&self, other: ArenaQueue<T, Priority>) -> Result<(), Error> {
+        self.lock()?.meld(other)?;
+        self.pushed.notify_all();
+        Ok(())
+    }
+}
@@ -0,0 +1,138 @@
+use crate::{error::Error, heap::BareQueue, max::MaxQueue};
+use alloc::vec::Vec;
+
+/* # top k */
+
+/// queue holding the kept items, with the one to evict next at its front
+enum Kept<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /// largest items, so the smallest of them is evicted first
+    Largest(BareQueue<T, Priority>),
+    /// smallest items, so the largest of them is evicted first
+    Smallest(MaxQueue<T, Priority>),
+}
+
+/**
+tracker of the k items with the largest, or the smallest, priorities seen in a stream
+
+items are kept in a queue with the worst of them at its front,
+which is evicted as soon as more than k items are kept
+
+```
+use fbheap::topk::TopK;
+
+let mut heaviest = TopK::largest(2);
+for (request, size) in [("a", 30), ("b", 10), ("c", 70), ("d", 50)] {
+    heaviest.push(request, size)?;
+}
+assert_eq!(heaviest.into_sorted_vec()?, vec![("c", 70), ("d", 50)]);
+
+let mut fastest = TopK::smallest(2);
+assert_eq!(fastest.push("a", 30)?, None);
+assert_eq!(fastest.push("b", 10)?, None);
+// the slowest one falls out
+assert_eq!(fastest.push("c", 20)?, Some(("a", 30)));
+assert_eq!(fastest.into_sorted_vec()?, vec![("b", 10), ("c", 20)]);
+# Ok::<(), fbheap::error::Error>(())
+```
+*/
+pub struct TopK<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /// kept items
+    kept: Kept<T, Priority>,
+    /// number of items to keep
+    k: usize,
+}
+
+impl<T, Priority> TopK<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /// construct tracker keeping the k items with the largest priorities
+    #[must_use]
+    pub const fn largest(k: usize) -> Self {
+        Self {
+            kept: Kept::Largest(BareQueue::new()),
+            k,
+        }
+    }
+
+    /// construct tracker keeping the k items with the smallest priorities
+    #[must_use]
+    pub const fn smallest(k: usize) -> Self {
+        Self {
+            kept: Kept::Smallest(MaxQueue::new()),
+            k,
+        }
+    }
+
+    /// returns the number of items kept at most
+    #[must_use]
+    pub const fn k(&self) -> usize {
+        self.k
+    }
+
+    /// returns true if no items are kept
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// returns the number of items kept
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        match &self.kept {
+            Kept::Largest(queue) => queue.len(),
+            Kept::Smallest(queue) => queue.len(),
+        }
+    }
+
+    /**
+    offer an item to the tracker, returning the item evicted to make room for it, if any,
+    which is the item itself when it is worse than all kept ones
+
+    # Errors
+    ReachedCapacity => the queue is already at capacity\n
+    NotFound => internal indexing error
+    */
+    pub fn push(&mut self, t: T, priority: Priority) -> Result<Option<(T, Priority)>, Error> {
+        match &mut self.kept {
+            Kept::Largest(queue) => queue.try_push(t, priority)?,
+            Kept::Smallest(queue) => queue.push(t, priority)?,
+        }
+        if self.len() <= self.k {
+            return Ok(None);
+        }
+        match &mut self.kept {
+            Kept::Largest(queue) => queue.pop().map(Some),
+            Kept::Smallest(queue) => queue.pop().map(Some),
+        }
+    }
+
+    /**
+    consume the tracker, returning the kept items from the best to the worst
+
+    # Errors
+    NotFound => internal indexing error
+    */
+    pub fn into_sorted_vec(self) -> Result<Vec<(T, Priority)>, Error> {
+        let mut sorted = Vec::with_capacity(self.len());
+        match self.kept {
+            Kept::Largest(queue) => sorted.extend(queue.into_sorted_vec()?),
+            Kept::Smallest(mut queue) => {
+                while !queue.is_empty() {
+                    sorted.push(queue.pop()?);
+                }
+            }
+        }
+        sorted.reverse();
+        Ok(sorted)
+    }
+}
@@ -0,0 +1,233 @@
+use crate::{error::Error, heap::BareQueue, policy::DuplicatePolicy, queue::PriorityQueue};
+
+/* # unique queue */
+
+/**
+fibonacci queue holding every value at most once,
+so that lookups by value always affect the one element holding it
+
+pushing a queued value again either fails or replaces the queued element, depending on the policy,
+which takes a search through the whole queue on every push
+
+```
+use fbheap::error::Error::DuplicateValue;
+use fbheap::policy::DuplicatePolicy;
+use fbheap::unique::UniqueQueue;
+
+let mut strict = UniqueQueue::new();
+strict.push("fetch", 4)?;
+assert_eq!(strict.push("fetch", 2), Err(DuplicateValue));
+assert_eq!(strict.len(), 1);
+
+let mut latest = UniqueQueue::with_policy(DuplicatePolicy::Replace);
+latest.push("fetch", 4)?;
+assert_eq!(latest.push("fetch", 6), Ok(Some(("fetch", 4))));
+assert_eq!(latest.pop(), Ok(("fetch", 6)));
+assert!(latest.is_empty());
+# Ok::<(), fbheap::error::Error>(())
+```
+*/
+pub struct UniqueQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /// underlying queue, holding no two equal values
+    queue: BareQueue<T, Priority>,
+    /// what happens to values pushed while already queued
+    policy: DuplicatePolicy,
+}
+
+impl<T, Priority> Default for UniqueQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, Priority> UniqueQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    /* # heap functionality */
+
+    /// construct empty queue rejecting duplicates
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::with_policy(DuplicatePolicy::Reject)
+    }
+
+    /// construct empty queue handling duplicates with the given policy
+    #[must_use]
+    pub const fn with_policy(policy: DuplicatePolicy) -> Self {
+        Self {
+            queue: BareQueue::new(),
+            policy,
+        }
+    }
+
+    /// returns the policy for values pushed while already queued
+    #[must_use]
+    pub const fn policy(&self) -> DuplicatePolicy {
+        self.policy
+    }
+
+    /// returns true if the queue is empty
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// returns the number of elements in the queue
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// returns true if the queue holds the given value, searching through the whole queue
+    #[must_use]
+    pub fn contains(&self, value: &T) -> bool {
+        self.queue.contains(value)
+    }
+
+    /**
+    push a value onto the queue with given priority,
+    returning the element it replaced if the value was already queued
+
+    # Errors
+    DuplicateValue => the value is already queued and the policy rejects duplicates\n
+    ReachedCapacity => the queue is already at capacity
+    */
+    pub fn push(&mut self, t: T, priority: Priority) -> Result<Option<(T, Priority)>, Error> {
+        let replaced = match (self.queue.contains(&t), self.policy) {
+            (false, _) => None,
+            (true, DuplicatePolicy::Reject) => return Err(Error::DuplicateValue),
+            (true, DuplicatePolicy::Replace) => Some(self.queue.delete(&t)?),
+        };
+        self.queue.try_push(t, priority)?;
+        Ok(replaced)
+    }
+
+    /**
+    return the element with the lowest priority
+
+    # Errors
+    Empty => cannot return element from empty queue\n
+    NotFound => internal indexing error
+    */
+    pub fn pop(&mut self) -> Result<(T, Priority), Error> {
+        self.queue.pop()
+    }
+
+    /**
+    decreases the priority of the item with given value
+
+    # Errors
+    NotFound => index with given value was not found in the queue\n
+    CannotIncreasePriority => the give prioprity is higher than the current one for the index of that value
+    */
+    pub fn decrease_priority(&mut self, value: &T, new_priority: Priority) -> Result<(), Error> {
+        self.queue.decrease_priority(value, new_priority)
+    }
+
+    /**
+    increases the priority of the item with given value
+
+    # Errors
+    NotFound => index with given value was not found in the queue\n
+    CannotDecreasePriority => the given priority is lower than the current one for the index of that value
+    */
+    pub fn increase_priority(&mut self, value: &T, new_priority: Priority) -> Result<(), Error> {
+        self.queue.increase_priority(value, new_priority)
+    }
+
+    /**
+    changes the priority of the item with given value, in whichever direction
+
+    # Errors
+    NotFound => index with given value was not found in the queue
+    */
+    pub fn update_priority(&mut self, value: &T, new_priority: Priority) -> Result<(), Error> {
+        self.queue.update_priority(value, new_priority)
+    }
+
+    /**
+    remove the item with given value from the queue, regardless of its priority
+
+    # Errors
+    NotFound => index with given value was not found in the queue
+    */
+    pub fn delete(&mut self, value: &T) -> Result<(T, Priority), Error> {
+        self.queue.delete(value)
+    }
+
+    /**
+    move all elements of another queue into this one, handling values queued in both
+    with the policy of this queue, which takes a search through this queue for every moved element
+
+    # Errors
+    DuplicateValue => a value is queued in both and the policy rejects duplicates,
+    in which case this queue is left unchanged and the other one is dropped\n
+    ReachedCapacity => the combined queue would exceed capacity
+    */
+    pub fn meld(&mut self, other: Self) -> Result<(), Error> {
+        let elements = other.queue.into_vec_unsorted()?;
+        if self.policy == DuplicatePolicy::Reject
+            && elements.iter().any(|(t, _)| self.queue.contains(t))
+        {
+            return Err(Error::DuplicateValue);
+        }
+        for (t, priority) in elements {
+            self.push(t, priority)?;
+        }
+        Ok(())
+    }
+}
+
+/* # priority queue */
+
+impl<T, Priority> PriorityQueue<T, Priority> for UniqueQueue<T, Priority>
+where
+    T: Eq,
+    Priority: Ord,
+{
+    fn push(&mut self, t: T, priority: Priority) -> Result<(), Error> {
+        Self::push(self, t, priority).map(drop)
+    }
+
+    fn pop(&mut self) -> Result<(T, Priority), Error> {
+        Self::pop(self)
+    }
+
+    fn peek(&self) -> Option<(T, Priority)>
+    where
+        T: Clone,
+        Priority: Clone,
+    {
+        self.queue.peek_cloned()
+    }
+
+    fn decrease_priority(&mut self, value: &T, new_priority: Priority) -> Result<(), Error> {
+        Self::decrease_priority(self, value, new_priority)
+    }
+
+    fn delete(&mut self, value: &T) -> Result<(T, Priority), Error> {
+        Self::delete(self, value)
+    }
+
+    fn is_empty(&self) -> bool {
+        Self::is_empty(self)
+    }
+
+    fn len(&self) -> usize {
+        Self::len(self)
+    }
+
+    fn meld(&mut self, other: Self) -> Result<(), Error> {
+        Self::meld(self, other)
+    }
+}
@@ -0,0 +1,52 @@
+use fbheap::{arena::ArenaQueue, error::Error};
+
+#[test]
+fn handles_of_other_queues_are_rejected() -> Result<(), Error> {
+    // both queues give out a handle to index 0 of generation 0
+    let mut queue = ArenaQueue::new();
+    let mut other = ArenaQueue::new();
+    let own = queue.push_with_handle("own", 2)?;
+    let foreign = other.push_with_handle("foreign", 3)?;
+    assert_eq!(
+        queue.decrease_priority_at(foreign, 1),
+        Err(Error::NotFound("handle"))
+    );
+    assert_eq!(queue.delete_at(foreign), Err(Error::NotFound("handle")));
+    // a queue which gave out no handles accepts none either
+    let mut fresh = ArenaQueue::new();
+    fresh.push("fresh", 1)?;
+    assert_eq!(fresh.delete_at(own), Err(Error::NotFound("handle")));
+
+    assert_eq!(queue.delete_at(own), Ok(("own", 2)));
+    assert_eq!(queue.delete_at(own), Err(Error::StaleHandle));
+    assert_eq!(other.delete_at(foreign), Ok(("foreign", 3)));
+    Ok(())
+}
+
+#[test]
+fn handles_of_melded_queues_are_rejected() -> Result<(), Error> {
+    let mut queue = ArenaQueue::new();
+    let kept = (0..4)
+        .map(|n| queue.push_with_handle(n, n))
+        .collect::<Result<Vec<_>, _>>()?;
+    queue.pop()?;
+    let mut other = ArenaQueue::new();
+    let moved = (10..14)
+        .map(|n| other.push_with_handle(n, n))
+        .collect::<Result<Vec<_>, _>>()?;
+    queue.meld(other)?;
+
+    for &handle in &moved {
+        assert_eq!(
+            queue.decrease_priority_at(handle, -1),
+            Err(Error::NotFound("handle"))
+        );
+        assert_eq!(queue.delete_at(handle), Err(Error::NotFound("handle")));
+    }
+    assert_eq!(queue.delete_at(kept[0]), Err(Error::StaleHandle));
+    queue.decrease_priority_at(kept[3], -1)?;
+    assert_eq!(queue.pop(), Ok((3, -1)));
+    assert_eq!(queue.delete_at(kept[2]), Ok((2, 2)));
+    assert_eq!(queue.len(), 5);
+    Ok(())
+}
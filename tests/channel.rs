@@ -0,0 +1,196 @@
+#![cfg(feature = "async")]
+
+use fbheap::{
+    channel::{channel, ChannelBuilder},
+    error::Error,
+    policy::EvictionPolicy,
+};
+use std::{
+    future::Future,
+    pin::pin,
+    sync::Arc,
+    task::{Context, Poll, Wake},
+    thread::Thread,
+    time::Duration,
+};
+
+/// waker unparking the thread blocked on a future
+struct Unpark(Thread);
+
+impl Wake for Unpark {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// minimal executor, driving a single future on the current thread
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = Arc::new(Unpark(std::thread::current())).into();
+    let mut context = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => std::thread::park(),
+        }
+    }
+}
+
+#[test]
+fn pending_items_are_received_by_priority() -> Result<(), Error> {
+    let (sender, mut receiver) = channel();
+    for n in [5, 1, 4, 2, 3] {
+        sender.send(n, n)?;
+    }
+    assert_eq!(receiver.len(), Ok(5));
+    let received = block_on(async {
+        let mut received = Vec::new();
+        for _ in 0..5 {
+            received.push(receiver.recv().await?.1);
+        }
+        Ok::<_, Error>(received)
+    })?;
+    assert_eq!(received, vec![1, 2, 3, 4, 5]);
+    assert_eq!(receiver.try_recv(), Err(Error::Empty));
+    drop(sender);
+    assert_eq!(receiver.try_recv(), Err(Error::Disconnected));
+    Ok(())
+}
+
+#[test]
+fn waiting_receiver_is_woken_by_producers() {
+    let (sender, mut receiver) = channel();
+    let producers: Vec<_> = (0..4)
+        .map(|worker| {
+            let sender = sender.clone();
+            std::thread::spawn(move || {
+                for task in 0..250 {
+                    sender.send((worker, task), task).unwrap();
+                }
+            })
+        })
+        .collect();
+    drop(sender);
+
+    let mut received = block_on(async {
+        let mut received = Vec::new();
+        while let Ok((item, _)) = receiver.recv().await {
+            received.push(item);
+        }
+        received
+    });
+    producers
+        .into_iter()
+        .for_each(|producer| producer.join().unwrap());
+    received.sort_unstable();
+    let expected: Vec<_> = (0..4)
+        .flat_map(|worker| (0..250).map(move |task| (worker, task)))
+        .collect();
+    assert_eq!(received, expected);
+}
+
+#[test]
+fn sending_fails_once_receiver_is_dropped() {
+    let (sender, receiver) = channel();
+    assert_eq!(sender.send("a", 1), Ok(()));
+    drop(receiver);
+    assert_eq!(sender.send("b", 2), Err(Error::Disconnected));
+}
+
+#[test]
+fn items_of_equal_priority_are_received_in_order_sent() -> Result<(), Error> {
+    let (sender, mut receiver) = channel();
+    for task in ["a", "b", "c"] {
+        sender.send(task, 1)?;
+    }
+    sender.send("d", 0)?;
+    let received: Vec<_> = std::iter::from_fn(|| receiver.try_recv().ok())
+        .map(|(task, _)| task)
+        .collect();
+    assert_eq!(received, vec!["d", "a", "b", "c"]);
+    Ok(())
+}
+
+#[test]
+fn bounded_channels_keep_order_sent_among_equal_priorities() -> Result<(), Error> {
+    let (sender, mut receiver) = ChannelBuilder::new()
+        .bounded(3, EvictionPolicy::EvictLargest)
+        .build();
+    for task in ["a", "b", "c"] {
+        sender.send(task, 1)?;
+    }
+    // among the items tied for the highest priority, the one sent last gives way
+    assert_eq!(sender.send("d", 0)?, Some(("c", 1)));
+    let received: Vec<_> = std::iter::from_fn(|| receiver.try_recv().ok())
+        .map(|(task, _)| task)
+        .collect();
+    assert_eq!(received, vec!["d", "a", "b"]);
+    Ok(())
+}
+
+#[test]
+fn bounded_channels_settle_overflows_by_policy() -> Result<(), Error> {
+    let expected = [
+        (EvictionPolicy::RejectNew, Some(("new", 2)), vec![1, 3]),
+        (EvictionPolicy::EvictLargest, Some(("old", 3)), vec![1, 2]),
+        (EvictionPolicy::EvictSmallest, Some(("old", 1)), vec![2, 3]),
+    ];
+    for (policy, evicted, remaining) in expected {
+        let (sender, mut receiver) = ChannelBuilder::new().bounded(2, policy).build();
+        assert_eq!(sender.send("old", 1)?, None);
+        assert_eq!(sender.send("old", 3)?, None);
+        assert_eq!(sender.send("new", 2)?, evicted);
+        assert_eq!(receiver.len(), Ok(2));
+        let received: Vec<_> = std::iter::from_fn(|| receiver.try_recv().ok())
+            .map(|(_, priority)| priority)
+            .collect();
+        assert_eq!(received, remaining);
+    }
+    Ok(())
+}
+
+#[test]
+fn blocking_receiver_is_woken_by_producers() {
+    let (sender, mut receiver) = ChannelBuilder::new().blocking().build();
+    let producers: Vec<_> = (0..4)
+        .map(|worker| {
+            let sender = sender.clone();
+            std::thread::spawn(move || {
+                for task in 0..250 {
+                    sender.send((worker, task), task).unwrap();
+                }
+            })
+        })
+        .collect();
+    drop(sender);
+
+    let mut received = Vec::new();
+    while let Ok((item, _)) = receiver.recv() {
+        received.push(item);
+    }
+    producers
+        .into_iter()
+        .for_each(|producer| producer.join().unwrap());
+    received.sort_unstable();
+    let expected: Vec<_> = (0..4)
+        .flat_map(|worker| (0..250).map(move |task| (worker, task)))
+        .collect();
+    assert_eq!(received, expected);
+}
+
+#[test]
+fn blocking_receiver_times_out_on_empty_channel() {
+    let (sender, mut receiver) = ChannelBuilder::new()
+        .single_producer()
+        .blocking()
+        .build::<&str, u32>();
+    assert_eq!(
+        receiver.recv_timeout(Duration::from_millis(10)),
+        Err(Error::Empty)
+    );
+    drop(sender);
+    assert_eq!(
+        receiver.recv_timeout(Duration::from_millis(10)),
+        Err(Error::Disconnected)
+    );
+}
@@ -0,0 +1,92 @@
+#![cfg(feature = "std")]
+
+use fbheap::{dary::DaryQueue, error::Error};
+use proptest::prelude::*;
+use std::collections::HashMap;
+
+/// replay pushes, pops, decreases and deletes against a map from values to their latest priority
+fn check<const D: usize>(steps: &[(u8, u8, i8)]) -> Result<(), TestCaseError> {
+    let mut queue = DaryQueue::<u8, i8, D>::new();
+    let mut model = HashMap::new();
+    for &(kind, value, priority) in steps {
+        match kind % 4 {
+            0 => prop_assert_eq!(
+                queue.push(value, priority),
+                Ok(model.insert(value, priority))
+            ),
+            1 => match queue.pop() {
+                Ok((value, priority)) => {
+                    prop_assert_eq!(model.remove(&value), Some(priority));
+                    prop_assert!(model.values().all(|&other| other >= priority));
+                }
+                Err(error) => {
+                    prop_assert_eq!(error, Error::Empty);
+                    prop_assert!(model.is_empty());
+                }
+            },
+            2 => {
+                let expected = match model.get_mut(&value) {
+                    None => Err(Error::NotFound("value")),
+                    Some(current) if *current <= priority => Err(Error::CannotIncreasePriority {
+                        current_was_lower: *current < priority,
+                    }),
+                    Some(current) => {
+                        *current = priority;
+                        Ok(())
+                    }
+                };
+                prop_assert_eq!(queue.decrease_priority(&value, priority), expected);
+            }
+            _ => prop_assert_eq!(
+                queue.delete(&value).ok(),
+                model.remove(&value).map(|priority| (value, priority))
+            ),
+        }
+        prop_assert_eq!(queue.len(), model.len());
+        prop_assert_eq!(queue.priority_of(&value), model.get(&value));
+    }
+    let mut last = i8::MIN;
+    while let Ok((value, priority)) = queue.pop() {
+        prop_assert!(priority >= last);
+        prop_assert_eq!(model.remove(&value), Some(priority));
+        last = priority;
+    }
+    prop_assert!(model.is_empty());
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn binary_queue_matches_map(steps in prop::collection::vec((0..4_u8, 0..32_u8, -30..30_i8), 0..300)) {
+        check::<2>(&steps)?;
+    }
+
+    #[test]
+    fn quaternary_queue_matches_map(steps in prop::collection::vec((0..4_u8, 0..32_u8, -30..30_i8), 0..300)) {
+        check::<4>(&steps)?;
+    }
+
+    #[test]
+    fn melding_keeps_the_other_priorities(
+        one in prop::collection::vec((0..32_u8, -30..30_i8), 0..50),
+        other in prop::collection::vec((0..32_u8, -30..30_i8), 0..50),
+    ) {
+        let mut queue = DaryQueue::<u8, i8, 3>::new();
+        let mut melded = DaryQueue::new();
+        let mut model = HashMap::new();
+        for (value, priority) in one {
+            queue.push(value, priority)?;
+            model.insert(value, priority);
+        }
+        for (value, priority) in other {
+            melded.push(value, priority)?;
+            model.insert(value, priority);
+        }
+        queue.meld(melded)?;
+        let mut popped = HashMap::new();
+        while let Ok((value, priority)) = queue.pop() {
+            popped.insert(value, priority);
+        }
+        prop_assert_eq!(popped, model);
+    }
+}
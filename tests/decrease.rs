@@ -0,0 +1,157 @@
+use fbheap::{error::Error, heap::BareQueue, policy::Consolidation};
+
+/// queue holding a tree rooted at 0 with 1 to 7 below it, next to the roots 8, 10, 11 and 12
+fn linked_queue(consolidation: Consolidation) -> BareQueue<u32, i32> {
+    let mut queue = BareQueue::with_consolidation(consolidation);
+    for value in 0..9 {
+        queue.push(value, value as i32);
+    }
+    queue.push(100, -1);
+    assert_eq!(queue.pop(), Ok((100, -1)));
+    for value in 10..13 {
+        queue.push(value, value as i32);
+    }
+    queue
+}
+
+#[test]
+fn decreasing_root_below_first() {
+    let mut queue = BareQueue::new();
+    queue.push("first", 1);
+    queue.push("root", 2);
+    queue.decrease_priority(&"root", 0).unwrap();
+    queue.assert_valid();
+    assert_eq!(queue.pop(), Ok(("root", 0)));
+    assert_eq!(queue.pop(), Ok(("first", 1)));
+}
+
+#[test]
+fn decreasing_root_above_first() {
+    let mut queue = BareQueue::new();
+    queue.push("first", 1);
+    queue.push("root", 4);
+    queue.decrease_priority(&"root", 2).unwrap();
+    queue.assert_valid();
+    assert_eq!(queue.pop(), Ok(("first", 1)));
+    assert_eq!(queue.pop(), Ok(("root", 2)));
+}
+
+#[test]
+fn decreasing_first_itself() {
+    let mut queue = BareQueue::new();
+    queue.push("first", 1);
+    queue.push("root", 2);
+    queue.decrease_priority(&"first", 0).unwrap();
+    queue.assert_valid();
+    assert_eq!(queue.pop(), Ok(("first", 0)));
+    assert_eq!(queue.pop(), Ok(("root", 2)));
+}
+
+#[test]
+fn decreasing_consolidated_root_below_first() {
+    for consolidation in [Consolidation::Eager, Consolidation::Lazy(1)] {
+        let mut queue = linked_queue(consolidation);
+        queue.decrease_priority(&12, -5).unwrap();
+        queue.assert_valid();
+        assert_eq!(queue.pop(), Ok((12, -5)));
+        assert_eq!(queue.pop(), Ok((0, 0)));
+        queue.assert_valid();
+    }
+}
+
+#[test]
+fn decreasing_child_below_first() {
+    for consolidation in [Consolidation::Eager, Consolidation::Lazy(1)] {
+        let mut queue = linked_queue(consolidation);
+        queue.decrease_priority(&7, -5).unwrap();
+        queue.assert_valid();
+        assert_eq!(queue.pop(), Ok((7, -5)));
+        assert_eq!(queue.pop(), Ok((0, 0)));
+        queue.assert_valid();
+    }
+}
+
+#[test]
+fn decreasing_through_handle_below_first() {
+    let mut queue = BareQueue::new();
+    queue.push("first", 1);
+    let root = queue.push_with_handle("root", 2);
+    queue.decrease_priority_at(&root, 0).unwrap();
+    queue.assert_valid();
+    assert_eq!(queue.pop(), Ok(("root", 0)));
+}
+
+#[test]
+fn handles_follow_their_elements_between_queues() {
+    let mut first = BareQueue::new();
+    let a = first.push_with_handle("a", 10);
+    let mut second = BareQueue::new();
+    let b = second.push_with_handle("b", 20);
+    assert_eq!(
+        second.decrease_priority_at(&a, 5),
+        Err(Error::NotFound("handle"))
+    );
+
+    // melding forwards the identity of the melded queue, also along a chain of melds
+    let mut third = BareQueue::new();
+    third.push("c", 30);
+    second.append(&mut first).unwrap();
+    third.meld(second).unwrap();
+    third.decrease_priority_at(&a, 1).unwrap();
+    third.decrease_priority_at(&b, 2).unwrap();
+    assert_eq!(
+        first.decrease_priority_at(&a, 0),
+        Err(Error::NotFound("handle"))
+    );
+
+    // elements split off carry their handles along
+    let mut late = third.split_off(&2).unwrap();
+    assert_eq!(
+        third.decrease_priority_at(&b, 0),
+        Err(Error::NotFound("handle"))
+    );
+    late.decrease_priority_at(&b, 0).unwrap();
+    third.assert_valid();
+    late.assert_valid();
+
+    // as do transferred ones
+    late.transfer(&mut third, &"b").unwrap();
+    assert_eq!(third.delete_at(&b), Ok(("b", 0)));
+    assert_eq!(third.delete_at(&b), Err(Error::StaleHandle));
+}
+
+#[test]
+fn decreasing_every_root_in_turn() {
+    let mut queue = BareQueue::new();
+    for value in 0..20 {
+        queue.push(value, 100 + value);
+    }
+    for value in (0..20).rev() {
+        queue.decrease_priority(&value, value - 20).unwrap();
+        queue.assert_valid();
+    }
+    for value in 0..20 {
+        assert_eq!(queue.pop(), Ok((value, value - 20)));
+    }
+}
+
+#[cfg(feature = "instrument")]
+#[test]
+fn decreasing_many_walks_the_trees_once() {
+    let mut queue = linked_queue(Consolidation::Eager);
+    queue.reset_metrics();
+    let decreases = [(7, -7), (3, -3), (12, -12), (99, -99), (3, -4), (11, 20)];
+    assert_eq!(queue.decrease_priority_many(decreases), Ok(3));
+    assert_eq!(queue.metrics().searches, 1);
+    queue.assert_valid();
+    assert_eq!(queue.pop(), Ok((12, -12)));
+    assert_eq!(queue.pop(), Ok((7, -7)));
+    assert_eq!(queue.pop(), Ok((3, -4)));
+
+    // an index finds every value on its own, without walking the trees at all
+    queue.set_lookup(fbheap::lookup::OrdIndex::new());
+    queue.reset_metrics();
+    assert_eq!(queue.decrease_priority_many([(5, -5), (6, -6)]), Ok(2));
+    assert_eq!(queue.metrics().searches, 0);
+    assert_eq!(queue.pop(), Ok((6, -6)));
+}
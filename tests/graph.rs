@@ -0,0 +1,170 @@
+use fbheap::graph::{astar, dijkstra, prim_mst};
+
+/// pseudo random graph, with as many edges per vertex as given
+fn random_graph(vertices: usize, degree: usize, seed: u64) -> Vec<Vec<(usize, u64)>> {
+    let mut state = seed;
+    let mut next = move || {
+        state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        state >> 33
+    };
+    (0..vertices)
+        .map(|_| {
+            (0..degree)
+                .map(|_| (next() as usize % vertices, next() % 100))
+                .collect()
+        })
+        .collect()
+}
+
+/// shortest paths by repeatedly relaxing every edge
+fn bellman_ford(adjacency: &[Vec<(usize, u64)>], source: usize) -> Vec<Option<u64>> {
+    let mut distances = vec![None; adjacency.len()];
+    distances[source] = Some(0);
+    for _ in 0..adjacency.len() {
+        for (vertex, edges) in adjacency.iter().enumerate() {
+            let Some(distance) = distances[vertex] else {
+                continue;
+            };
+            for &(target, weight) in edges {
+                let candidate = distance + weight;
+                if distances[target].is_none_or(|current| candidate < current) {
+                    distances[target] = Some(candidate);
+                }
+            }
+        }
+    }
+    distances
+}
+
+#[test]
+fn matches_relaxation_on_random_graphs() {
+    for seed in 0..50 {
+        let adjacency = random_graph(60, 3, seed);
+        for source in [0, 17, 59] {
+            assert_eq!(
+                dijkstra(&adjacency, source),
+                Ok(bellman_ford(&adjacency, source))
+            );
+        }
+    }
+}
+
+#[test]
+fn rejects_unknown_vertices() {
+    let adjacency = vec![vec![(1, 1)], vec![(2, 1)]];
+    assert_eq!(
+        dijkstra(&adjacency, 2),
+        Err(fbheap::error::Error::NotFound("vertex"))
+    );
+    assert_eq!(
+        dijkstra(&adjacency, 0),
+        Err(fbheap::error::Error::NotFound("vertex"))
+    );
+}
+
+#[test]
+fn reports_overflowing_paths() {
+    let adjacency = vec![vec![(1, u64::MAX)], vec![(2, 1)], vec![]];
+    assert_eq!(
+        dijkstra(&adjacency, 0),
+        Err(fbheap::error::Error::Numerical)
+    );
+}
+
+#[test]
+fn astar_matches_dijkstra_on_random_graphs() {
+    for seed in 0..50 {
+        let adjacency = random_graph(60, 3, seed);
+        let distances = dijkstra(&adjacency, 0).unwrap();
+        for target in [0, 17, 59] {
+            let found = astar(&adjacency, 0, target, |_| 0).unwrap();
+            assert_eq!(found.as_ref().map(|(cost, _)| *cost), distances[target]);
+            if let Some((cost, path)) = found {
+                assert_eq!((path[0], path[path.len() - 1]), (0, target));
+                let walked: u64 = path
+                    .windows(2)
+                    .map(|step| {
+                        adjacency[step[0]]
+                            .iter()
+                            .filter(|&&(next, _)| next == step[1])
+                            .map(|&(_, weight)| weight)
+                            .min()
+                            .unwrap()
+                    })
+                    .sum();
+                assert_eq!(walked, cost);
+            }
+        }
+    }
+}
+
+#[test]
+fn astar_reopens_vertices_with_inconsistent_heuristics() {
+    // the estimate for vertex 1 is admissible but makes it look worse than it is
+    let adjacency = vec![vec![(1, 1), (2, 1)], vec![(2, 1)], vec![(3, 1)], vec![]];
+    let estimates = [0, 2, 0, 0];
+    assert_eq!(
+        astar(&adjacency, 0, 3, |vertex| estimates[vertex]),
+        Ok(Some((2, vec![0, 2, 3])))
+    );
+    let adjacency = vec![vec![(1, 1), (2, 3)], vec![(2, 1)], vec![(3, 1)], vec![]];
+    let estimates = [0, 2, 0, 0];
+    assert_eq!(
+        astar(&adjacency, 0, 3, |vertex| estimates[vertex]),
+        Ok(Some((3, vec![0, 1, 2, 3])))
+    );
+}
+
+/// weight of a minimum spanning forest by sorting the edges and joining components
+fn kruskal(adjacency: &[Vec<(usize, u64)>]) -> u64 {
+    let mut edges: Vec<_> = adjacency
+        .iter()
+        .enumerate()
+        .flat_map(|(a, edges)| edges.iter().map(move |&(b, weight)| (weight, a, b)))
+        .collect();
+    edges.sort_unstable();
+    let mut components: Vec<usize> = (0..adjacency.len()).collect();
+    fn find(components: &mut [usize], vertex: usize) -> usize {
+        let mut root = vertex;
+        while components[root] != root {
+            root = components[root];
+        }
+        components[vertex] = root;
+        root
+    }
+    let mut total = 0;
+    for (weight, a, b) in edges {
+        let (a, b) = (find(&mut components, a), find(&mut components, b));
+        if a != b {
+            components[a] = b;
+            total += weight;
+        }
+    }
+    total
+}
+
+#[test]
+fn prim_matches_kruskal_on_random_graphs() {
+    for seed in 0..50 {
+        let mut adjacency = random_graph(60, 2, seed);
+        for a in 0..adjacency.len() {
+            for (b, weight) in adjacency[a].clone() {
+                adjacency[b].push((a, weight));
+            }
+        }
+        let (edges, total) = prim_mst(&adjacency).unwrap();
+        assert_eq!(total, kruskal(&adjacency));
+        assert_eq!(
+            edges.iter().map(|&(_, _, weight)| weight).sum::<u64>(),
+            total
+        );
+        let mut reached = vec![false; adjacency.len()];
+        for &(parent, vertex, weight) in &edges {
+            assert!(adjacency[parent].contains(&(vertex, weight)));
+            assert!(!reached[vertex]);
+            reached[vertex] = true;
+        }
+    }
+}
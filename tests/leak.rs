@@ -0,0 +1,123 @@
+use fbheap::heap::BareQueue;
+use std::{cell::Cell, rc::Rc};
+
+/// value which keeps count of how many of its instances are alive
+struct Tracked {
+    id: u32,
+    alive: Rc<Cell<usize>>,
+}
+
+impl Tracked {
+    fn new(id: u32, alive: &Rc<Cell<usize>>) -> Self {
+        alive.set(alive.get() + 1);
+        Self {
+            id,
+            alive: Rc::clone(alive),
+        }
+    }
+}
+
+impl Clone for Tracked {
+    fn clone(&self) -> Self {
+        Self::new(self.id, &self.alive)
+    }
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        self.alive.set(self.alive.get() - 1);
+    }
+}
+
+impl PartialEq for Tracked {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Tracked {}
+
+impl core::hash::Hash for Tracked {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+/// queue with nested trees and marked nodes, built from the given values
+fn populated(alive: &Rc<Cell<usize>>) -> BareQueue<Tracked, u32> {
+    let mut queue = BareQueue::new();
+    for id in 0..100 {
+        queue.push(Tracked::new(id, alive), id);
+    }
+    queue.pop().unwrap();
+    for id in (50..100).step_by(3) {
+        queue
+            .decrease_priority(&Tracked::new(id, alive), id - 50)
+            .unwrap();
+    }
+    queue.pop().unwrap();
+    queue
+}
+
+#[test]
+fn dropping_queue_frees_every_node() {
+    let alive = Rc::new(Cell::new(0));
+    let queue = populated(&alive);
+    assert_eq!(alive.get(), queue.len());
+    drop(queue);
+    assert_eq!(alive.get(), 0);
+}
+
+#[test]
+fn dropping_melded_queue_frees_every_node() {
+    let alive = Rc::new(Cell::new(0));
+    let mut queue = populated(&alive);
+    queue.meld(populated(&alive)).unwrap();
+    queue.pop().unwrap();
+    assert_eq!(alive.get(), queue.len());
+    drop(queue);
+    assert_eq!(alive.get(), 0);
+}
+
+#[test]
+fn clearing_queue_frees_every_node() {
+    let alive = Rc::new(Cell::new(0));
+    let mut queue = populated(&alive);
+    queue.clear();
+    assert_eq!(alive.get(), 0);
+}
+
+#[test]
+fn popping_into_storage_frees_replaced_elements() {
+    let alive = Rc::new(Cell::new(0));
+    let mut queue = populated(&alive);
+    let mut slot = None;
+    let mut last = None;
+    while queue.pop_min_into(&mut slot).unwrap() {
+        let (_, priority) = slot.as_ref().unwrap();
+        assert!(last <= Some(*priority));
+        last = Some(*priority);
+        assert_eq!(alive.get(), queue.len() + 1);
+    }
+    assert!(slot.is_none());
+    assert_eq!(alive.get(), 0);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn dropping_hash_queue_frees_every_node() {
+    let alive = Rc::new(Cell::new(0));
+    let mut queue = fbheap::hash::HashQueue::new();
+    for id in 0..100 {
+        queue.push(Tracked::new(id, &alive), id).unwrap();
+    }
+    queue.pop().unwrap();
+    for id in (50..100).step_by(3) {
+        queue
+            .decrease_priority(&Tracked::new(id, &alive), id - 50)
+            .unwrap();
+    }
+    queue.pop().unwrap();
+    drop(queue);
+    assert_eq!(alive.get(), 0);
+}
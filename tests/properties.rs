@@ -0,0 +1,572 @@
+use fbheap::{
+    arena::ArenaQueue,
+    binomial::BinomialQueue,
+    dary::DaryQueue,
+    heap::BareQueue,
+    huffman,
+    inspect::NodeView,
+    lazy::LazyQueue,
+    leftist::LeftistQueue,
+    lookup::OrdIndex,
+    median::RunningMedian,
+    merge::kmerge,
+    minmax::MinMaxQueue,
+    model::{Model, Op},
+    pairing::PairingQueue,
+    persistent::PersistentQueue,
+    policy::Consolidation,
+    queue::PriorityQueue,
+    soft::SoftQueue,
+    stable::StableQueue,
+};
+use proptest::prelude::*;
+
+/// total length of an optimal prefix code, as the sum of all merged weights, merging by sorting
+fn optimal_cost(frequencies: &[u64]) -> u64 {
+    let mut weights = frequencies.to_vec();
+    let mut cost = 0;
+    while weights.len() > 1 {
+        weights.sort_unstable_by(|a, b| b.cmp(a));
+        let merged = weights.pop().unwrap() + weights.pop().unwrap();
+        cost += merged;
+        weights.push(merged);
+    }
+    cost
+}
+
+/// operations on few values with few priorities, so that lookups hit and priorities tie often
+fn op() -> impl Strategy<Value = Op<u8, i8>> {
+    prop_oneof![
+        3 => (0..32_u8, -20..20_i8).prop_map(|(t, priority)| Op::Push(t, priority)),
+        2 => Just(Op::Pop),
+        1 => Just(Op::Peek),
+        2 => (0..32_u8, -30..20_i8).prop_map(|(t, priority)| Op::DecreasePriority(t, priority)),
+        1 => (0..32_u8).prop_map(Op::Delete),
+    ]
+}
+
+fn consolidation() -> impl Strategy<Value = Consolidation> {
+    prop_oneof![
+        Just(Consolidation::Eager),
+        (1..4_usize).prop_map(Consolidation::Lazy),
+        Just(Consolidation::Adaptive),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn bare_queue_matches_model(
+        consolidation in consolidation(),
+        ops in prop::collection::vec(op(), 0..200),
+    ) {
+        let mut queue = BareQueue::with_consolidation(consolidation);
+        let mut model = Model::new();
+        for op in ops {
+            model.apply(&mut queue, op);
+            queue.assert_valid();
+        }
+        prop_assert_eq!(queue.into_sorted_vec().map(|sorted| sorted.len()), Ok(model.len()));
+    }
+
+    #[test]
+    fn arena_queue_matches_model(ops in prop::collection::vec(op(), 0..200)) {
+        Model::check(&mut ArenaQueue::new(), ops);
+    }
+
+    #[test]
+    fn leftist_queue_matches_model(ops in prop::collection::vec(op(), 0..200)) {
+        Model::check(&mut LeftistQueue::new(), ops);
+    }
+
+    #[test]
+    fn dary_queue_matches_model(ops in prop::collection::vec(op(), 0..200)) {
+        Model::check(&mut DaryQueue::<_, _, 2>::new(), ops);
+    }
+
+    #[test]
+    fn pairing_queue_matches_model(ops in prop::collection::vec(op(), 0..200)) {
+        Model::check(&mut PairingQueue::new(), ops);
+    }
+
+    #[test]
+    fn binomial_queue_matches_model(ops in prop::collection::vec(op(), 0..200)) {
+        Model::check(&mut BinomialQueue::new(), ops);
+    }
+
+    #[test]
+    fn stable_queue_matches_model(ops in prop::collection::vec(op(), 0..200)) {
+        Model::check(&mut StableQueue::new(), ops);
+    }
+
+    #[test]
+    fn lazy_queue_matches_model(
+        threshold in 0..4_usize,
+        steps in prop::collection::vec((op(), prop::option::weighted(0.1, 0..32_u8)), 0..200),
+    ) {
+        let mut queue = LazyQueue::with_threshold(threshold);
+        let mut model = Model::new();
+        for (op, delete) in steps {
+            model.apply(&mut queue, op);
+            // deleting a value which is not queued is a logic error for a lazy queue
+            if let Some(value) = delete.filter(|value| model.contains(value)) {
+                prop_assert_eq!(queue.delete(&value), model.delete(&value).map(drop));
+            }
+        }
+        while !model.is_empty() {
+            model.apply(&mut queue, Op::Pop);
+        }
+        prop_assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn lazy_deletes_do_not_depend_on_compaction(
+        threshold in 0..4_usize,
+        pushes in prop::collection::vec((0..4_u8, -20..20_i8), 0..40),
+        deletes in prop::collection::vec(0..4_u8, 0..20),
+    ) {
+        let mut queue = LazyQueue::with_threshold(threshold);
+        let mut expected = Vec::new();
+        for &(t, priority) in &pushes {
+            queue.push(t, priority)?;
+            expected.push((priority, t));
+        }
+        expected.sort_unstable();
+        for value in deletes {
+            // a tombstone takes out the queued copy with the lowest priority
+            if let Some(position) = expected.iter().position(|&(_, t)| t == value) {
+                expected.remove(position);
+                queue.delete(&value)?;
+            }
+        }
+        let mut popped = Vec::new();
+        while let Ok((t, priority)) = queue.pop() {
+            popped.push((priority, t));
+        }
+        prop_assert!(popped.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+        popped.sort_unstable();
+        prop_assert_eq!(popped, expected);
+    }
+
+    #[test]
+    fn huffman_code_is_optimal_and_prefix_free(
+        frequencies in prop::collection::vec(0..1000_u64, 2..40)
+    ) {
+        let codewords = huffman::code(&frequencies).unwrap();
+        let cost: u64 = codewords
+            .iter()
+            .zip(&frequencies)
+            .map(|(codeword, &frequency)| codeword.len() as u64 * frequency)
+            .sum();
+        prop_assert_eq!(cost, optimal_cost(&frequencies));
+        for (a, first) in codewords.iter().enumerate() {
+            for (b, second) in codewords.iter().enumerate() {
+                prop_assert!(a == b || !second.starts_with(first));
+            }
+        }
+    }
+
+    #[test]
+    fn kmerge_matches_stable_sort(
+        mut runs in prop::collection::vec(prop::collection::vec(0..50_u8, 0..30), 0..12)
+    ) {
+        for run in &mut runs {
+            run.sort_unstable();
+        }
+        // tag every item with its input, so that the order of equal items is checked as well
+        let tagged: Vec<Vec<(u8, usize)>> = runs
+            .iter()
+            .enumerate()
+            .map(|(input, run)| run.iter().map(|&item| (item, input)).collect())
+            .collect();
+        let mut expected: Vec<_> = tagged.iter().flatten().copied().collect();
+        expected.sort_unstable();
+        let merged = kmerge(tagged.into_iter().map(Vec::into_iter));
+        prop_assert_eq!(merged.size_hint(), (expected.len(), Some(expected.len())));
+        prop_assert_eq!(merged.collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn running_median_matches_sorting(priorities in prop::collection::vec(-50..50_i32, 1..100)) {
+        let mut median = RunningMedian::new();
+        for (count, &priority) in priorities.iter().enumerate() {
+            median.insert(priority).unwrap();
+            let mut sorted = priorities[..=count].to_vec();
+            sorted.sort_unstable();
+            prop_assert_eq!(median.median(), Some(sorted[count / 2]));
+            prop_assert_eq!(median.upper_median(), Some(sorted[count.div_ceil(2)]));
+        }
+    }
+
+    #[test]
+    fn min_max_queue_matches_sorting(ops in prop::collection::vec((0..3_u8, -20..20_i8), 0..200)) {
+        let mut queue = MinMaxQueue::new();
+        let mut model: Vec<i8> = Vec::new();
+        for (kind, priority) in ops {
+            match kind {
+                0 => {
+                    queue.push(priority, priority).unwrap();
+                    model.push(priority);
+                    model.sort_unstable();
+                }
+                1 => prop_assert_eq!(
+                    queue.pop_min().ok().map(|(_, priority)| priority),
+                    (!model.is_empty()).then(|| model.remove(0))
+                ),
+                _ => prop_assert_eq!(queue.pop_max().ok().map(|(_, priority)| priority), model.pop()),
+            }
+            prop_assert_eq!(queue.len(), model.len());
+            prop_assert_eq!(queue.peek_min().map(|(_, priority)| priority), model.first().copied());
+            prop_assert_eq!(queue.peek_max().map(|(_, priority)| priority), model.last().copied());
+        }
+    }
+
+    #[test]
+    fn batched_decreases_match_single_ones(
+        pushes in prop::collection::vec((0..32_u8, -20..20_i8), 0..60),
+        pops in 0..10_usize,
+        decreases in prop::collection::vec((0..40_u8, -40..20_i8), 0..40),
+    ) {
+        let mut single = BareQueue::new();
+        let mut batched = BareQueue::new();
+        let mut indexed = BareQueue::new();
+        indexed.set_lookup(OrdIndex::new());
+        for &(t, priority) in &pushes {
+            // distinct values, so that lookups find the same element in all queues
+            if !single.contains(&t) {
+                single.push(t, priority);
+                batched.push(t, priority);
+                indexed.push(t, priority);
+            }
+        }
+        for _ in 0..pops {
+            let popped = single.pop().ok().map(|(_, p)| p);
+            prop_assert_eq!(popped, batched.pop().ok().map(|(_, p)| p));
+            prop_assert_eq!(popped, indexed.pop().ok().map(|(_, p)| p));
+        }
+        let mut decreased = 0;
+        for &(t, priority) in &decreases {
+            decreased += usize::from(single.decrease_priority(&t, priority).is_ok());
+        }
+        let relaxed = batched.decrease_priority_many(decreases.clone()).unwrap();
+        prop_assert!(relaxed <= decreased);
+        prop_assert_eq!(indexed.decrease_priority_many(decreases).unwrap(), relaxed);
+        batched.assert_valid();
+        indexed.assert_valid();
+        prop_assert_eq!(
+            PriorityQueue::peek(&single).map(|(_, p)| p),
+            PriorityQueue::peek(&batched).map(|(_, p)| p)
+        );
+        let mut single = single.into_sorted_vec().unwrap();
+        let mut batched = batched.into_sorted_vec().unwrap();
+        let mut indexed = indexed.into_sorted_vec().unwrap();
+        single.sort_unstable();
+        batched.sort_unstable();
+        indexed.sort_unstable();
+        prop_assert_eq!(&single, &batched);
+        prop_assert_eq!(single, indexed);
+    }
+
+    #[test]
+    fn split_off_partitions_by_bound(
+        priorities in prop::collection::vec(-50..50_i32, 0..100),
+        pops in 0..10_usize,
+        decreases in prop::collection::vec((0..100_usize, -60..50_i32), 0..20),
+        bound in -60..60_i32,
+    ) {
+        let mut queue: BareQueue<_, _> = priorities.iter().copied().enumerate().collect();
+        for _ in 0..pops {
+            queue.pop().ok();
+        }
+        for (t, priority) in decreases {
+            queue.decrease_priority(&t, priority).ok();
+        }
+        let mut all = queue.to_vec_unsorted();
+        all.sort_unstable();
+
+        let split = queue.split_off(&bound).unwrap();
+        queue.assert_valid();
+        split.assert_valid();
+        let below = queue.into_sorted_vec().unwrap();
+        let above = split.into_sorted_vec().unwrap();
+        prop_assert!(below.iter().all(|&(_, priority)| priority < bound));
+        prop_assert!(above.iter().all(|&(_, priority)| priority >= bound));
+        let mut joined: Vec<_> = below.into_iter().chain(above).collect();
+        joined.sort_unstable();
+        prop_assert_eq!(joined, all);
+    }
+
+    #[test]
+    fn priority_range_matches_filter(
+        priorities in prop::collection::vec(-50..50_i32, 0..100),
+        pops in 0..10_usize,
+        start in -60..60_i32,
+        length in 0..40_i32,
+    ) {
+        let mut queue: BareQueue<_, _> = priorities.iter().copied().enumerate().collect();
+        for _ in 0..pops {
+            queue.pop().ok();
+        }
+        let range = start..start + length;
+        let mut expected: Vec<_> = queue
+            .iter()
+            .filter(|(_, priority)| range.contains(priority))
+            .collect();
+        let mut found: Vec<_> = queue.iter_priority_range(range).collect();
+        expected.sort_unstable();
+        found.sort_unstable();
+        prop_assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn popping_ties_matches_sorting(
+        priorities in prop::collection::vec(0..6_u8, 0..100),
+        decreases in prop::collection::vec((0..100_usize, 0..6_u8), 0..20),
+    ) {
+        let mut queue: BareQueue<_, _> = priorities.iter().copied().enumerate().collect();
+        queue.pop().ok();
+        for (t, priority) in decreases {
+            queue.decrease_priority(&t, priority).ok();
+        }
+        let mut sorted = queue.to_vec_unsorted();
+        sorted.sort_unstable_by_key(|&(t, priority)| (priority, t));
+        while let Ok(mut tied) = queue.pop_equal_min() {
+            queue.assert_valid();
+            tied.sort_unstable_by_key(|&(t, priority)| (priority, t));
+            let rest = sorted.split_off(sorted.partition_point(|&(_, priority)| priority <= tied[0].1));
+            prop_assert_eq!(&tied, &sorted);
+            sorted = rest;
+        }
+        prop_assert!(sorted.is_empty());
+    }
+
+    #[test]
+    fn popping_k_matches_popping_one_by_one(
+        priorities in prop::collection::vec(-20..20_i8, 0..100),
+        decreases in prop::collection::vec((0..100_usize, -30..20_i8), 0..20),
+        ks in prop::collection::vec(0..30_usize, 0..8),
+    ) {
+        // ties are broken by push order, so that both queues pop the same elements
+        let mut batched = BareQueue::new();
+        batched.set_deterministic(true);
+        batched.extend(priorities.iter().copied().enumerate());
+        batched.pop().ok();
+        for (t, priority) in decreases {
+            batched.decrease_priority(&t, priority).ok();
+        }
+        let mut single = batched.clone();
+        for k in ks {
+            let popped = batched.pop_k(k).unwrap();
+            batched.assert_valid();
+            let expected: Vec<_> = (0..k).map_while(|_| single.pop().ok()).collect();
+            prop_assert_eq!(popped, expected);
+        }
+        prop_assert_eq!(batched.into_iter().collect::<Vec<_>>(), single.into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn soft_queue_bounds_corruption(
+        priorities in prop::collection::vec(-1000..1000_i32, 0..600),
+        one_in in 1..20_u64,
+        pops in 0..300_usize,
+    ) {
+        let mut queue = SoftQueue::new(one_in);
+        for (t, &priority) in priorities.iter().enumerate() {
+            queue.push(t, priority).unwrap();
+            prop_assert!(queue.corrupted() as u64 <= (t as u64 + 1) / one_in);
+        }
+        let mut popped = Vec::new();
+        for _ in 0..pops {
+            if let Ok(element) = queue.pop() {
+                popped.push(element);
+            }
+            prop_assert!(queue.corrupted() as u64 <= priorities.len() as u64 / one_in);
+        }
+        prop_assert_eq!(queue.len(), priorities.len() - popped.len());
+        popped.extend(core::iter::from_fn(|| queue.pop().ok()));
+        popped.sort_unstable();
+        prop_assert_eq!(popped, priorities.into_iter().enumerate().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn persistent_versions_stay_intact(
+        steps in prop::collection::vec((0..4_u8, any::<usize>(), 0..32_u8, -20..20_i8), 0..200),
+    ) {
+        // every version next to the sorted priorities it should hold
+        let mut versions = vec![(PersistentQueue::new(), Vec::new())];
+        for (kind, pick, t, priority) in steps {
+            let (queue, model) = versions[pick % versions.len()].clone();
+            let next = match kind {
+                0 | 1 => {
+                    let mut model = model;
+                    let position = model.partition_point(|&other| other <= priority);
+                    model.insert(position, priority);
+                    (queue.push(t, priority)?, model)
+                }
+                2 => match queue.pop() {
+                    Ok(((_, popped), rest)) => {
+                        prop_assert_eq!(Some(&popped), model.first());
+                        (rest, model[1..].to_vec())
+                    }
+                    Err(_) => {
+                        prop_assert!(model.is_empty());
+                        continue;
+                    }
+                },
+                _ => {
+                    let (other, other_model) = &versions[pick / 2 % versions.len()];
+                    let mut model = model;
+                    model.extend(other_model);
+                    model.sort_unstable();
+                    (queue.meld(other)?, model)
+                }
+            };
+            versions.push(next);
+        }
+        for (mut queue, model) in versions {
+            prop_assert_eq!(queue.len(), model.len());
+            let mut popped = Vec::new();
+            while let Ok(((_, priority), rest)) = queue.pop() {
+                popped.push(priority);
+                queue = rest;
+            }
+            prop_assert_eq!(popped, model);
+        }
+    }
+
+    #[test]
+    fn union_of_queues_matches_sorting(
+        shards in prop::collection::vec(prop::collection::vec(-50..50_i16, 0..30), 0..8),
+    ) {
+        let queues = shards
+            .iter()
+            .map(|shard| shard.iter().map(|&priority| ((), priority)).collect::<BareQueue<_, _>>());
+        let queue = BareQueue::from_queues(queues.collect::<Vec<_>>())?;
+        let mut expected: Vec<_> = shards.into_iter().flatten().collect();
+        expected.sort_unstable();
+        let sorted = queue.into_sorted_vec()?;
+        prop_assert_eq!(sorted.into_iter().map(|(_, priority)| priority).collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn deterministic_pops_follow_push_order(
+        consolidation in consolidation(),
+        steps in prop::collection::vec((0..3_u8, -5..5_i8, any::<usize>()), 0..200),
+    ) {
+        // values are pushed in increasing order, so that sorting by priority and then by value
+        // gives the order in which ties should come out, whatever the strategy
+        let mut queue = BareQueue::with_consolidation(consolidation);
+        queue.set_deterministic(true);
+        let mut model: Vec<(i8, usize)> = Vec::new();
+        for (value, (kind, priority, pick)) in steps.into_iter().enumerate() {
+            match kind {
+                0 => {
+                    queue.push(value, priority);
+                    model.push((priority, value));
+                }
+                1 if !model.is_empty() => {
+                    model.sort_unstable();
+                    let (priority, value) = model.remove(0);
+                    prop_assert_eq!(queue.pop(), Ok((value, priority)));
+                }
+                2 if !model.is_empty() => {
+                    let count = model.len();
+                    let entry = &mut model[pick % count];
+                    if priority < entry.0 {
+                        queue.decrease_priority(&entry.1, priority)?;
+                        entry.0 = priority;
+                    }
+                }
+                _ => {}
+            }
+        }
+        model.sort_unstable();
+        let popped: Vec<_> = queue.into_iter().map(|(value, priority)| (priority, value)).collect();
+        prop_assert_eq!(popped, model);
+    }
+
+    #[test]
+    fn deterministic_sorted_vec_matches_popping(
+        consolidation in consolidation(),
+        priorities in prop::collection::vec(0..4_u8, 0..100),
+        pops in 0..20_usize,
+    ) {
+        // few distinct priorities, so that most elements are tied with others
+        let mut queue = BareQueue::with_consolidation(consolidation);
+        queue.set_deterministic(true);
+        for (value, priority) in priorities.into_iter().enumerate() {
+            queue.push(value, priority);
+        }
+        for _ in 0..pops {
+            let _ = queue.pop();
+        }
+        let mut popped = queue.clone();
+        let drained: Vec<_> = core::iter::from_fn(|| popped.pop().ok()).collect();
+        prop_assert_eq!(queue.into_sorted_vec(), Ok(drained));
+    }
+
+    #[test]
+    fn indexed_lookups_match_searching(
+        steps in prop::collection::vec((0..9_u8, 0..24_u8, 0..24_u8, -20..20_i8), 0..200),
+    ) {
+        // values stay distinct, so that searching and the index find the same elements
+        let mut searched = BareQueue::new();
+        let mut indexed = BareQueue::new();
+        indexed.set_lookup(OrdIndex::new());
+        for (kind, value, other, priority) in steps {
+            match kind {
+                0 | 1 if !searched.contains(&value) => {
+                    searched.push(value, priority);
+                    indexed.push(value, priority);
+                }
+                2 => prop_assert_eq!(searched.pop(), indexed.pop()),
+                3 => prop_assert_eq!(
+                    searched.decrease_priority(&value, priority),
+                    indexed.decrease_priority(&value, priority)
+                ),
+                4 => prop_assert_eq!(searched.delete(&value), indexed.delete(&value)),
+                5 if !searched.contains(&other) => prop_assert_eq!(
+                    searched.replace(&value, other),
+                    indexed.replace(&value, other)
+                ),
+                6 => {
+                    let mut split = searched.split_off(&priority)?;
+                    searched.append(&mut split)?;
+                    let mut split = indexed.split_off(&priority)?;
+                    indexed.append(&mut split)?;
+                }
+                7 => {
+                    searched.retain(|_, priority| priority % 3 != 0)?;
+                    indexed.retain(|_, priority| priority % 3 != 0)?;
+                }
+                8 => indexed = indexed.clone(),
+                _ => {}
+            }
+            prop_assert_eq!(searched.contains(&value), indexed.contains(&value));
+        }
+        prop_assert_eq!(searched.into_sorted_vec(), indexed.into_sorted_vec());
+    }
+
+    #[test]
+    fn inspected_trees_are_heap_ordered(ops in prop::collection::vec(op(), 0..200)) {
+        let mut queue = BareQueue::new();
+        let mut model = Model::new();
+        for op in ops {
+            model.apply(&mut queue, op);
+        }
+        let mut nodes: Vec<_> = queue.roots().collect();
+        let mut visited = Vec::new();
+        while let Some(node) = nodes.pop() {
+            let children: Vec<_> = node.children().collect();
+            prop_assert_eq!(children.len(), node.rank());
+            for child in &children {
+                prop_assert!(*child.priority() >= *node.priority());
+            }
+            visited.push((*node.value(), *node.priority()));
+            nodes.extend(children);
+        }
+        visited.sort_unstable();
+        let mut elements = queue.to_vec_unsorted();
+        elements.sort_unstable();
+        prop_assert_eq!(visited, elements);
+    }
+}
@@ -0,0 +1,66 @@
+#![cfg(feature = "raw")]
+
+use fbheap::{
+    error::Error,
+    model::{Model, Op},
+    raw::RawQueue,
+};
+
+/// deterministic pseudo random operations on few values, kept short enough to run under miri
+fn ops(count: usize, seed: u64) -> Vec<Op<u8, i8>> {
+    let mut state = seed;
+    let mut next = move || {
+        state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (state >> 33) as u8
+    };
+    (0..count)
+        .map(|_| match next() % 8 {
+            0..=2 => Op::Push(next() % 32, (next() % 40) as i8 - 20),
+            3 => Op::Delete(next() % 32),
+            4 | 5 => Op::Pop,
+            6 => Op::DecreasePriority(next() % 32, (next() % 50) as i8 - 30),
+            _ => Op::Peek,
+        })
+        .collect()
+}
+
+#[test]
+fn matches_model() {
+    let (runs, count) = if cfg!(miri) { (4, 120) } else { (200, 400) };
+    for seed in 0..runs {
+        Model::check(&mut RawQueue::new(), ops(count, seed));
+    }
+}
+
+#[test]
+fn drops_elements_left_in_trees() {
+    let mut queue = RawQueue::new();
+    for x in 0..64 {
+        queue.push(vec![x], x).unwrap();
+    }
+    // linking leaves trees behind, which are freed together with the queue
+    assert_eq!(queue.pop(), Ok((vec![0], 0)));
+    queue.decrease_priority(&vec![40], -1).unwrap();
+    queue.decrease_priority(&vec![41], -2).unwrap();
+    assert_eq!(queue.len(), 63);
+}
+
+#[test]
+fn melds_queues() {
+    let mut queue: RawQueue<_, _> = RawQueue::new();
+    let mut other = RawQueue::new();
+    for x in 0..10 {
+        queue.push(2 * x, 2 * x).unwrap();
+        other.push(2 * x + 1, 2 * x + 1).unwrap();
+    }
+    queue.pop().unwrap();
+    other.pop().unwrap();
+    queue.meld(other).unwrap();
+    let popped: Vec<_> = core::iter::from_fn(|| queue.pop().ok())
+        .map(|(x, _)| x)
+        .collect();
+    assert_eq!(popped, (2..20).collect::<Vec<_>>());
+    assert_eq!(queue.pop(), Err(Error::Empty));
+}
@@ -0,0 +1,82 @@
+#![cfg(feature = "serde")]
+
+use fbheap::{
+    arena::{ArenaHandle, ArenaQueue},
+    error::Error,
+};
+
+#[test]
+fn arena_queue_keeps_structure() -> Result<(), Error> {
+    let mut queue = ArenaQueue::new();
+    let handles = (0..20)
+        .map(|n| queue.push_with_handle(n, (n * 7) % 20))
+        .collect::<Result<Vec<_>, _>>()?;
+    queue.pop()?;
+    queue.delete_at(handles[5])?;
+    queue.push(100, 50)?;
+
+    let json = serde_json::to_string(&queue).unwrap();
+    let mut restored: ArenaQueue<i32, i32> = serde_json::from_str(&json).unwrap();
+    assert_eq!(serde_json::to_string(&restored).unwrap(), json);
+    assert_eq!(restored.len(), queue.len());
+
+    while !queue.is_empty() {
+        assert_eq!(restored.pop(), queue.pop());
+    }
+    assert!(restored.is_empty());
+    Ok(())
+}
+
+#[test]
+fn arena_queue_rejects_handles_from_before_serializing() -> Result<(), Error> {
+    let mut queue = ArenaQueue::new();
+    let handle = queue.push_with_handle(1, 1)?;
+    let json = serde_json::to_string(&(&queue, handle)).unwrap();
+
+    let (mut restored, handle): (ArenaQueue<i32, i32>, ArenaHandle) =
+        serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.delete_at(handle), Err(Error::NotFound("handle")));
+    // handles given out by the restored queue do not reach into the original one either
+    let restored_handle = restored.push_with_handle(2, 2)?;
+    assert_eq!(queue.delete_at(restored_handle), Err(Error::NotFound("handle")));
+    assert_eq!(queue.delete_at(handle), Ok((1, 1)));
+    Ok(())
+}
+
+#[test]
+fn arena_queue_rejects_broken_structure() {
+    let broken = [
+        // generations missing
+        r#"[[[1,1,null,[],false]],[],[0]]"#,
+        // root listed twice
+        r#"[[[1,1,null,[],false]],[0],[0,0]]"#,
+        // node not reachable from any root
+        r#"[[[1,1,null,[],false],[2,2,null,[],false]],[0,0],[0]]"#,
+        // child without matching parent link
+        r#"[[[1,1,null,[1],false],[2,2,null,[],false]],[0,0],[0]]"#,
+        // child ordered before its parent
+        r#"[[[1,3,null,[1],false],[2,2,0,[],false]],[0,0],[0]]"#,
+        // root pointing at a vacant slot
+        r#"[[null],[0],[0]]"#,
+    ];
+    for json in broken {
+        assert!(
+            serde_json::from_str::<ArenaQueue<i32, i32>>(json).is_err(),
+            "{json}"
+        );
+    }
+}
+
+#[test]
+fn arena_queue_retires_exhausted_slots() -> Result<(), Error> {
+    // the slot at index 0 was released with its last generation, the one at index 1 can be reused
+    let json = r#"[[null,null,[7,7,null,[],false]],[4294967295,3,0],[2]]"#;
+    let mut queue: ArenaQueue<i32, i32> = serde_json::from_str(json).unwrap();
+    queue.push(1, 1)?;
+    queue.push(2, 2)?;
+    assert_eq!(
+        serde_json::to_string(&queue).unwrap(),
+        r#"[[null,[1,1,null,[],false],[7,7,null,[],false],[2,2,null,[],false]],[4294967295,3,0,0],[2,1,3]]"#
+    );
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use fbheap::{heap::BareQueue, persistent::PersistentQueue};
+
+/// build a single tree whose spine is a path of marked nodes, one node per round,
+/// so that cutting the bottom of the path cascades through every node on it
+fn marked_path(queue: &mut BareQueue<i64, i64>, rounds: i64) {
+    // a root with a single child, values are negated priorities
+    queue.push(0, 0);
+    queue.push(1, -1);
+    queue.push(2, -2);
+    assert_eq!(queue.pop(), Ok((2, -2)));
+
+    for round in 1..=rounds {
+        let (x, y, z) = (3 * round + 3, 3 * round + 2, 3 * round + 1);
+        queue.push(x, -x);
+        queue.push(y, -y);
+        queue.push(z, -z);
+        // links z below y, then the previous path below y
+        assert_eq!(queue.pop(), Ok((x, -x)));
+        // cutting z marks y, which stays marked once it is linked below the next root
+        queue.decrease_priority(&z, i64::MIN).unwrap();
+        assert_eq!(queue.pop(), Ok((z, i64::MIN)));
+    }
+}
+
+#[test]
+fn cascading_cut_of_long_path() {
+    let rounds = 200_000;
+    let mut queue = BareQueue::new();
+    marked_path(&mut queue, rounds);
+    assert_eq!(queue.len(), rounds as usize + 2);
+    queue.assert_valid();
+
+    let trace = queue.explain_decrease(&1, i64::MIN).unwrap();
+    assert_eq!(trace.steps.len(), rounds as usize);
+    assert_eq!(queue.pop(), Ok((1, i64::MIN)));
+    queue.assert_valid();
+
+    let mut last = i64::MIN;
+    for (_, priority) in queue {
+        assert!(last <= priority);
+        last = priority;
+    }
+}
+
+#[test]
+fn dropping_long_path() {
+    let mut queue = BareQueue::new();
+    marked_path(&mut queue, 200_000);
+    drop(queue);
+}
+
+#[test]
+fn clearing_long_path() {
+    let mut queue = BareQueue::new();
+    marked_path(&mut queue, 200_000);
+    queue.clear();
+    assert!(queue.is_empty());
+    queue.push(0, 0);
+    assert_eq!(queue.pop(), Ok((0, 0)));
+}
+
+#[test]
+fn dropping_long_persistent_path() {
+    // every push of a lower priority puts the whole queue below the new root on the left
+    let mut queue = PersistentQueue::new();
+    for priority in (0..200_000).rev() {
+        queue = queue.push(priority, priority).unwrap();
+    }
+    let snapshot = queue.clone();
+    drop(queue);
+    assert_eq!(snapshot.peek(), Some((&0, &0)));
+    drop(snapshot);
+}
+
+#[cfg(feature = "instrument")]
+#[test]
+fn counting_cascading_cuts() {
+    let rounds = 1_000;
+    let mut queue = BareQueue::new();
+    marked_path(&mut queue, rounds);
+    queue.reset_metrics();
+    queue.decrease_priority(&1, i64::MIN).unwrap();
+    let metrics = queue.metrics();
+    assert_eq!(metrics.cuts, rounds as usize);
+    assert_eq!(metrics.cascading_cuts, rounds as usize - 1);
+    assert_eq!(metrics.links, 0);
+    assert_eq!(metrics.consolidations, 0);
+}
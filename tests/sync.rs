@@ -0,0 +1,48 @@
+#![cfg(feature = "std")]
+
+use fbheap::{error::Error, sync::SyncQueue};
+use std::time::Duration;
+
+#[test]
+fn waiting_workers_take_every_job() {
+    let jobs = SyncQueue::new();
+    let workers = 4;
+    let mut done: Vec<u32> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    // every worker stops at the first sentinel it pops, which sort after all jobs
+                    while let Ok((Some(job), _)) = jobs.pop_wait() {
+                        done.push(job);
+                    }
+                    done
+                })
+            })
+            .collect();
+        for job in 0..1000 {
+            jobs.push(Some(job), job).unwrap();
+        }
+        for sentinel in 0..workers {
+            jobs.push(None, u32::MAX - sentinel).unwrap();
+        }
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect()
+    });
+    done.sort_unstable();
+    assert_eq!(done, (0..1000).collect::<Vec<_>>());
+    assert_eq!(jobs.is_empty(), Ok(true));
+}
+
+#[test]
+fn timing_out_on_empty_queue() {
+    let queue = SyncQueue::<u8, u8>::new();
+    assert_eq!(
+        queue.pop_timeout(Duration::from_millis(10)),
+        Err(Error::Empty)
+    );
+    queue.push(1, 1).unwrap();
+    assert_eq!(queue.pop_timeout(Duration::ZERO), Ok((1, 1)));
+}
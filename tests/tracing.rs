@@ -0,0 +1,88 @@
+#![cfg(all(feature = "tracing", feature = "std"))]
+
+use fbheap::heap::BareQueue;
+use std::{
+    fmt::Debug,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+use tracing::{
+    field::{Field, Visit},
+    span, Event, Metadata, Subscriber,
+};
+
+/// subscriber writing down the names of spans and the fields of events, one line per event
+#[derive(Clone, Default)]
+struct Recorder {
+    spans: Arc<Mutex<Vec<&'static str>>>,
+    events: Arc<Mutex<Vec<String>>>,
+    next_id: Arc<AtomicU64>,
+}
+
+struct Fields(String);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0 += &format!("{}={value:?} ", field.name());
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &span::Attributes<'_>) -> span::Id {
+        self.spans.lock().unwrap().push(span.metadata().name());
+        span::Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+    }
+
+    fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+    fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields(String::new());
+        event.record(&mut fields);
+        self.events.lock().unwrap().push(fields.0);
+    }
+
+    fn enter(&self, _: &span::Id) {}
+
+    fn exit(&self, _: &span::Id) {}
+}
+
+#[test]
+fn consolidation_reports_incoming_ranks() {
+    let recorder = Recorder::default();
+    tracing::subscriber::with_default(recorder.clone(), || {
+        let mut queue: BareQueue<_, _> = (0..8).map(|value| (value, value)).collect();
+        queue.pop().unwrap();
+    });
+    assert_eq!(*recorder.spans.lock().unwrap(), vec!["pop"]);
+    assert_eq!(
+        *recorder.events.lock().unwrap(),
+        vec!["message=consolidated roots nodes=7 roots_before=7 roots_after=3 incoming_ranks=[7] "]
+    );
+}
+
+#[test]
+fn cuts_are_reported_within_decreases() {
+    let recorder = Recorder::default();
+    let mut queue: BareQueue<_, _> = (0..8).map(|value| (value, value)).collect();
+    queue.pop().unwrap();
+    tracing::subscriber::with_default(recorder.clone(), || {
+        queue.decrease_priority(&6, 0).unwrap();
+        queue.decrease_priority(&7, 0).unwrap();
+    });
+    assert_eq!(
+        *recorder.spans.lock().unwrap(),
+        vec!["decrease_priority", "decrease_priority"]
+    );
+    assert_eq!(
+        *recorder.events.lock().unwrap(),
+        vec!["message=cut node cascading=false rank=0 "]
+    );
+}